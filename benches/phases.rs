@@ -0,0 +1,340 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-GARAGE. All rights reserved.
+
+//! PLONK Prover and Verifier Phase Benchmarks
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::PrimeField;
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_poly_commit::LabeledPolynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use core::marker::PhantomData;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use plonk::commitment::{HomomorphicCommitment, KZG10};
+use plonk::prelude::*;
+use plonk::proof_system::{Prover, Verifier};
+use rand::rngs::OsRng;
+
+const MINIMUM_DEGREE: usize = 8;
+const MAXIMUM_DEGREE: usize = 16;
+
+/// Number of polynomials committed to by the prover: the four wires, the
+/// permutation polynomial and the four quotient pieces.
+const COMMITTED_POLYNOMIALS: usize = 9;
+
+/// Number of proofs created in a row with the same prover key, so that the
+/// work cached in the key is amortized over them.
+const REPEATED_PROOFS: usize = 4;
+
+/// Preprocessed circuit, keys and proof for a given circuit size.
+struct Fixture<F, P, HC>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    HC: HomomorphicCommitment<F>,
+{
+    degree: usize,
+    commit_key: HC::CommitterKey,
+    verifier_key: HC::VerifierKey,
+    prover: Prover<F, P, HC>,
+    prover_key: ProverKey<F>,
+    verifier: Verifier<F, P, HC>,
+    public_inputs: Vec<F>,
+    proof: Proof<F, HC>,
+}
+
+/// Fills `composer` with dummy constraints up to `2^degree` gates.
+fn fill_circuit<F, P>(composer: &mut StandardComposer<F, P>, degree: usize)
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    while composer.circuit_size() < (1 << degree) - 1 {
+        composer.add_dummy_constraints();
+    }
+}
+
+fn bls12_381_benchmarks(c: &mut Criterion) {
+    phase_benchmarks::<
+        <Bls12_381 as PairingEngine>::Fr,
+        ark_ed_on_bls12_381::EdwardsParameters,
+        KZG10<Bls12_381>,
+    >("Bls12_381", c);
+}
+
+fn bls12_377_benchmarks(c: &mut Criterion) {
+    phase_benchmarks::<
+        <Bls12_377 as PairingEngine>::Fr,
+        ark_ed_on_bls12_377::EdwardsParameters,
+        KZG10<Bls12_377>,
+    >("Bls12_377", c);
+}
+
+/// Generates benchmarks for the quotient computation, proving, verifying and
+/// proof serialization. The public parameters are sampled once and trimmed
+/// for each circuit size so that setup is never measured.
+fn phase_benchmarks<F, P, HC>(name: &str, c: &mut Criterion)
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    HC: HomomorphicCommitment<F>,
+{
+    let pp = HC::setup(1 << MAXIMUM_DEGREE, None, &mut OsRng)
+        .expect("Unable to sample public parameters.");
+
+    let fixtures: Vec<Fixture<F, P, HC>> = (MINIMUM_DEGREE..=MAXIMUM_DEGREE)
+        .map(|degree| {
+            let (commit_key, verifier_key) =
+                HC::trim(&pp, 1 << degree, 0, None)
+                    .expect("Unable to trim public parameters.");
+
+            let mut prover = Prover::<F, P, HC>::new(b"phases");
+            fill_circuit(prover.mut_cs(), degree);
+            prover
+                .preprocess(&commit_key)
+                .expect("Unable to preprocess circuit.");
+            let prover_key = prover.prover_key.clone().unwrap();
+            let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+            let proof = prover
+                .prove_with_preprocessed(&commit_key, &prover_key, PhantomData)
+                .expect("Unable to prove benchmark circuit.");
+
+            let mut verifier = Verifier::<F, P, HC>::new(b"phases");
+            fill_circuit(verifier.mut_cs(), degree);
+            verifier
+                .preprocess(&commit_key)
+                .expect("Unable to preprocess circuit.");
+
+            Fixture {
+                degree,
+                commit_key,
+                verifier_key,
+                prover,
+                prover_key,
+                verifier,
+                public_inputs,
+                proof,
+            }
+        })
+        .collect();
+
+    let mut commit_benchmarks = c.benchmark_group(format!("{0}/commit", name));
+    for fixture in &fixtures {
+        let polys = (0..COMMITTED_POLYNOMIALS)
+            .map(|_| {
+                DensePolynomial::<F>::rand(1 << fixture.degree, &mut OsRng)
+            })
+            .collect::<Vec<_>>();
+        let labeled_polys = polys
+            .iter()
+            .map(|poly| {
+                LabeledPolynomial::new(
+                    "poly".to_owned(),
+                    poly.clone(),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        commit_benchmarks.bench_with_input(
+            BenchmarkId::new("individual", fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    labeled_polys
+                        .iter()
+                        .map(|poly| {
+                            HC::commit(&fixture.commit_key, [poly], None)
+                                .unwrap()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        // `batch_commit` still computes one multi-scalar multiplication per
+        // polynomial, so it is expected to match the loop above.
+        commit_benchmarks.bench_with_input(
+            BenchmarkId::new("batch", fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    HC::batch_commit(&fixture.commit_key, &polys).unwrap()
+                })
+            },
+        );
+    }
+    commit_benchmarks.finish();
+
+    let mut quotient_benchmarks =
+        c.benchmark_group(format!("{0}/quotient", name));
+    for fixture in &fixtures {
+        let compute_quotient = fixture
+            .prover
+            .quotient_poly_bench(&fixture.prover_key)
+            .expect("Unable to build quotient inputs.");
+        quotient_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| b.iter(|| compute_quotient().unwrap()),
+        );
+    }
+    quotient_benchmarks.finish();
+
+    let mut proving_benchmarks = c.benchmark_group(format!("{0}/prove", name));
+    for fixture in &fixtures {
+        proving_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    fixture
+                        .prover
+                        .prove_with_preprocessed(
+                            &fixture.commit_key,
+                            &fixture.prover_key,
+                            PhantomData,
+                        )
+                        .unwrap()
+                })
+            },
+        );
+    }
+    proving_benchmarks.finish();
+
+    let mut repeated_proving_benchmarks =
+        c.benchmark_group(format!("{0}/prove_repeated", name));
+    for fixture in &fixtures {
+        repeated_proving_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    (0..REPEATED_PROOFS)
+                        .map(|_| {
+                            fixture
+                                .prover
+                                .prove_with_preprocessed(
+                                    &fixture.commit_key,
+                                    &fixture.prover_key,
+                                    PhantomData,
+                                )
+                                .unwrap()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+    }
+    repeated_proving_benchmarks.finish();
+
+    let mut verifying_benchmarks =
+        c.benchmark_group(format!("{0}/verify", name));
+    for fixture in &fixtures {
+        verifying_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    fixture
+                        .verifier
+                        .verify(
+                            &fixture.proof,
+                            &fixture.verifier_key,
+                            &fixture.public_inputs,
+                        )
+                        .expect("Unable to verify benchmark circuit.")
+                })
+            },
+        );
+    }
+    verifying_benchmarks.finish();
+
+    let mut serialization_benchmarks =
+        c.benchmark_group(format!("{0}/serialize", name));
+    for fixture in &fixtures {
+        let proof = &fixture.proof;
+        serialization_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    let mut bytes = Vec::with_capacity(proof.serialized_size());
+                    proof.serialize(&mut bytes).unwrap();
+                    bytes
+                })
+            },
+        );
+    }
+    serialization_benchmarks.finish();
+
+    let mut deserialization_benchmarks =
+        c.benchmark_group(format!("{0}/deserialize", name));
+    for fixture in &fixtures {
+        let mut proof_bytes = Vec::new();
+        fixture.proof.serialize(&mut proof_bytes).unwrap();
+        deserialization_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    Proof::<F, HC>::deserialize(proof_bytes.as_slice()).unwrap()
+                })
+            },
+        );
+    }
+    deserialization_benchmarks.finish();
+
+    let mut batch_checked_deserialization_benchmarks =
+        c.benchmark_group(format!("{0}/deserialize_batch_checked", name));
+    for fixture in &fixtures {
+        let proof_bytes = fixture.proof.to_bytes();
+        batch_checked_deserialization_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    Proof::<F, HC>::deserialize_batch_checked(
+                        proof_bytes.as_slice(),
+                        &mut OsRng,
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+    batch_checked_deserialization_benchmarks.finish();
+
+    let mut unchecked_deserialization_benchmarks =
+        c.benchmark_group(format!("{0}/deserialize_unchecked", name));
+    for fixture in &fixtures {
+        let mut proof_bytes = Vec::new();
+        fixture.proof.serialize(&mut proof_bytes).unwrap();
+        unchecked_deserialization_benchmarks.bench_with_input(
+            BenchmarkId::from_parameter(fixture.degree),
+            &fixture.degree,
+            |b, _| {
+                b.iter(|| {
+                    Proof::<F, HC>::unchecked_deserialize(
+                        proof_bytes.as_slice(),
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+    unchecked_deserialization_benchmarks.finish();
+}
+
+criterion_group! {
+    name = phases;
+    config = Criterion::default().sample_size(10);
+    targets = bls12_381_benchmarks, bls12_377_benchmarks
+}
+criterion_main!(phases);
@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) ZK-GARAGE. All rights reserved.
+
+//! Verification from serialized data, as done by a verifier built for
+//! `wasm32-unknown-unknown`.
+//!
+//! Verification needs no random source, so the [`verify`] function only
+//! requires `plonk-core` to be built without default features:
+//!
+//! ```text
+//! cargo build -p plonk-core --no-default-features \
+//!     --target wasm32-unknown-unknown
+//! ```
+//!
+//! A WASM module would export [`verify`] as is. The `main` function below
+//! plays the part of the prover, which does need the `prover` feature, to
+//! produce the bytes it is given.
+
+use ark_bls12_381::{Bls12_381, Fr as BlsScalar};
+use ark_ed_on_bls12_381::EdwardsParameters as JubJubParameters;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{sonic_pc::SonicKZG10, PolynomialCommitment};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use plonk_core::prelude::*;
+use plonk_core::proof_system::{Prover, Verifier};
+use rand::rngs::OsRng;
+
+type PC = SonicKZG10<Bls12_381, DensePolynomial<BlsScalar>>;
+
+/// Transcript label shared by the prover and the verifier.
+const LABEL: &[u8] = b"verify_only";
+
+/// Adds a gate constraining `a + b` to the public input `c`.
+fn gadget(
+    composer: &mut StandardComposer<BlsScalar, JubJubParameters>,
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+) {
+    let a = composer.add_input(a);
+    let b = composer.add_input(b);
+    let zero = composer.zero_var();
+    composer.arithmetic_gate(|gate| {
+        gate.witness(a, b, Some(zero))
+            .add(BlsScalar::from(1u64), BlsScalar::from(1u64))
+            .pi(-c)
+    });
+}
+
+/// Verifies the serialized `proof` against the serialized PLONK
+/// `verifier_key`, commitment scheme verifier key `pc_verifier_key` and
+/// `public_inputs`.
+fn verify(
+    verifier_key: &[u8],
+    pc_verifier_key: &[u8],
+    proof: &[u8],
+    public_inputs: &[u8],
+) -> Result<(), Error> {
+    let mut verifier = Verifier::<BlsScalar, JubJubParameters, PC>::new(LABEL);
+    verifier.verifier_key = Some(VerifierKey::deserialize(verifier_key)?);
+    let pc_verifier_key =
+        <PC as PolynomialCommitment<_, _>>::VerifierKey::deserialize(
+            pc_verifier_key,
+        )?;
+    let proof = Proof::deserialize(proof)?;
+    let public_inputs = Vec::<BlsScalar>::deserialize(public_inputs)?;
+    verifier.verify(&proof, &pc_verifier_key, &public_inputs)
+}
+
+fn main() -> Result<(), Error> {
+    let pp = PC::setup(1 << 6, None, &mut OsRng)
+        .expect("Unable to sample public parameters.");
+    let (ck, vk) = PC::trim(&pp, 1 << 5, 0, None)
+        .expect("Unable to trim public parameters.");
+
+    let mut prover = Prover::<BlsScalar, JubJubParameters, PC>::new(LABEL);
+    gadget(
+        prover.mut_cs(),
+        BlsScalar::from(2u64),
+        BlsScalar::from(3u64),
+        BlsScalar::from(5u64),
+    );
+    let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+    let proof = prover.prove(&ck)?;
+
+    let mut verifier = Verifier::<BlsScalar, JubJubParameters, PC>::new(LABEL);
+    gadget(
+        verifier.mut_cs(),
+        BlsScalar::from(0u64),
+        BlsScalar::from(0u64),
+        BlsScalar::from(0u64),
+    );
+    verifier.preprocess(&ck)?;
+
+    let verifier_key = verifier.verifier_key.expect("preprocessed above");
+    let mut verifier_key_bytes = Vec::new();
+    verifier_key.serialize(&mut verifier_key_bytes)?;
+    let mut pc_verifier_key_bytes = Vec::new();
+    vk.serialize(&mut pc_verifier_key_bytes)?;
+    let mut proof_bytes = Vec::new();
+    proof.serialize(&mut proof_bytes)?;
+    let mut public_inputs_bytes = Vec::new();
+    public_inputs.serialize(&mut public_inputs_bytes)?;
+
+    verify(
+        &verifier_key_bytes,
+        &pc_verifier_key_bytes,
+        &proof_bytes,
+        &public_inputs_bytes,
+    )
+}
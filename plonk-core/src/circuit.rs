@@ -13,8 +13,9 @@ use crate::{
     proof_system::{Proof, Prover, ProverKey, Verifier, VerifierKey},
 };
 use ark_ec::models::TEModelParameters;
-use ark_ff::{Field, PrimeField, ToConstraintField};
+use ark_ff::{BigInteger, Field, PrimeField, ToConstraintField};
 use ark_serialize::*;
+use core::marker::PhantomData;
 
 /// Public Input Builder
 #[derive(derivative::Derivative)]
@@ -61,6 +62,162 @@ where
     }
 }
 
+/// Layout of the flat public input values of a circuit, as given to
+/// [`verify_proof`], as a sequence of logical values split into limbs.
+///
+/// Values wider than the field, e.g. 256-bit hash digests, cannot be exposed
+/// as a single public input and are split by the circuit into limbs of a
+/// fixed number of bits. The layout records how many bits each value and
+/// each of its limbs have, so that both sides agree on the order of the
+/// limbs: every value is split into limbs from the least significant one up,
+/// and values are laid out one after the other.
+///
+/// Values are read and written as little-endian bytes.
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Default(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct PublicInputLayout<F>
+where
+    F: PrimeField,
+{
+    /// Number of bits of each value, and of each of its limbs.
+    values: Vec<(usize, usize)>,
+
+    /// Type Parameter Marker
+    __: PhantomData<F>,
+}
+
+impl<F> PublicInputLayout<F>
+where
+    F: PrimeField,
+{
+    /// Creates an empty layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a value of `num_bits` bits split into limbs of `limb_bits`
+    /// bits, the last limb holding the remaining most significant bits.
+    ///
+    /// # Panics
+    /// This function will panic if `limb_bits` is zero or if a limb of
+    /// `limb_bits` bits might not fit in the field, ie.
+    /// `limb_bits >= F::size_in_bits()`.
+    pub fn value(mut self, num_bits: usize, limb_bits: usize) -> Self {
+        assert!(
+            limb_bits > 0 && limb_bits < F::size_in_bits(),
+            "limbs of {} bits do not fit in the field",
+            limb_bits
+        );
+        self.values.push((num_bits, limb_bits));
+        self
+    }
+
+    /// Returns the number of logical values of the layout.
+    pub fn num_values(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the number of public input values the layout spans.
+    pub fn num_limbs(&self) -> usize {
+        self.values
+            .iter()
+            .map(|(num_bits, limb_bits)| num_limbs(*num_bits, *limb_bits))
+            .sum()
+    }
+
+    /// Splits `values`, given as little-endian bytes, into the flat public
+    /// input values of the layout.
+    ///
+    /// Returns [`Error::PublicInputLayoutMismatch`] if the number of values
+    /// differs from the one of the layout, and
+    /// [`Error::PublicInputValueTooLarge`] if a value has more bits than the
+    /// layout assigns to it.
+    pub fn build<B>(&self, values: &[B]) -> Result<Vec<F>, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        if values.len() != self.values.len() {
+            return Err(Error::PublicInputLayoutMismatch {
+                expected: self.values.len(),
+                found: values.len(),
+            });
+        }
+        let mut limbs = Vec::with_capacity(self.num_limbs());
+        for (index, (value, (num_bits, limb_bits))) in
+            values.iter().zip(&self.values).enumerate()
+        {
+            let mut bits = value
+                .as_ref()
+                .iter()
+                .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+                .collect::<Vec<_>>();
+            if bits.iter().skip(*num_bits).any(|bit| *bit) {
+                return Err(Error::PublicInputValueTooLarge { index });
+            }
+            bits.resize(*num_bits, false);
+            limbs.extend(bits.chunks(*limb_bits).map(|limb| {
+                F::from_repr(F::BigInt::from_bits_le(limb))
+                    .expect("limbs are smaller than the modulus")
+            }));
+        }
+        Ok(limbs)
+    }
+
+    /// Reassembles the logical values of the layout, as little-endian bytes,
+    /// from the flat public input values `pub_inputs`.
+    ///
+    /// Returns [`Error::PublicInputLayoutMismatch`] if the number of public
+    /// input values differs from the one the layout spans, and
+    /// [`Error::PublicInputValueTooLarge`] with the index of the offending
+    /// public input value if a limb has more bits than the layout assigns
+    /// to it.
+    pub fn parse(&self, pub_inputs: &[F]) -> Result<Vec<Vec<u8>>, Error> {
+        if pub_inputs.len() != self.num_limbs() {
+            return Err(Error::PublicInputLayoutMismatch {
+                expected: self.num_limbs(),
+                found: pub_inputs.len(),
+            });
+        }
+        let mut limbs = pub_inputs.iter().enumerate();
+        let mut values = Vec::with_capacity(self.values.len());
+        for (num_bits, limb_bits) in &self.values {
+            let mut bits = Vec::with_capacity(*num_bits);
+            for (index, limb) in
+                limbs.by_ref().take(num_limbs(*num_bits, *limb_bits))
+            {
+                let width = (*num_bits - bits.len()).min(*limb_bits);
+                let limb = limb.into_repr().to_bits_le();
+                if limb.iter().skip(width).any(|bit| *bit) {
+                    return Err(Error::PublicInputValueTooLarge { index });
+                }
+                bits.extend_from_slice(&limb[..width]);
+            }
+            values.push(
+                bits.chunks(8)
+                    .map(|byte| {
+                        byte.iter()
+                            .rev()
+                            .fold(0u8, |acc, bit| (acc << 1) | *bit as u8)
+                    })
+                    .collect(),
+            );
+        }
+        Ok(values)
+    }
+}
+
+/// Returns the number of limbs of `limb_bits` bits a value of `num_bits` bits
+/// is split into.
+fn num_limbs(num_bits: usize, limb_bits: usize) -> usize {
+    (num_bits + limb_bits - 1) / limb_bits
+}
+
 /// Collection of structs/objects that the Verifier will use in order to
 /// de/serialize data needed for Circuit proof verification.
 /// This structure can be seen as a link between the [`Circuit`] public input
@@ -393,7 +550,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{constraint_system::StandardComposer, util};
+    use crate::{batch_test_field, constraint_system::StandardComposer, util};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
     use ark_ec::{
@@ -539,6 +696,72 @@ mod test {
         Ok(())
     }
 
+    fn test_public_input_layout<F>()
+    where
+        F: PrimeField,
+    {
+        let layout = PublicInputLayout::<F>::new().value(256, 128).value(20, 8);
+        assert_eq!(layout.num_values(), 2);
+        assert_eq!(layout.num_limbs(), 5);
+
+        let digest = (0..32u8).map(|i| 0xf0 ^ i).collect::<Vec<_>>();
+        let small = vec![0x34, 0x12, 0x0a];
+        let pub_inputs = layout.build(&[&digest, &small]).unwrap();
+
+        // Limbs are laid out from the least significant one up
+        let low = F::from_le_bytes_mod_order(&digest[..16]);
+        let high = F::from_le_bytes_mod_order(&digest[16..]);
+        assert_eq!(
+            pub_inputs,
+            [
+                low,
+                high,
+                F::from(0x34u64),
+                F::from(0x12u64),
+                F::from(0x0au64)
+            ]
+        );
+        assert_eq!(layout.parse(&pub_inputs).unwrap(), [digest.clone(), small]);
+
+        // Values and limbs must fit their widths
+        assert!(matches!(
+            layout.build(&[vec![0u8; 32], vec![0, 0, 0x10]]),
+            Err(Error::PublicInputValueTooLarge { index: 1 })
+        ));
+        let mut too_large = pub_inputs.clone();
+        too_large[1] = F::from(1u64) + F::from(u128::MAX);
+        assert!(matches!(
+            layout.parse(&too_large),
+            Err(Error::PublicInputValueTooLarge { index: 1 })
+        ));
+
+        // And their number must match the layout
+        assert!(matches!(
+            layout.parse(&pub_inputs[..4]),
+            Err(Error::PublicInputLayoutMismatch {
+                expected: 5,
+                found: 4
+            })
+        ));
+        assert!(matches!(
+            layout.build(&[&digest]),
+            Err(Error::PublicInputLayoutMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    batch_test_field!(
+        [test_public_input_layout],
+        [] => (Bls12_381)
+    );
+
+    batch_test_field!(
+        [test_public_input_layout],
+        [] => (Bls12_377)
+    );
+
     #[test]
     #[allow(non_snake_case)]
     fn test_full_on_Bls12_381() -> Result<(), Error> {
@@ -1,8 +1,72 @@
 //! Useful commitment stuff
-use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine};
-use ark_ff::{Field, PrimeField};
+use crate::util::{check_span, group_ops_span};
+use ark_ec::{
+    msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve,
+};
+use ark_ff::{Field, One, PrimeField, Zero};
 use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::{sonic_pc::SonicKZG10, PolynomialCommitment};
+use ark_poly_commit::{
+    sonic_pc::SonicKZG10, LabeledCommitment, LabeledPolynomial, PCCommitterKey,
+    PCVerifierKey, PolynomialCommitment,
+};
+use ark_serialize::{CanonicalDeserialize, Read, SerializationError};
+use rand::RngCore;
+
+/// Batch opening proof of several commitments at a single point, as checked
+/// by [`PolynomialCommitment::check`].
+pub struct OpeningGroup<'a, F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Point at which the polynomials are opened.
+    pub point: F,
+
+    /// Commitments to the opened polynomials.
+    pub commitments: &'a [LabeledCommitment<PC::Commitment>],
+
+    /// Claimed evaluations of the polynomials at `point`, in the same order
+    /// as their commitments.
+    pub evaluations: &'a [F],
+
+    /// Batch opening proof.
+    pub opening: &'a PC::Proof,
+
+    /// Challenge the polynomials are combined with in `opening`.
+    pub challenge: F,
+}
+
+/// Checks each of the `groups` on its own with
+/// [`PolynomialCommitment::check`].
+pub(crate) fn check_each<F, PC>(
+    vk: &PC::VerifierKey,
+    groups: &[OpeningGroup<'_, F, PC>],
+) -> Result<bool, PC::Error>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    for group in groups {
+        check_span!(group.commitments.len());
+        group_ops_span!(
+            PC::check_msm_terms(vk, group.commitments.len()),
+            PC::PAIRINGS_PER_CHECK
+        );
+        let valid = PC::check(
+            vk,
+            group.commitments,
+            &group.point,
+            group.evaluations.iter().copied(),
+            group.opening,
+            group.challenge,
+            None,
+        )?;
+        if !valid {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
 
 /// A homomorphic polynomial commitment
 pub trait HomomorphicCommitment<F>:
@@ -11,11 +75,157 @@ where
     F: PrimeField,
     Self::VerifierKey: std::fmt::Debug,
 {
+    /// Group of the points of the commitments and opening proofs.
+    type Point: AffineCurve<ScalarField = F>;
+
+    /// Number of pairings computed by [`PolynomialCommitment::check`] to
+    /// check a single batch opening proof.
+    const PAIRINGS_PER_CHECK: usize;
+
+    /// Whether the cofactor of [`HomomorphicCommitment::Point`] has no small
+    /// prime factor, so that
+    /// [`HomomorphicCommitment::batch_subgroup_check`] may check all of the
+    /// points at once.
+    ///
+    /// `false` by default, and for the schemes of this crate, which can be
+    /// instantiated over the G1 groups of BLS12-381 and BLS12-377, whose
+    /// cofactors are divisible by `3` and `2` respectively.
+    const BATCH_SUBGROUP_CHECK: bool = false;
+
+    /// Number of pairings computed by
+    /// [`HomomorphicCommitment::check_multi_point`] to check `num_groups`
+    /// batch opening proofs at once.
+    fn multi_point_pairings(num_groups: usize) -> usize {
+        num_groups * Self::PAIRINGS_PER_CHECK
+    }
+
+    /// Number of terms of the multi-scalar multiplications over
+    /// [`HomomorphicCommitment::Point`] computed by
+    /// [`PolynomialCommitment::check`] to check a batch opening proof of
+    /// `num_commitments` commitments.
+    ///
+    /// By default, only the combination of the commitments is counted.
+    fn check_msm_terms(
+        vk: &Self::VerifierKey,
+        num_commitments: usize,
+    ) -> usize {
+        let _ = vk;
+        num_commitments
+    }
+
+    /// Number of terms of the multi-scalar multiplications over
+    /// [`HomomorphicCommitment::Point`] computed by
+    /// [`HomomorphicCommitment::check_multi_point`] to check batch opening
+    /// proofs of `group_sizes` commitments each.
+    fn multi_point_msm_terms(
+        vk: &Self::VerifierKey,
+        group_sizes: &[usize],
+    ) -> usize {
+        group_sizes
+            .iter()
+            .map(|&num_commitments| Self::check_msm_terms(vk, num_commitments))
+            .sum()
+    }
+
+    /// Checks several batch opening proofs, each at its own point, returning
+    /// whether all of them are valid.
+    ///
+    /// Schemes which permit it combine the checks of the `groups` with the
+    /// powers of `separator` into a single one, which must then be drawn
+    /// after all of the `groups` are fixed. By default, each group is checked
+    /// on its own and `separator` is unused.
+    fn check_multi_point(
+        vk: &Self::VerifierKey,
+        groups: &[OpeningGroup<'_, F, Self>],
+        separator: F,
+    ) -> Result<bool, Self::Error> {
+        let _ = separator;
+        check_each(vk, groups)
+    }
+
+    /// Returns the maximum degree of the polynomials `commit_key` can commit
+    /// to.
+    fn max_supported_degree(commit_key: &Self::CommitterKey) -> usize {
+        commit_key.supported_degree()
+    }
+
     /// Combine a linear combination of homomorphic commitments
     fn multi_scalar_mul(
         commitments: &[Self::Commitment],
         scalars: &[F],
     ) -> Self::Commitment;
+
+    /// Checks that all of `points` lie in the prime order subgroup,
+    /// returning `false` if any of them does not.
+    ///
+    /// With [`HomomorphicCommitment::BATCH_SUBGROUP_CHECK`], only a random
+    /// linear combination of the points is checked, with coefficients sampled
+    /// from `rng`, which saves one scalar multiplication by the group order
+    /// per point. Otherwise, the points are checked one by one and `rng` is
+    /// unused.
+    fn batch_subgroup_check<R>(points: &[Self::Point], rng: &mut R) -> bool
+    where
+        R: RngCore,
+    {
+        if Self::BATCH_SUBGROUP_CHECK {
+            combined_subgroup_check(points, rng)
+        } else {
+            points.iter().all(|point| {
+                point.into_projective().mul(F::characteristic()).is_zero()
+            })
+        }
+    }
+
+    /// Deserializes a commitment like [`CanonicalDeserialize::deserialize`],
+    /// except that its points are not checked to lie in the prime order
+    /// subgroup but pushed to `points`, to be checked all at once with
+    /// [`HomomorphicCommitment::batch_subgroup_check`].
+    fn deserialize_commitment_deferred<R>(
+        reader: R,
+        points: &mut Vec<Self::Point>,
+    ) -> Result<Self::Commitment, SerializationError>
+    where
+        R: Read;
+
+    /// Deserializes an opening proof like
+    /// [`HomomorphicCommitment::deserialize_commitment_deferred`].
+    fn deserialize_proof_deferred<R>(
+        reader: R,
+        points: &mut Vec<Self::Point>,
+    ) -> Result<Self::Proof, SerializationError>
+    where
+        R: Read;
+
+    /// Commits to all of `polys` without hiding, returning their commitments
+    /// in the same order.
+    ///
+    /// This is a convenience over [`PolynomialCommitment::commit`], which
+    /// labels the polynomials and drops the unused randomness. Each
+    /// polynomial is still committed to with its own multi-scalar
+    /// multiplication, so it is no faster than committing to them one by
+    /// one.
+    fn batch_commit(
+        commit_key: &Self::CommitterKey,
+        polys: &[DensePolynomial<F>],
+    ) -> Result<Vec<Self::Commitment>, Self::Error> {
+        let labeled_polys = polys
+            .iter()
+            .enumerate()
+            .map(|(i, poly)| {
+                LabeledPolynomial::new(
+                    format!("poly_{}", i),
+                    poly.clone(),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, _) = Self::commit(commit_key, &labeled_polys, None)?;
+        Ok(commitments
+            .into_iter()
+            .map(|commitment| commitment.commitment().clone())
+            .collect())
+    }
 }
 
 /// The Default KZG-style commitment scheme
@@ -30,6 +240,10 @@ impl<E> HomomorphicCommitment<E::Fr> for KZG10<E>
 where
     E: PairingEngine,
 {
+    type Point = E::G1Affine;
+
+    const PAIRINGS_PER_CHECK: usize = 2;
+
     fn multi_scalar_mul(
         commitments: &[KZG10Commitment<E>],
         scalars: &[E::Fr],
@@ -46,6 +260,129 @@ where
                 .into(),
         )
     }
+
+    fn deserialize_commitment_deferred<R>(
+        reader: R,
+        points: &mut Vec<E::G1Affine>,
+    ) -> Result<KZG10Commitment<E>, SerializationError>
+    where
+        R: Read,
+    {
+        Ok(ark_poly_commit::kzg10::Commitment(read_point(
+            reader, points,
+        )?))
+    }
+
+    fn deserialize_proof_deferred<R>(
+        mut reader: R,
+        points: &mut Vec<E::G1Affine>,
+    ) -> Result<Self::Proof, SerializationError>
+    where
+        R: Read,
+    {
+        let w = read_point(&mut reader, points)?;
+        let random_v = CanonicalDeserialize::deserialize(&mut reader)?;
+        Ok(ark_poly_commit::kzg10::Proof { w, random_v })
+    }
+
+    fn multi_point_pairings(num_groups: usize) -> usize {
+        if num_groups == 0 {
+            0
+        } else {
+            Self::PAIRINGS_PER_CHECK
+        }
+    }
+
+    /// Counts, for each group, its commitments and opening in the left
+    /// multi-scalar multiplication and its opening in the right one, and
+    /// the two generators of the left one.
+    fn multi_point_msm_terms(
+        vk: &Self::VerifierKey,
+        group_sizes: &[usize],
+    ) -> usize {
+        let _ = vk;
+        if group_sizes.is_empty() {
+            0
+        } else {
+            group_sizes.iter().map(|size| size + 2).sum::<usize>() + 2
+        }
+    }
+
+    /// Checks all of the `groups` with a single product of two pairings.
+    ///
+    /// Each opening `w` at `z` of the combined commitment `C` to the value
+    /// `v` satisfies `e(C - v * g + z * w, h) = e(w, beta * h)`, hiding
+    /// openings subtracting their blinding `r * gamma_g` on the left. The
+    /// equations are summed up weighted by the powers of `separator`.
+    /// Commitments with degree bounds are not supported by the combined
+    /// check, and groups holding them are checked one by one.
+    fn check_multi_point(
+        vk: &Self::VerifierKey,
+        groups: &[OpeningGroup<'_, E::Fr, Self>],
+        separator: E::Fr,
+    ) -> Result<bool, Self::Error> {
+        let has_degree_bounds = groups.iter().any(|group| {
+            group
+                .commitments
+                .iter()
+                .any(|commitment| commitment.degree_bound().is_some())
+        });
+        if has_degree_bounds {
+            return check_each(vk, groups);
+        }
+        check_span!(groups
+            .iter()
+            .map(|group| group.commitments.len())
+            .sum::<usize>());
+
+        let mut bases = Vec::new();
+        let mut scalars = Vec::new();
+        let mut witnesses = Vec::new();
+        let mut weights = Vec::new();
+        let mut value = E::Fr::zero();
+        let mut blinding = E::Fr::zero();
+        let separators = crate::util::powers_of(separator);
+        for (group, weight) in groups.iter().zip(separators) {
+            if group.commitments.len() != group.evaluations.len() {
+                return Ok(false);
+            }
+            let powers = crate::util::powers_of(group.challenge);
+            for ((commitment, eval), power) in
+                group.commitments.iter().zip(group.evaluations).zip(powers)
+            {
+                bases.push(commitment.commitment().0);
+                scalars.push(weight * power);
+                value += weight * power * eval;
+            }
+            bases.push(group.opening.w);
+            scalars.push(weight * group.point);
+            if let Some(random_v) = group.opening.random_v {
+                blinding += weight * random_v;
+            }
+            witnesses.push(group.opening.w);
+            weights.push(weight.into_repr());
+        }
+        bases.push(vk.g);
+        scalars.push(-value);
+        bases.push(vk.gamma_g);
+        scalars.push(-blinding);
+        let scalars = scalars
+            .iter()
+            .map(PrimeField::into_repr)
+            .collect::<Vec<_>>();
+
+        group_ops_span!(
+            bases.len() + witnesses.len(),
+            Self::multi_point_pairings(groups.len())
+        );
+        let left = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+        let right = VariableBaseMSM::multi_scalar_mul(&witnesses, &weights);
+        let product = E::product_of_pairings(&[
+            (left.into_affine().into(), vk.prepared_h.clone()),
+            ((-right).into_affine().into(), vk.prepared_beta_h.clone()),
+        ]);
+        Ok(product.is_one())
+    }
 }
 
 /// Shortened type for Inner Product Argument polynomial commitment schemes
@@ -67,6 +404,23 @@ where
     G: AffineCurve,
     D: Digest,
 {
+    type Point = G;
+
+    const PAIRINGS_PER_CHECK: usize = 0;
+
+    /// Counts the combination of the commitments, the two round commitments
+    /// of each of the `log(d + 1)` rounds of the argument and the `d + 1`
+    /// points of the commitment key the final key is checked against, where
+    /// `d` is the supported degree of `vk`.
+    fn check_msm_terms(
+        vk: &Self::VerifierKey,
+        num_commitments: usize,
+    ) -> usize {
+        let key_size = PCVerifierKey::supported_degree(vk) + 1;
+        let rounds = key_size.next_power_of_two().trailing_zeros() as usize;
+        num_commitments + 2 * rounds + key_size
+    }
+
     fn multi_scalar_mul(
         commitments: &[IPACommitment<G, D>],
         scalars: &[<G as ark_ec::AffineCurve>::ScalarField],
@@ -88,6 +442,133 @@ where
             shifted_comm: None, // TODO: support degree bounds?
         }
     }
+
+    fn deserialize_commitment_deferred<R>(
+        mut reader: R,
+        points: &mut Vec<G>,
+    ) -> Result<IPACommitment<G, D>, SerializationError>
+    where
+        R: Read,
+    {
+        let comm = read_point(&mut reader, points)?;
+        let shifted_comm = read_optional_point(&mut reader, points)?;
+        Ok(IPACommitment::<G, D> { comm, shifted_comm })
+    }
+
+    fn deserialize_proof_deferred<R>(
+        mut reader: R,
+        points: &mut Vec<G>,
+    ) -> Result<Self::Proof, SerializationError>
+    where
+        R: Read,
+    {
+        let l_vec = read_points(&mut reader, points)?;
+        let r_vec = read_points(&mut reader, points)?;
+        let final_comm_key = read_point(&mut reader, points)?;
+        let c = CanonicalDeserialize::deserialize(&mut reader)?;
+        let hiding_comm = read_optional_point(&mut reader, points)?;
+        let rand = CanonicalDeserialize::deserialize(&mut reader)?;
+        Ok(ark_poly_commit::ipa_pc::Proof {
+            l_vec,
+            r_vec,
+            final_comm_key,
+            c,
+            hiding_comm,
+            rand,
+        })
+    }
+}
+
+/// Reads a point serialized with [`CanonicalSerialize::serialize`] from
+/// `reader`, without checking that it lies in the prime order subgroup, and
+/// pushes it to `points`.
+///
+/// The point is recovered from its compressed form, so it always lies on the
+/// curve, and only its canonical encoding is accepted.
+fn read_point<G, R>(
+    mut reader: R,
+    points: &mut Vec<G>,
+) -> Result<G, SerializationError>
+where
+    G: AffineCurve,
+    R: Read,
+{
+    let mut bytes = vec![0u8; G::zero().serialized_size()];
+    reader.read_exact(&mut bytes)?;
+    let point =
+        G::from_random_bytes(&bytes).ok_or(SerializationError::InvalidData)?;
+    // Decoding random bytes ignores the bits unused by the encoding, so the
+    // point must encode back to the same bytes.
+    let mut encoding = Vec::with_capacity(bytes.len());
+    point.serialize(&mut encoding)?;
+    if encoding != bytes {
+        return Err(SerializationError::InvalidData);
+    }
+    points.push(point);
+    Ok(point)
+}
+
+/// Reads an optional point like [`read_point`].
+fn read_optional_point<G, R>(
+    mut reader: R,
+    points: &mut Vec<G>,
+) -> Result<Option<G>, SerializationError>
+where
+    G: AffineCurve,
+    R: Read,
+{
+    if bool::deserialize(&mut reader)? {
+        Ok(Some(read_point(reader, points)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Reads a vector of points like [`read_point`].
+fn read_points<G, R>(
+    mut reader: R,
+    points: &mut Vec<G>,
+) -> Result<Vec<G>, SerializationError>
+where
+    G: AffineCurve,
+    R: Read,
+{
+    let len = u64::deserialize(&mut reader)?;
+    (0..len).map(|_| read_point(&mut reader, points)).collect()
+}
+
+/// Checks that all of `points` lie in the prime order subgroup with a single
+/// multi-scalar multiplication, returning `false` if any of them does not.
+///
+/// A linear combination of the points with random 128-bit coefficients
+/// sampled from `rng` is computed, and only the combination is checked to lie
+/// in the subgroup, which it does whenever all of the points do.
+///
+/// # Security
+///
+/// A point outside of the subgroup has a component of order dividing the
+/// cofactor of the curve, which the combination only cancels out with
+/// probability the inverse of this order. At worst, all of the components
+/// are picked of the order of the smallest prime factor `p` of the cofactor
+/// and the check passes with probability `1 / p`. It must therefore only be
+/// used on groups whose cofactor has no small prime factor, as gated by
+/// [`HomomorphicCommitment::BATCH_SUBGROUP_CHECK`].
+fn combined_subgroup_check<G, R>(points: &[G], rng: &mut R) -> bool
+where
+    G: AffineCurve,
+    R: RngCore,
+{
+    let scalars = points
+        .iter()
+        .map(|_| {
+            let mut bytes = [0u8; 16];
+            rng.fill_bytes(&mut bytes);
+            G::ScalarField::from(u128::from_le_bytes(bytes)).into_repr()
+        })
+        .collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(points, &scalars)
+        .mul(G::ScalarField::characteristic())
+        .is_zero()
 }
 
 /// Computes a linear combination of the polynomial evaluations and polynomial
@@ -120,10 +601,241 @@ pub fn aggregate_polynomials<F: Field>(
     polynomials: &[DensePolynomial<F>],
     challenge: F,
 ) -> DensePolynomial<F> {
-    use num_traits::Zero;
     use std::ops::Add;
     crate::util::powers_of(challenge)
         .zip(polynomials)
         .map(|(challenge, poly)| poly * challenge)
         .fold(Zero::zero(), Add::add)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+    use ark_poly::UVPolynomial;
+    use ark_serialize::CanonicalSerialize;
+    use rand::rngs::OsRng;
+
+    fn test_batch_commit<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: std::fmt::Debug + PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // As many polynomials as the prover commits to: four wires, the
+        // permutation and four quotient pieces.
+        let polys = (0..9)
+            .map(|_| DensePolynomial::<F>::rand(2 * 16, &mut OsRng))
+            .collect::<Vec<_>>();
+        let batched = PC::batch_commit(&ck, &polys).unwrap();
+        assert_eq!(batched.len(), polys.len());
+
+        for (poly, commitment) in polys.iter().zip(&batched) {
+            let (individual, _) = PC::commit(
+                &ck,
+                &[LabeledPolynomial::new(
+                    "poly".to_string(),
+                    poly.clone(),
+                    None,
+                    None,
+                )],
+                None,
+            )
+            .unwrap();
+            assert_eq!(individual[0].commitment(), commitment);
+        }
+    }
+
+    /// Opening point, challenge, opening proof and evaluations of a group.
+    type Opening<F, PC> = (
+        F,
+        F,
+        <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Proof,
+        Vec<F>,
+    );
+
+    /// Checks `openings` of the first `commitments` with
+    /// [`HomomorphicCommitment::check_multi_point`].
+    fn check_openings<F, PC>(
+        vk: &PC::VerifierKey,
+        commitments: &[LabeledCommitment<PC::Commitment>],
+        openings: &[Opening<F, PC>],
+    ) -> bool
+    where
+        F: PrimeField,
+        PC: HomomorphicCommitment<F>,
+    {
+        let groups = openings
+            .iter()
+            .map(|(point, challenge, opening, evals)| OpeningGroup {
+                point: *point,
+                commitments: &commitments[..evals.len()],
+                evaluations: evals,
+                opening,
+                challenge: *challenge,
+            })
+            .collect::<Vec<_>>();
+        PC::check_multi_point(vk, &groups, F::rand(&mut OsRng)).unwrap()
+    }
+
+    fn test_check_multi_point<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let polys = (0..3)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("poly_{}", i),
+                    DensePolynomial::rand(16, &mut OsRng),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, &polys, None).unwrap();
+
+        // Open the first `count` polynomials at a random point.
+        let open = |count: usize| -> Opening<F, PC> {
+            let point = F::rand(&mut OsRng);
+            let challenge = F::rand(&mut OsRng);
+            let opening = PC::open(
+                &ck,
+                &polys[..count],
+                &commitments[..count],
+                &point,
+                challenge,
+                &rands[..count],
+                None,
+            )
+            .unwrap();
+            let evals = polys[..count]
+                .iter()
+                .map(|poly| poly.evaluate(&point))
+                .collect();
+            (point, challenge, opening, evals)
+        };
+        let openings = vec![open(3), open(2)];
+        assert!(check_openings::<F, PC>(&vk, &commitments, &openings));
+
+        // Tampering with the evaluations or the point of either group is
+        // rejected.
+        for i in 0..openings.len() {
+            let mut tampered = openings.clone();
+            tampered[i].3[1] += F::one();
+            assert!(!check_openings::<F, PC>(&vk, &commitments, &tampered));
+
+            let mut tampered = openings.clone();
+            tampered[i].0 += F::one();
+            assert!(!check_openings::<F, PC>(&vk, &commitments, &tampered));
+        }
+    }
+
+    fn test_deferred_deserialization<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let polys = (0..2)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("poly_{}", i),
+                    DensePolynomial::rand(16, &mut OsRng),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, &polys, None).unwrap();
+        let opening = PC::open(
+            &ck,
+            &polys,
+            &commitments,
+            &F::rand(&mut OsRng),
+            F::rand(&mut OsRng),
+            &rands,
+            None,
+        )
+        .unwrap();
+
+        // Both are read back as they are deserialized, along with their
+        // points, which are all in the subgroup.
+        let mut points = Vec::new();
+        let mut bytes = Vec::new();
+        commitments[0].commitment().serialize(&mut bytes).unwrap();
+        let commitment =
+            PC::deserialize_commitment_deferred(bytes.as_slice(), &mut points)
+                .unwrap();
+        let mut encoding = Vec::new();
+        commitment.serialize(&mut encoding).unwrap();
+        assert_eq!(encoding, bytes);
+
+        let mut bytes = Vec::new();
+        opening.serialize(&mut bytes).unwrap();
+        let opening =
+            PC::deserialize_proof_deferred(bytes.as_slice(), &mut points)
+                .unwrap();
+        let mut encoding = Vec::new();
+        opening.serialize(&mut encoding).unwrap();
+        assert_eq!(encoding, bytes);
+
+        assert!(points.len() > 1);
+        assert!(PC::batch_subgroup_check(&points, &mut OsRng));
+
+        // A point on the curve outside of the prime order subgroup is
+        // rejected along with them.
+        let size = PC::Point::zero().serialized_size();
+        let point = loop {
+            let mut random = vec![0u8; size];
+            OsRng.fill_bytes(&mut random);
+            if let Some(point) = PC::Point::from_random_bytes(&random) {
+                if !point.into_projective().mul(F::characteristic()).is_zero() {
+                    break point;
+                }
+            }
+        };
+        points.push(point);
+        assert!(!PC::batch_subgroup_check(&points, &mut OsRng));
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_batch_commit,
+            test_check_multi_point,
+            test_deferred_deserialization
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_batch_commit,
+            test_check_multi_point,
+            test_deferred_deserialization
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -7,6 +7,7 @@
 //! Simple Arithmetic Gates
 
 use crate::constraint_system::{StandardComposer, Variable};
+use alloc::collections::VecDeque;
 use ark_ec::TEModelParameters;
 use ark_ff::PrimeField;
 
@@ -94,6 +95,65 @@ where
     }
 }
 
+/// Arithmetic constraint
+/// `q_m * a * b + q_1 * w_1 + ... + q_k * w_k + q_c + PI = 0` over any
+/// number `k` of wires, added with [`StandardComposer::wide_gate`].
+#[derive(Clone, Debug)]
+pub struct WideGate<F>
+where
+    F: PrimeField,
+{
+    pub(crate) mul: Option<(F, Variable, Variable)>,
+    pub(crate) wires: Vec<(F, Variable)>,
+    pub(crate) const_selector: F,
+    pub(crate) pi: Option<F>,
+}
+
+impl<F> Default for WideGate<F>
+where
+    F: PrimeField,
+{
+    fn default() -> Self {
+        Self {
+            mul: None,
+            wires: Vec::new(),
+            const_selector: F::zero(),
+            pi: None,
+        }
+    }
+}
+
+impl<F> WideGate<F>
+where
+    F: PrimeField,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the product term `q_m * a * b`.
+    pub fn mul(&mut self, q_m: F, a: Variable, b: Variable) -> &mut Self {
+        self.mul = Some((q_m, a, b));
+        self
+    }
+
+    /// Adds the term `q * w`, reading `w` through one more wire.
+    pub fn wire(&mut self, q: F, w: Variable) -> &mut Self {
+        self.wires.push((q, w));
+        self
+    }
+
+    pub fn constant(&mut self, q_c: F) -> &mut Self {
+        self.const_selector = q_c;
+        self
+    }
+
+    pub fn pi(&mut self, pi: F) -> &mut Self {
+        self.pi = Some(pi);
+        self
+    }
+}
+
 impl<F, P> StandardComposer<F, P>
 where
     F: PrimeField,
@@ -135,11 +195,7 @@ where
         self.q_variable_group_add.push(F::zero());
 
         if let Some(pi) = gate.pi {
-            let insert_res = self.public_inputs_sparse_store.insert(self.n, pi);
-            assert!(
-                insert_res.is_none(),
-                "Attempting to overwrite an already existing PI"
-            )
+            self.insert_public_input(self.n, pi);
         };
 
         let c = gate_witness.2.unwrap_or_else(|| {
@@ -167,6 +223,96 @@ where
 
         c
     }
+
+    /// Adds the constraint described by a `WideGate`, over as many wires
+    /// as needed.
+    ///
+    /// The wires that do not fit in a single gate are virtual: the
+    /// constraint is lowered to a chain of arithmetic gates, each of them
+    /// folding three wires, or the product and one wire for the first gate,
+    /// into a partial sum read by the left wire of the next gate. The last
+    /// gate also reads a wire through its output and holds the constant and
+    /// the public input. The wires are [`Variable`]s, so the permutation
+    /// argument connects each of their uses as for any other gate.
+    ///
+    /// A constraint over `k > 4` wires without product takes
+    /// `1 + ceil((k - 4) / 2)` gates.
+    pub fn wide_gate<Fn>(&mut self, func: Fn)
+    where
+        Fn: FnOnce(&mut WideGate<F>) -> &mut WideGate<F>,
+    {
+        let mut gate = WideGate::<F>::new();
+        func(&mut gate);
+
+        let zero = (F::zero(), self.zero_var);
+        let mut mul = gate.mul;
+        let mut wires = VecDeque::from(gate.wires);
+        loop {
+            let input_wires = if mul.is_some() { 1 } else { 3 };
+            let is_last = wires.len() <= input_wires + 1;
+            let (q_m, (q_l, w_l), (q_r, w_r)) = match mul.take() {
+                Some((q_m, a, b)) => (q_m, (F::zero(), a), (F::zero(), b)),
+                None => (
+                    F::zero(),
+                    wires.pop_front().unwrap_or(zero),
+                    wires.pop_front().unwrap_or(zero),
+                ),
+            };
+            let (q_4, w_4) = wires.pop_front().unwrap_or(zero);
+
+            if is_last {
+                let (q_o, w_o) = wires.pop_front().unwrap_or(zero);
+                self.arithmetic_gate(|arith| {
+                    arith
+                        .witness(w_l, w_r, Some(w_o))
+                        .mul(q_m)
+                        .add(q_l, q_r)
+                        .fan_in_3(q_4, w_4)
+                        .out(q_o)
+                        .constant(gate.const_selector);
+                    match gate.pi {
+                        Some(pi) => arith.pi(pi),
+                        None => arith,
+                    }
+                });
+                break;
+            }
+
+            let partial_sum = self.arithmetic_gate(|arith| {
+                arith
+                    .witness(w_l, w_r, None)
+                    .mul(q_m)
+                    .add(q_l, q_r)
+                    .fan_in_3(q_4, w_4)
+            });
+            wires.push_front((F::one(), partial_sum));
+        }
+    }
+
+    /// Returns a [`Variable`] holding the sum of `vars`, or the zero
+    /// [`Variable`] if `vars` is empty.
+    ///
+    /// The sum is accumulated through the left wire of a chain of arithmetic
+    /// gates, each of them adding two more of `vars` through its right and
+    /// fourth wires. Summing `k > 0` variables takes `(k - 1) / 2` gates,
+    /// rounded up, instead of the `k - 1` gates of pairwise additions.
+    pub fn sum(&mut self, vars: &[Variable]) -> Variable {
+        let (first, rest) = match vars.split_first() {
+            Some(split) => split,
+            None => return self.zero_var,
+        };
+        rest.chunks(2).fold(*first, |total, pair| {
+            let (q_4, w_4) = match pair {
+                [_, w_4] => (F::one(), *w_4),
+                _ => (F::zero(), self.zero_var),
+            };
+            self.arithmetic_gate(|gate| {
+                gate.witness(total, pair[0], None)
+                    .add(F::one(), F::one())
+                    .fan_in_3(q_4, w_4)
+            })
+        })
+    }
 }
 
 #[cfg(test)]
@@ -425,6 +571,93 @@ mod test {
         assert!(res.is_err());
     }
 
+    fn test_wide_gate<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // 1 + 2 * 2 + 3 * 3 + 4 * 4 + 5 * 5 + PI = 60
+        fn gadget<F, P>(composer: &mut StandardComposer<F, P>, pi: u64)
+        where
+            F: PrimeField,
+            P: TEModelParameters<BaseField = F>,
+        {
+            let wires = (1..=5u64)
+                .map(|i| (F::from(i), composer.add_input(F::from(i))))
+                .collect::<Vec<_>>();
+            let size = composer.circuit_size();
+            composer.wide_gate(|gate| {
+                for (q, w) in &wires {
+                    gate.wire(*q, *w);
+                }
+                gate.constant(-F::from(60u64)).pi(F::from(pi))
+            });
+            assert_eq!(composer.circuit_size(), size + 2);
+        }
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| gadget(composer, 5),
+            200,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| gadget(composer, 6),
+            200,
+        );
+        assert!(res.is_err());
+
+        // 2 * 3 + 1 + 2 + 3 + 4 + 5 + 6 = 27, the product taking the
+        // left and right wires of the first gate.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(2u64));
+                let b = composer.add_input(F::from(3u64));
+                let wires = (1..=6u64)
+                    .map(|i| composer.add_input(F::from(i)))
+                    .collect::<Vec<_>>();
+                composer.wide_gate(|gate| {
+                    for w in &wires {
+                        gate.wire(F::one(), *w);
+                    }
+                    gate.mul(F::one(), a, b).constant(-F::from(27u64))
+                });
+            },
+            200,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_sum<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let vars = (1..=10u64)
+                    .map(|i| composer.add_input(F::from(i)))
+                    .collect::<Vec<_>>();
+
+                let gates = composer.circuit_size();
+                let total = composer.sum(&vars);
+                // Better than the 9 gates of pairwise additions
+                assert_eq!(composer.circuit_size() - gates, 5);
+                composer.constrain_to_constant(total, F::from(55u64), None);
+
+                let odd = composer.sum(&vars[..5]);
+                composer.constrain_to_constant(odd, F::from(15u64), None);
+
+                let single = composer.sum(&vars[3..4]);
+                assert_eq!(single, vars[3]);
+                let empty = composer.sum(&[]);
+                composer.constrain_to_constant(empty, F::zero(), None);
+            },
+            200,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
     // Bls12-381 tests
     batch_test!(
         [
@@ -434,7 +667,9 @@ mod test {
             test_correct_big_add_mul_gate,
             test_correct_big_arith_gate,
             test_incorrect_add_mul_gate,
-            test_incorrect_big_arith_gate
+            test_incorrect_big_arith_gate,
+            test_wide_gate,
+            test_sum
         ],
         [] => (
             Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
@@ -450,7 +685,9 @@ mod test {
             test_correct_big_add_mul_gate,
             test_correct_big_arith_gate,
             test_incorrect_add_mul_gate,
-            test_incorrect_big_arith_gate
+            test_incorrect_big_arith_gate,
+            test_wide_gate,
+            test_sum
         ],
         [] => (
             Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
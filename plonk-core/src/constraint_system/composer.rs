@@ -14,8 +14,12 @@
 //! It allows us not only to build Add and Mul constraints but also to build
 //! ECC op. gates, Range checks, Logical gates (Bitwise ops) etc.
 
-use crate::{constraint_system::Variable, permutation::Permutation};
-use alloc::collections::BTreeMap;
+use crate::{
+    constraint_system::{Variable, WireData},
+    error::Error,
+    permutation::Permutation,
+};
+use alloc::collections::{btree_map::Entry, BTreeMap, BTreeSet};
 
 use ark_ec::{models::TEModelParameters, ModelParameters};
 use ark_ff::PrimeField;
@@ -23,6 +27,27 @@ use core::marker::PhantomData;
 use hashbrown::HashMap;
 use rand::{CryptoRng, RngCore};
 
+/// Number of blinding factors of the blinding rows added to every circuit by
+/// [`StandardComposer::new`], see [`StandardComposer::set_blinding_factors`].
+pub const NUM_BLINDING_FACTORS: usize = 8;
+
+/// Number of gates added to every circuit by
+/// [`StandardComposer::with_expected_size`]: the gate pinning the zero
+/// [`Variable`], one blinding gate per four blinding factors and the gate
+/// blinding the permutation polynomial.
+const INITIAL_GATES: usize = 1 + NUM_BLINDING_FACTORS / 4 + 1;
+
+/// Summary of a circuit which passed the validations of
+/// [`StandardComposer::finalize`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FinalizedCircuit {
+    /// Number of gates of the circuit.
+    pub circuit_size: usize,
+
+    /// Number of Public Inputs of the circuit.
+    pub num_public_inputs: usize,
+}
+
 /// The StandardComposer is the circuit-builder tool that the `plonk` repository
 /// provides to create, stored and transformed circuit descriptions
 /// into a [`Proof`](crate::proof_system::Proof) at some point.
@@ -86,6 +111,14 @@ where
     /// non-zero ones to it's actual values.
     pub(crate) public_inputs_sparse_store: BTreeMap<usize, F>,
 
+    /// Positions of the Public Inputs which have been assigned two different
+    /// values.
+    pub(crate) conflicting_public_inputs: BTreeSet<usize>,
+
+    /// Positions of the Public Inputs which are public outputs of the
+    /// circuit, linked to the [`Variable`] they expose.
+    pub(crate) public_outputs: BTreeMap<usize, Variable>,
+
     // Witness vectors
     /// Left wire witness vector.
     pub(crate) w_l: Vec<Variable>,
@@ -108,6 +141,15 @@ where
     /// Permutation argument.
     pub(crate) perm: Permutation,
 
+    /// Whether the blinding rows hold blinding factors sampled by
+    /// [`StandardComposer::add_blinding_factors`] or set by
+    /// [`StandardComposer::set_blinding_factors`], rather than zeros.
+    pub(crate) blinded: bool,
+
+    /// Variables holding the blinding factors of the last blinding rows
+    /// added, in the order of [`StandardComposer::set_blinding_factors`].
+    pub(crate) blinding_vars: [Variable; NUM_BLINDING_FACTORS],
+
     /// Type Parameter Marker
     __: PhantomData<P>,
 }
@@ -141,6 +183,524 @@ where
         // or Iterator.
         self.public_inputs_sparse_store.keys().copied().collect()
     }
+
+    /// Returns the positions of the public outputs of the circuit, in
+    /// increasing order.
+    ///
+    /// Public outputs are Public Inputs whose values are computed by the
+    /// prover, see [`StandardComposer::public_output`].
+    pub fn public_output_positions(&self) -> Vec<usize> {
+        self.public_outputs.keys().copied().collect()
+    }
+
+    /// Returns the values of the public outputs of the circuit, ordered by
+    /// their positions.
+    ///
+    /// These are sent by the prover along with the [`Proof`], and read by
+    /// the verifier through
+    /// [`Verifier::verify_with_public_outputs`].
+    ///
+    /// [`Proof`]: crate::proof_system::Proof
+    /// [`Verifier::verify_with_public_outputs`]:
+    /// crate::proof_system::Verifier::verify_with_public_outputs
+    pub fn public_outputs(&self) -> Vec<F> {
+        self.public_outputs
+            .values()
+            .map(|var| self.variables[var])
+            .collect()
+    }
+
+    /// Returns the dense Public Inputs `public_inputs` with the values of the
+    /// public outputs of the circuit replaced by `public_outputs`, ordered by
+    /// their positions.
+    ///
+    /// Returns [`Error::PublicOutputCountMismatch`] if the number of
+    /// `public_outputs` differs from the number of public outputs of the
+    /// circuit.
+    pub fn with_public_outputs(
+        &self,
+        public_inputs: &[F],
+        public_outputs: &[F],
+    ) -> Result<Vec<F>, Error> {
+        if public_outputs.len() != self.public_outputs.len() {
+            return Err(Error::PublicOutputCountMismatch {
+                expected: self.public_outputs.len(),
+                found: public_outputs.len(),
+            });
+        }
+        let mut pi = public_inputs.to_vec();
+        let positions = self.public_outputs.keys();
+        for (position, value) in positions.zip(public_outputs) {
+            if *position >= pi.len() {
+                pi.resize(position + 1, F::zero());
+            }
+            pi[*position] = *value;
+        }
+        Ok(pi)
+    }
+
+    /// Stores `value` as the Public Input at `position`.
+    ///
+    /// Assigning the same value twice to a position is allowed. Assigning a
+    /// different value never overwrites the stored one: the first value is
+    /// kept and the position is recorded as a conflict, reported by
+    /// [`StandardComposer::check_public_inputs`].
+    pub(crate) fn insert_public_input(&mut self, position: usize, value: F) {
+        match self.public_inputs_sparse_store.entry(position) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(entry) => {
+                if *entry.get() != value {
+                    self.conflicting_public_inputs.insert(position);
+                }
+            }
+        }
+    }
+
+    /// Checks that no Public Input position has been assigned conflicting
+    /// values, returning [`Error::ConflictingPublicInput`] with the first
+    /// such position otherwise.
+    ///
+    /// This is checked when preprocessing and proving the circuit.
+    pub fn check_public_inputs(&self) -> Result<(), Error> {
+        match self.conflicting_public_inputs.iter().next() {
+            Some(position) => Err(Error::ConflictingPublicInput {
+                position: *position,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Reorders the gates of the circuit so that the gate currently placed at
+    /// row `order[i]` is moved to row `i`.
+    ///
+    /// The wires, selectors, public input positions and copy constraints are
+    /// all moved together, so the reordered circuit proves exactly the same
+    /// statement. It can be used to improve the memory locality of the
+    /// circuit before preprocessing it.
+    ///
+    /// # Note
+    ///
+    /// Reordering changes the circuit description and therefore its keys. The
+    /// same `order` must be applied to the circuit used by the `Verifier`,
+    /// and to every witness that is later proven against the reordered
+    /// circuit.
+    ///
+    /// Returns [`Error::InvalidRowOrder`] if `order` is not a permutation of
+    /// the rows of the circuit, or if it moves a gate away from the next row
+    /// it reads from (range, logic and elliptic curve gates).
+    pub fn permute_rows(&mut self, order: &[usize]) -> Result<(), Error> {
+        if order.len() != self.n {
+            return Err(Error::InvalidRowOrder);
+        }
+
+        // `position[row]` is the new position of the gate at `row`.
+        let mut position = vec![usize::MAX; self.n];
+        for (new_row, &row) in order.iter().enumerate() {
+            if row >= self.n || position[row] != usize::MAX {
+                return Err(Error::InvalidRowOrder);
+            }
+            position[row] = new_row;
+        }
+
+        // Gates which read from the next row must stay next to it.
+        for row in 0..self.n {
+            let keeps_next_row = match position.get(row + 1) {
+                Some(next) => *next == position[row] + 1,
+                None => position[row] == self.n - 1,
+            };
+            if self.reads_next_row(row) && !keeps_next_row {
+                return Err(Error::InvalidRowOrder);
+            }
+        }
+
+        fn reorder<T: Copy>(values: &mut Vec<T>, order: &[usize]) {
+            *values = order.iter().map(|&row| values[row]).collect();
+        }
+        reorder(&mut self.q_m, order);
+        reorder(&mut self.q_l, order);
+        reorder(&mut self.q_r, order);
+        reorder(&mut self.q_o, order);
+        reorder(&mut self.q_4, order);
+        reorder(&mut self.q_c, order);
+        reorder(&mut self.q_arith, order);
+        reorder(&mut self.q_range, order);
+        reorder(&mut self.q_logic, order);
+        reorder(&mut self.q_fixed_group_add, order);
+        reorder(&mut self.q_variable_group_add, order);
+        reorder(&mut self.w_l, order);
+        reorder(&mut self.w_r, order);
+        reorder(&mut self.w_o, order);
+        reorder(&mut self.w_4, order);
+
+        self.public_inputs_sparse_store = self
+            .public_inputs_sparse_store
+            .iter()
+            .map(|(row, value)| (position[*row], *value))
+            .collect();
+        self.conflicting_public_inputs = self
+            .conflicting_public_inputs
+            .iter()
+            .map(|row| position[*row])
+            .collect();
+        self.public_outputs = self
+            .public_outputs
+            .iter()
+            .map(|(row, var)| (position[*row], *var))
+            .collect();
+
+        self.perm
+            .variable_map
+            .values_mut()
+            .flat_map(|wires| wires.iter_mut())
+            .for_each(|wire| *wire = wire.with_row(position[wire.row()]));
+
+        Ok(())
+    }
+
+    /// Returns the copy constraints of the circuit as explicit links between
+    /// wires.
+    ///
+    /// Each [`Variable`] used in several wires contributes the cycle of links
+    /// `w_0 -> w_1 -> ... -> w_k -> w_0` through the wires it occupies, which
+    /// is how the sigma permutation connects them. Wires holding a
+    /// [`Variable`] used only once are mapped to themselves by the
+    /// permutation and are omitted. Links are sorted by [`Variable`] in
+    /// creation order.
+    pub fn permutation_mapping(&self) -> Vec<(WireData, WireData)> {
+        let mut variables = self
+            .perm
+            .variable_map
+            .iter()
+            .filter(|(_, wires)| wires.len() > 1)
+            .collect::<Vec<_>>();
+        variables.sort_unstable_by_key(|(variable, _)| variable.0);
+
+        variables
+            .into_iter()
+            .flat_map(|(_, wires)| {
+                wires
+                    .iter()
+                    .zip(wires.iter().cycle().skip(1))
+                    .map(|(from, to)| (*from, *to))
+            })
+            .collect()
+    }
+
+    /// Shrinks the circuit by removing the arithmetic gates whose constraint
+    /// is implied by the rest of the circuit.
+    ///
+    /// A [`Variable`] is treated as a constant once an arithmetic gate pins it
+    /// to a single value, either directly like
+    /// [`StandardComposer::constrain_to_constant`] does, or through other
+    /// constants. Then, until no more gates can be removed:
+    ///
+    /// - gates involving only constants, and satisfied by them, are removed,
+    /// - gates pinning a [`Variable`] to the value of a constant found before
+    ///   are removed, and both [`Variable`]s are merged,
+    /// - identity gates `k * a - k * b = 0` are removed, and `b` is merged into
+    ///   `a`.
+    ///
+    /// Merged [`Variable`]s share their wires in the permutation argument, so
+    /// the optimized circuit proves exactly the same statement. Gates holding
+    /// a Public Input, custom gates and the rows they read from are never
+    /// removed.
+    ///
+    /// Returns the number of removed gates.
+    ///
+    /// # Note
+    ///
+    /// As with [`StandardComposer::permute_rows`], the circuit used by the
+    /// `Verifier` must be optimized as well. This should be the last step of
+    /// the circuit construction since the [`Variable`]s merged away must not
+    /// be used by later gates. The positions of the Public Inputs move along
+    /// with the removed gates, so the ones returned before, e.g. by
+    /// [`StandardComposer::add_public_input_variable`], must be read again
+    /// from [`StandardComposer::pi_positions`].
+    pub fn optimize(&mut self) -> usize {
+        let initial_size = self.n;
+        // Every iteration but the last removes at least one gate, so there
+        // are at most `initial_size + 1` of them.
+        loop {
+            let constants = self.find_constants();
+
+            // The first constant found with each value.
+            let mut representatives = HashMap::<F, (Variable, usize)>::new();
+            for (var, constant) in constants.iter() {
+                let representative = representatives
+                    .entry(constant.value)
+                    .or_insert((*var, constant.rank));
+                if constant.rank < representative.1 {
+                    *representative = (*var, constant.rank);
+                }
+            }
+
+            let mut removed = vec![false; self.n];
+            for (row, removed) in removed.iter_mut().enumerate() {
+                if !self.is_foldable(row) {
+                    continue;
+                }
+                let gate = self.fold_gate(row, &constants);
+                if gate.nonlinear {
+                    continue;
+                }
+                match *gate.linear.as_slice() {
+                    [] if gate.constant.is_zero() => *removed = true,
+                    [(var, _)] => {
+                        // Only fold the gate `var` was found constant by,
+                        // gates reduced to `var` by the merges above are
+                        // looked at again in the next iteration.
+                        let representative = constants
+                            .get(&var)
+                            .filter(|constant| constant.row == row)
+                            .map(|constant| representatives[&constant.value].0)
+                            .filter(|representative| *representative != var);
+                        if let Some(representative) = representative {
+                            self.merge_variables(representative, var);
+                            *removed = true;
+                        }
+                    }
+                    [(a, k_a), (b, k_b)]
+                        if gate.constant.is_zero() && (k_a + k_b).is_zero() =>
+                    {
+                        self.merge_variables(a, b);
+                        *removed = true;
+                    }
+                    _ => {}
+                }
+            }
+
+            if !removed.contains(&true) {
+                return initial_size - self.n;
+            }
+            self.remove_rows(&removed);
+        }
+    }
+
+    /// Returns the row following `row`, whose wires are read by the range,
+    /// logic and elliptic curve gates at `row`.
+    ///
+    /// The rows wrap around like the evaluation domain, so the row following
+    /// the last one is the first one.
+    pub(crate) fn next_row(&self, row: usize) -> usize {
+        debug_assert!(row < self.n);
+        if row + 1 == self.n {
+            0
+        } else {
+            row + 1
+        }
+    }
+
+    /// Returns `true` if the gate at `row` reads the wires of the next row,
+    /// as range, logic and elliptic curve gates do.
+    fn reads_next_row(&self, row: usize) -> bool {
+        !self.q_range[row].is_zero()
+            || !self.q_logic[row].is_zero()
+            || !self.q_fixed_group_add[row].is_zero()
+            || !self.q_variable_group_add[row].is_zero()
+    }
+
+    /// Returns `true` if the gate at `row` is a plain arithmetic gate which
+    /// [`StandardComposer::optimize`] is allowed to remove.
+    fn is_foldable(&self, row: usize) -> bool {
+        !self.q_arith[row].is_zero()
+            && !self.reads_next_row(row)
+            && (row == 0 || !self.reads_next_row(row - 1))
+            && !self.public_inputs_sparse_store.contains_key(&row)
+    }
+
+    /// Finds the [`Variable`]s pinned to a single value by the arithmetic
+    /// gates of the circuit.
+    fn find_constants(&self) -> HashMap<Variable, Constant<F>> {
+        let mut constants = HashMap::new();
+        // Every iteration but the last finds at least one new constant, and
+        // there is at most one constant per gate.
+        loop {
+            let found = constants.len();
+            for row in (0..self.n).filter(|row| self.is_foldable(*row)) {
+                let gate = self.fold_gate(row, &constants);
+                if let (false, [(var, coeff)]) =
+                    (gate.nonlinear, gate.linear.as_slice())
+                {
+                    let rank = constants.len();
+                    constants.entry(*var).or_insert(Constant {
+                        value: -gate.constant / coeff,
+                        row,
+                        rank,
+                    });
+                }
+            }
+            if constants.len() == found {
+                return constants;
+            }
+        }
+    }
+
+    /// Substitutes the `constants` into the arithmetic constraint of the gate
+    /// at `row`, except for the ones pinned by that same gate.
+    fn fold_gate(
+        &self,
+        row: usize,
+        constants: &HashMap<Variable, Constant<F>>,
+    ) -> FoldedGate<F> {
+        let value = |var: Variable| {
+            constants
+                .get(&var)
+                .filter(|constant| constant.row != row)
+                .map(|constant| constant.value)
+        };
+        let (a, b) = (self.w_l[row], self.w_r[row]);
+        let mut gate = FoldedGate {
+            constant: self.q_c[row],
+            linear: Vec::new(),
+            nonlinear: false,
+        };
+
+        let q_m = self.q_m[row];
+        match (value(a), value(b)) {
+            (Some(a_value), Some(b_value)) => {
+                gate.constant += q_m * a_value * b_value
+            }
+            (Some(a_value), None) => gate.add_term(b, q_m * a_value),
+            (None, Some(b_value)) => gate.add_term(a, q_m * b_value),
+            (None, None) => gate.nonlinear = !q_m.is_zero(),
+        }
+        for (var, coeff) in [
+            (a, self.q_l[row]),
+            (b, self.q_r[row]),
+            (self.w_o[row], self.q_o[row]),
+            (self.w_4[row], self.q_4[row]),
+        ] {
+            match value(var) {
+                Some(value) => gate.constant += coeff * value,
+                None => gate.add_term(var, coeff),
+            }
+        }
+        gate.linear.retain(|(_, coeff)| !coeff.is_zero());
+        gate
+    }
+
+    /// Replaces every use of `merged` in the circuit by `kept`.
+    fn merge_variables(&mut self, kept: Variable, merged: Variable) {
+        for wires in
+            [&mut self.w_l, &mut self.w_r, &mut self.w_o, &mut self.w_4]
+        {
+            wires
+                .iter_mut()
+                .filter(|var| **var == merged)
+                .for_each(|var| *var = kept);
+        }
+        let merged_wires = core::mem::take(
+            self.perm
+                .variable_map
+                .get_mut(&merged)
+                .expect("variables are always allocated in the map"),
+        );
+        self.perm
+            .variable_map
+            .get_mut(&kept)
+            .expect("variables are always allocated in the map")
+            .extend(merged_wires);
+        self.variables.remove(&merged);
+    }
+
+    /// Removes the gates at the rows flagged in `removed`, none of which can
+    /// hold a Public Input.
+    fn remove_rows(&mut self, removed: &[bool]) {
+        // `position[row]` is the new position of the gate at `row`.
+        let mut position = Vec::with_capacity(self.n);
+        let mut n = 0;
+        for is_removed in removed {
+            position.push(n);
+            if !is_removed {
+                n += 1;
+            }
+        }
+
+        fn retain<T>(values: &mut Vec<T>, removed: &[bool]) {
+            let mut row = 0;
+            values.retain(|_| {
+                row += 1;
+                !removed[row - 1]
+            });
+        }
+        retain(&mut self.q_m, removed);
+        retain(&mut self.q_l, removed);
+        retain(&mut self.q_r, removed);
+        retain(&mut self.q_o, removed);
+        retain(&mut self.q_4, removed);
+        retain(&mut self.q_c, removed);
+        retain(&mut self.q_arith, removed);
+        retain(&mut self.q_range, removed);
+        retain(&mut self.q_logic, removed);
+        retain(&mut self.q_fixed_group_add, removed);
+        retain(&mut self.q_variable_group_add, removed);
+        retain(&mut self.w_l, removed);
+        retain(&mut self.w_r, removed);
+        retain(&mut self.w_o, removed);
+        retain(&mut self.w_4, removed);
+
+        self.public_inputs_sparse_store = self
+            .public_inputs_sparse_store
+            .iter()
+            .map(|(row, value)| (position[*row], *value))
+            .collect();
+        self.conflicting_public_inputs = self
+            .conflicting_public_inputs
+            .iter()
+            .map(|row| position[*row])
+            .collect();
+        self.public_outputs = self
+            .public_outputs
+            .iter()
+            .map(|(row, var)| (position[*row], *var))
+            .collect();
+
+        for wires in self.perm.variable_map.values_mut() {
+            wires.retain(|wire| !removed[wire.row()]);
+            wires
+                .iter_mut()
+                .for_each(|wire| *wire = wire.with_row(position[wire.row()]));
+        }
+
+        self.n = n;
+    }
+}
+
+/// Value a [`Variable`] is pinned to by an arithmetic gate.
+#[derive(Clone, Copy)]
+struct Constant<F> {
+    /// Value of the [`Variable`].
+    value: F,
+    /// Row of the gate pinning the [`Variable`].
+    row: usize,
+    /// Number of constants found before this one.
+    rank: usize,
+}
+
+/// Arithmetic constraint of a gate once the known constants have been
+/// substituted into it.
+struct FoldedGate<F> {
+    /// Constant term.
+    constant: F,
+    /// Coefficients of the remaining [`Variable`]s.
+    linear: Vec<(Variable, F)>,
+    /// Whether the product of two remaining [`Variable`]s is left.
+    nonlinear: bool,
+}
+
+impl<F> FoldedGate<F>
+where
+    F: PrimeField,
+{
+    /// Adds `coeff * var` to the constraint.
+    fn add_term(&mut self, var: Variable, coeff: F) {
+        match self.linear.iter_mut().find(|(other, _)| *other == var) {
+            Some((_, total)) => *total += coeff,
+            None => self.linear.push((var, coeff)),
+        }
+    }
 }
 
 impl<F, P> Default for StandardComposer<F, P>
@@ -198,6 +758,8 @@ where
             q_fixed_group_add: Vec::with_capacity(expected_size),
             q_variable_group_add: Vec::with_capacity(expected_size),
             public_inputs_sparse_store: BTreeMap::new(),
+            conflicting_public_inputs: BTreeSet::new(),
+            public_outputs: BTreeMap::new(),
             w_l: Vec::with_capacity(expected_size),
             w_r: Vec::with_capacity(expected_size),
             w_o: Vec::with_capacity(expected_size),
@@ -205,6 +767,8 @@ where
             zero_var: Variable(0),
             variables: HashMap::with_capacity(expected_size),
             perm: Permutation::new(),
+            blinded: false,
+            blinding_vars: [Variable(0); NUM_BLINDING_FACTORS],
             __: PhantomData::<P>,
         };
 
@@ -213,7 +777,11 @@ where
             composer.add_witness_to_circuit_description(F::zero());
 
         // Add dummy constraints
+        #[cfg(feature = "prover")]
         composer.add_blinding_factors(&mut rand::rngs::OsRng);
+        #[cfg(not(feature = "prover"))]
+        composer.add_blinding_rows([F::zero(); NUM_BLINDING_FACTORS]);
+        debug_assert_eq!(composer.n, INITIAL_GATES);
 
         composer
     }
@@ -279,10 +847,7 @@ where
         self.q_variable_group_add.push(F::zero());
 
         if let Some(pi) = pi {
-            assert!(self
-                .public_inputs_sparse_store
-                .insert(self.n, pi)
-                .is_none());
+            self.insert_public_input(self.n, pi);
         }
 
         self.perm
@@ -292,6 +857,71 @@ where
         (a, b, c)
     }
 
+    /// Adds a single gate to the circuit description by explicitly setting the
+    /// value of every selector for the row.
+    ///
+    /// This is the lowest level gate constructor the [`StandardComposer`]
+    /// exposes: the wires `a`, `b`, `c` and `d` are linked into the
+    /// permutation argument and every selector is pushed as given, including
+    /// the custom gate selectors `q_range`, `q_logic`, `q_fixed_group_add` and
+    /// `q_variable_group_add`. No witness value is computed on behalf of the
+    /// caller, so it is up to them to make sure that the resulting row
+    /// satisfies the constraints enabled by the selectors.
+    ///
+    /// When only `q_arith` is enabled the constraint added is:
+    /// `(a * b) * q_m + a * q_l + b * q_r + c * q_o + d * q_4 + q_c + PI = 0`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `pi` is set and this row already holds a public
+    /// input.
+    pub fn add_gate_with_selectors(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        c: Variable,
+        d: Variable,
+        q_m: F,
+        q_l: F,
+        q_r: F,
+        q_o: F,
+        q_4: F,
+        q_c: F,
+        q_arith: F,
+        q_range: F,
+        q_logic: F,
+        q_fixed_group_add: F,
+        q_variable_group_add: F,
+        pi: Option<F>,
+    ) -> (Variable, Variable, Variable, Variable) {
+        self.w_l.push(a);
+        self.w_r.push(b);
+        self.w_o.push(c);
+        self.w_4.push(d);
+
+        self.q_m.push(q_m);
+        self.q_l.push(q_l);
+        self.q_r.push(q_r);
+        self.q_o.push(q_o);
+        self.q_4.push(q_4);
+        self.q_c.push(q_c);
+        self.q_arith.push(q_arith);
+
+        self.q_range.push(q_range);
+        self.q_logic.push(q_logic);
+        self.q_fixed_group_add.push(q_fixed_group_add);
+        self.q_variable_group_add.push(q_variable_group_add);
+
+        if let Some(pi) = pi {
+            self.insert_public_input(self.n, pi);
+        }
+
+        self.perm.add_variables_to_map(a, b, c, d, self.n);
+        self.n += 1;
+
+        (a, b, c, d)
+    }
+
     /// Constrain a [`Variable`] to be equal to
     /// a specific constant value which is part of the circuit description and
     /// **NOT** a Public Input. ie. this value will be the same for all of the
@@ -315,6 +945,87 @@ where
         );
     }
 
+    /// Adds a gate `-var + PI = 0` binding `var` to the Public Input placed
+    /// at its row, returning the position of the Public Input.
+    fn bind_to_public_input(&mut self, var: Variable) -> usize {
+        let position = self.n;
+        let value = self.variables[&var];
+        self.poly_gate(
+            var,
+            var,
+            var,
+            F::zero(),
+            -F::one(),
+            F::zero(),
+            F::zero(),
+            F::zero(),
+            Some(value),
+        );
+        position
+    }
+
+    /// Allocates a [`Variable`] holding `value` and binds it to a Public
+    /// Input, returning the variable and the position of the Public Input.
+    ///
+    /// The position is the index of the Public Input in the dense vector of
+    /// [`StandardComposer::construct_dense_pi_vec`], which is the order the
+    /// verifier evaluates the Public Inputs in. The verifier can therefore
+    /// build its Public Inputs by placing each value at its position in a
+    /// vector of [`StandardComposer::circuit_size`] zeros.
+    ///
+    /// # Note
+    ///
+    /// The position is the row of the gate holding the Public Input, so it
+    /// no longer holds once [`StandardComposer::optimize`] or
+    /// [`StandardComposer::permute_rows`] move the gates. After those, the
+    /// positions must be read again from
+    /// [`StandardComposer::pi_positions`]. Since `optimize` keeps the
+    /// remaining gates in order, the Public Inputs keep their relative order
+    /// through it.
+    pub fn add_public_input_variable(&mut self, value: F) -> (Variable, usize) {
+        let var = self.add_input(value);
+        let position = self.bind_to_public_input(var);
+        (var, position)
+    }
+
+    /// Allocates a [`Variable`] equal to `constant` and binds it to a Public
+    /// Input, so that `constant` is part of the public statement of the
+    /// circuit.
+    ///
+    /// This is an alias of [`StandardComposer::add_public_input_variable`]
+    /// which drops the position of the Public Input.
+    ///
+    /// Unlike [`StandardComposer::constrain_to_constant`], which fixes the
+    /// value in the selectors of the circuit description, the value is placed
+    /// in the Public Inputs of the circuit, from which the verifier reads it
+    /// back with [`StandardComposer::construct_dense_pi_vec`] when building
+    /// the same circuit. The verifier key is therefore the same for any
+    /// `constant`, and a [`Proof`](crate::proof_system::Proof) only verifies
+    /// against the Public Inputs holding the `constant` it was made with.
+    pub fn add_constant_gate(&mut self, constant: F) -> Variable {
+        self.add_public_input_variable(constant).0
+    }
+
+    /// Exposes the value of `var` as a public output of the circuit,
+    /// returning the position of its Public Input.
+    ///
+    /// A public output is a Public Input whose value comes from the prover
+    /// rather than from the verifier: the gate `-var + PI = 0` places the
+    /// value of `var` in the dense Public Inputs, at a position returned by
+    /// [`StandardComposer::public_output_positions`]. Its value is then
+    /// interpolated into the Public Input polynomial like any other Public
+    /// Input, so the proof binds it exactly as it binds the Public Inputs
+    /// supplied by the verifier.
+    ///
+    /// The verifier builds the circuit with any value for `var` and receives
+    /// the actual values from the prover, see
+    /// [`StandardComposer::public_outputs`].
+    pub fn public_output(&mut self, var: Variable) -> usize {
+        let position = self.bind_to_public_input(var);
+        self.public_outputs.insert(position, var);
+        position
+    }
+
     /// Add a constraint into the circuit description that states that two
     /// [`Variable`]s are equal.
     pub fn assert_equal(&mut self, a: Variable, b: Variable) {
@@ -365,6 +1076,11 @@ where
 
     /// A gate which outputs a variable whose value is 1 if the
     /// two input variables have equal values and whose value is 0 otherwise.
+    ///
+    /// The output is constrained through the inverse of `a - b`, which the
+    /// prover supplies as an advice variable (see
+    /// [`StandardComposer::is_zero_with_output`]), so it is a boolean which
+    /// can be used directly by [`StandardComposer::conditional_select`].
     pub fn is_eq_with_output(&mut self, a: Variable, b: Variable) -> Variable {
         let difference = self.arithmetic_gate(|gate| {
             gate.witness(a, b, None).add(F::one(), -F::one())
@@ -372,6 +1088,22 @@ where
         self.is_zero_with_output(difference)
     }
 
+    /// Asserts that `x` is not zero.
+    ///
+    /// The prover supplies the inverse of `x` as an advice variable, and the
+    /// gate `x * x_inv - 1 = 0` constrains it, which no value of `x_inv`
+    /// satisfies when `x` is zero.
+    pub fn assert_nonzero(&mut self, x: Variable) {
+        let x_inv_value = self.variables[&x].inverse().unwrap_or_else(F::zero);
+        let x_inv = self.add_input(x_inv_value);
+        let zero = self.zero_var();
+        self.arithmetic_gate(|gate| {
+            gate.witness(x, x_inv, Some(zero))
+                .mul(F::one())
+                .constant(-F::one())
+        });
+    }
+
     /// Conditionally selects a [`Variable`] based on an input bit.
     ///
     /// If:
@@ -531,18 +1263,37 @@ where
     /// and permutation polynomials.
     /// All gate selectors are turned off to guarantee the constraints
     /// are still satisfied.
+    ///
+    /// # Note
+    ///
+    /// The blinding rows are part of the circuit description, so the
+    /// `Verifier` must add the same number of them as the `Prover`. Their
+    /// values are witnesses though, which the `Verifier` never sees, so a
+    /// [`Proof`](crate::proof_system::Proof) verifies regardless of the
+    /// blinding values chosen by the `Prover`.
     pub fn add_blinding_factors<R>(&mut self, rng: &mut R)
     where
         R: CryptoRng + RngCore + ?Sized,
     {
+        let factors = [(); NUM_BLINDING_FACTORS].map(|_| F::rand(rng));
+        self.add_blinding_rows(factors);
+        self.blinded = true;
+    }
+
+    /// Adds the blinding rows of [`StandardComposer::add_blinding_factors`]
+    /// with the given blinding `factors`.
+    fn add_blinding_rows(&mut self, factors: [F; NUM_BLINDING_FACTORS]) {
         let mut rand_var_1 = self.zero_var();
         let mut rand_var_2 = self.zero_var();
         // Blinding wires
-        for _ in 0..2 {
-            rand_var_1 = self.add_input(F::rand(rng));
-            rand_var_2 = self.add_input(F::rand(rng));
-            let rand_var_3 = self.add_input(F::rand(rng));
-            let rand_var_4 = self.add_input(F::rand(rng));
+        for (i, row) in factors.chunks_exact(4).enumerate() {
+            rand_var_1 = self.add_input(row[0]);
+            rand_var_2 = self.add_input(row[1]);
+            let rand_var_3 = self.add_input(row[2]);
+            let rand_var_4 = self.add_input(row[3]);
+            self.blinding_vars[4 * i..4 * i + 4].copy_from_slice(&[
+                rand_var_1, rand_var_2, rand_var_3, rand_var_4,
+            ]);
 
             self.w_l.push(rand_var_1);
             self.w_r.push(rand_var_2);
@@ -598,40 +1349,119 @@ where
         );
         self.n += 1;
     }
-    /// Utility function that checks on the "front-end"
-    /// side of the PLONK implementation if the identity polynomial
-    /// is satisfied for each of the [`StandardComposer`]'s gates.
+
+    /// Replaces the blinding factors of the blinding rows added by
+    /// [`StandardComposer::new`], or by the last call to
+    /// [`StandardComposer::add_blinding_factors`], with `factors`.
     ///
-    /// The recommended usage is to derive the std output and the std error to a
-    /// text file and analyze the gates there.
+    /// With the `prover` feature, [`StandardComposer::new`] samples these
+    /// factors from the operating system. Without it, no random source is
+    /// used and they are all zero, so a `Prover` built without the feature
+    /// must call this function before proving, which fails with
+    /// [`Error::MissingBlindingFactors`] otherwise.
     ///
-    /// # Panic
-    /// The function by itself will print each circuit gate info until one of
-    /// the gates does not satisfy the equation or there are no more gates. If
-    /// the cause is an unsatisfied gate equation, the function will panic.
-    #[cfg(feature = "trace")]
-    pub fn check_circuit_satisfied(&self) {
+    /// This lets the blinding factors be supplied from outside the composer,
+    /// e.g. by a multi-party computation in which each party contributes a
+    /// share of them. As with [`StandardComposer::add_blinding_factors`], the
+    /// circuit description is unchanged, so the `Verifier` is unaffected.
+    ///
+    /// # Security
+    ///
+    /// The blinding factors hide the witness in the evaluations of the
+    /// witness and permutation polynomials contained in a
+    /// [`Proof`](crate::proof_system::Proof). They must be uniformly random,
+    /// kept secret, and never reused across proofs, otherwise the proofs may
+    /// leak information about the witness.
+    pub fn set_blinding_factors(&mut self, factors: [F; NUM_BLINDING_FACTORS]) {
+        for (var, factor) in self.blinding_vars.into_iter().zip(factors) {
+            self.variables.insert(var, factor);
+        }
+        self.blinded = true;
+    }
+
+    /// Validates the circuit before it gets preprocessed, so that an invalid
+    /// circuit is reported before any commitment work is done.
+    ///
+    /// On top of [`StandardComposer::check_public_inputs`], it checks that:
+    /// - the circuit has at least one gate besides the ones added by
+    ///   [`StandardComposer::new`], returning [`Error::EmptyCircuit`]
+    ///   otherwise,
+    /// - every Public Input is placed at a row of the circuit, returning
+    ///   [`Error::PublicInputOutOfRange`] otherwise,
+    /// - every [`Variable`] added with [`StandardComposer::add_input`] is used
+    ///   by at least one gate, returning [`Error::DanglingVariable`] with the
+    ///   first unused one otherwise.
+    pub fn finalize(&self) -> Result<FinalizedCircuit, Error> {
+        self.check_poly_same_len()?;
+        self.check_public_inputs()?;
+        if self.n <= INITIAL_GATES {
+            return Err(Error::EmptyCircuit);
+        }
+        if let Some(position) = self
+            .public_inputs_sparse_store
+            .keys()
+            .copied()
+            .find(|position| *position >= self.n)
+        {
+            return Err(Error::PublicInputOutOfRange {
+                position,
+                circuit_size: self.n,
+            });
+        }
+        if let Some(variable) = self
+            .variables
+            .keys()
+            .filter(|var| self.perm.variable_map[*var].is_empty())
+            .min_by_key(|var| var.0)
+        {
+            return Err(Error::DanglingVariable {
+                variable: *variable,
+            });
+        }
+        Ok(FinalizedCircuit {
+            circuit_size: self.n,
+            num_public_inputs: self.public_inputs_sparse_store.len(),
+        })
+    }
+
+    /// Returns the index of the first gate of the circuit whose equation is
+    /// not satisfied by the witness held by the composer, if any.
+    ///
+    /// The equations of the arithmetic, range and logic gates are checked,
+    /// while the elliptic curve gates are not. With the `parallel` feature,
+    /// the gates are checked in parallel, still returning the lowest index
+    /// of the failing gates.
+    pub fn first_unsatisfied_gate(&self) -> Option<usize> {
+        let pi_vec = self.construct_dense_pi_vec();
+        #[cfg(feature = "parallel")]
+        let find = Self::first_unsatisfied_gate_parallel;
+        #[cfg(not(feature = "parallel"))]
+        let find = Self::first_unsatisfied_gate_sequential;
+        find(self, &pi_vec)
+    }
+
+    /// Returns the index of the first unsatisfied gate, checking the gates
+    /// in parallel.
+    #[cfg(feature = "parallel")]
+    fn first_unsatisfied_gate_parallel(&self, pi_vec: &[F]) -> Option<usize> {
+        use rayon::prelude::*;
+        (0..self.n)
+            .into_par_iter()
+            .find_first(|&i| !self.gate_eval(i, pi_vec).is_zero())
+    }
+
+    /// Returns the index of the first unsatisfied gate, checking the gates
+    /// one after the other.
+    #[cfg(any(test, not(feature = "parallel")))]
+    fn first_unsatisfied_gate_sequential(&self, pi_vec: &[F]) -> Option<usize> {
+        (0..self.n).find(|&i| !self.gate_eval(i, pi_vec).is_zero())
+    }
+
+    /// Evaluates the equation of the `i`-th gate over the witness held by
+    /// the composer and the dense vector of the public inputs `pi_vec`,
+    /// which is zero if the gate is satisfied.
+    fn gate_eval(&self, i: usize, pi_vec: &[F]) -> F {
         use ark_ff::BigInteger;
-        let w_l: Vec<&F> = self
-            .w_l
-            .iter()
-            .map(|w_l_i| self.variables.get(w_l_i).unwrap())
-            .collect();
-        let w_r: Vec<&F> = self
-            .w_r
-            .iter()
-            .map(|w_r_i| self.variables.get(w_r_i).unwrap())
-            .collect();
-        let w_o: Vec<&F> = self
-            .w_o
-            .iter()
-            .map(|w_o_i| self.variables.get(w_o_i).unwrap())
-            .collect();
-        let w_4: Vec<&F> = self
-            .w_4
-            .iter()
-            .map(|w_4_i| self.variables.get(w_4_i).unwrap())
-            .collect();
         // Computes f(f-1)(f-2)(f-3)
         let delta = |f: F| -> F {
             let f_1 = f - F::one();
@@ -639,35 +1469,117 @@ where
             let f_3 = f - F::from(3u64);
             f * f_1 * f_2 * f_3
         };
-        let pi_vec = self.construct_dense_pi_vec();
         let four = F::from(4u64);
-        for i in 0..self.n {
-            let qm = self.q_m[i];
-            let ql = self.q_l[i];
-            let qr = self.q_r[i];
-            let qo = self.q_o[i];
-            let qc = self.q_c[i];
-            let q4 = self.q_4[i];
-            let qarith = self.q_arith[i];
-            let qrange = self.q_range[i];
-            let qlogic = self.q_logic[i];
-            #[cfg(all(feature = "trace-print", feature = "std"))]
-            let qfixed = self.q_fixed_group_add[i];
-            #[cfg(all(feature = "trace-print", feature = "std"))]
-            let qvar = self.q_variable_group_add[i];
-            let pi = pi_vec[i];
-
-            let a = w_l[i];
-            let a_next = w_l[(i + 1) % self.n];
-            let b = w_r[i];
-            let b_next = w_r[(i + 1) % self.n];
-            let c = w_o[i];
-            let d = w_4[i];
-            let d_next = w_4[(i + 1) % self.n];
-
-            #[cfg(all(feature = "trace-print", feature = "std"))]
-            std::println!(
-                "--------------------------------------------\n
+        let next = self.next_row(i);
+
+        let qm = self.q_m[i];
+        let ql = self.q_l[i];
+        let qr = self.q_r[i];
+        let qo = self.q_o[i];
+        let qc = self.q_c[i];
+        let q4 = self.q_4[i];
+        let qarith = self.q_arith[i];
+        let qrange = self.q_range[i];
+        let qlogic = self.q_logic[i];
+        let pi = pi_vec[i];
+
+        let a = &self.variables[&self.w_l[i]];
+        let a_next = &self.variables[&self.w_l[next]];
+        let b = &self.variables[&self.w_r[i]];
+        let b_next = &self.variables[&self.w_r[next]];
+        let c = &self.variables[&self.w_o[i]];
+        let d = &self.variables[&self.w_4[i]];
+        let d_next = &self.variables[&self.w_4[next]];
+
+        qarith
+            * ((qm * a * b)
+                + (ql * a)
+                + (qr * b)
+                + (qo * c)
+                + (q4 * d)
+                + pi
+                + qc)
+            + qlogic
+                * (((delta(*a_next - four * a) - delta(*b_next - four * b))
+                    * c)
+                    + delta(*a_next - four * a)
+                    + delta(*b_next - four * b)
+                    + delta(*d_next - four * d)
+                    + match (qlogic == F::one(), qlogic == -F::one()) {
+                        (true, false) => {
+                            let a_bits = a.into_repr().to_bits_le();
+                            let b_bits = b.into_repr().to_bits_le();
+                            let a_and_b = a_bits
+                                .iter()
+                                .zip(b_bits)
+                                .map(|(a_bit, b_bit)| a_bit & b_bit)
+                                .collect::<Vec<bool>>();
+
+                            F::from_repr(
+                                <F as PrimeField>::BigInt::from_bits_le(
+                                    &a_and_b,
+                                ),
+                            )
+                            .unwrap()
+                                - *d
+                        }
+                        (false, true) => {
+                            let a_bits = a.into_repr().to_bits_le();
+                            let b_bits = b.into_repr().to_bits_le();
+                            let a_xor_b = a_bits
+                                .iter()
+                                .zip(b_bits)
+                                .map(|(a_bit, b_bit)| a_bit ^ b_bit)
+                                .collect::<Vec<bool>>();
+
+                            F::from_repr(
+                                <F as PrimeField>::BigInt::from_bits_le(
+                                    &a_xor_b,
+                                ),
+                            )
+                            .unwrap()
+                                - *d
+                        }
+                        (false, false) => F::zero(),
+                        _ => unreachable!(),
+                    })
+            + qrange
+                * (delta(*c - four * d)
+                    + delta(*b - four * c)
+                    + delta(*a - four * b)
+                    + delta(*d_next - four * a))
+    }
+
+    /// Utility function that checks on the "front-end"
+    /// side of the PLONK implementation if the identity polynomial
+    /// is satisfied for each of the [`StandardComposer`]'s gates.
+    ///
+    /// The recommended usage is to derive the std output and the std error to a
+    /// text file and analyze the gates there.
+    ///
+    /// # Panic
+    /// The gates are checked with
+    /// [`StandardComposer::first_unsatisfied_gate`]. The function will then
+    /// print the info of each circuit gate until the first one which does
+    /// not satisfy the equation or there are no more gates. If there is an
+    /// unsatisfied gate equation, the function will panic.
+    #[cfg(feature = "trace")]
+    pub fn check_circuit_satisfied(&self) {
+        let failing = self.first_unsatisfied_gate();
+        #[cfg(all(feature = "trace-print", feature = "std"))]
+        for i in 0..failing.map_or(self.n, |i| i + 1) {
+            self.print_gate(i);
+        }
+        if let Some(i) = failing {
+            panic!("Check failed at gate {}", i);
+        }
+    }
+
+    /// Prints the selectors and the witness of the `i`-th gate.
+    #[cfg(all(feature = "trace", feature = "trace-print", feature = "std"))]
+    fn print_gate(&self, i: usize) {
+        std::println!(
+            "--------------------------------------------\n
             #Gate Index = {}
             #Selector Polynomials:\n
             - qm -> {:?}\n
@@ -686,85 +1598,23 @@ where
             - w_r -> {:?}\n
             - w_o -> {:?}\n
             - w_4 -> {:?}\n",
-                i,
-                qm,
-                ql,
-                qr,
-                q4,
-                qo,
-                qc,
-                qarith,
-                qrange,
-                qlogic,
-                qfixed,
-                qvar,
-                a,
-                b,
-                c,
-                d
-            );
-
-            let k = qarith
-                * ((qm * a * b)
-                    + (ql * a)
-                    + (qr * b)
-                    + (qo * c)
-                    + (q4 * d)
-                    + pi
-                    + qc)
-                + qlogic
-                    * (((delta(*a_next - four * a)
-                        - delta(*b_next - four * b))
-                        * c)
-                        + delta(*a_next - four * a)
-                        + delta(*b_next - four * b)
-                        + delta(*d_next - four * d)
-                        + match (qlogic == F::one(), qlogic == -F::one()) {
-                            (true, false) => {
-                                let a_bits = a.into_repr().to_bits_le();
-                                let b_bits = b.into_repr().to_bits_le();
-                                let a_and_b = a_bits
-                                    .iter()
-                                    .zip(b_bits)
-                                    .map(|(a_bit, b_bit)| a_bit & b_bit)
-                                    .collect::<Vec<bool>>();
-
-                                F::from_repr(
-                                    <F as PrimeField>::BigInt::from_bits_le(
-                                        &a_and_b,
-                                    ),
-                                )
-                                .unwrap()
-                                    - *d
-                            }
-                            (false, true) => {
-                                let a_bits = a.into_repr().to_bits_le();
-                                let b_bits = b.into_repr().to_bits_le();
-                                let a_xor_b = a_bits
-                                    .iter()
-                                    .zip(b_bits)
-                                    .map(|(a_bit, b_bit)| a_bit ^ b_bit)
-                                    .collect::<Vec<bool>>();
-
-                                F::from_repr(
-                                    <F as PrimeField>::BigInt::from_bits_le(
-                                        &a_xor_b,
-                                    ),
-                                )
-                                .unwrap()
-                                    - *d
-                            }
-                            (false, false) => F::zero(),
-                            _ => unreachable!(),
-                        })
-                + qrange
-                    * (delta(*c - four * d)
-                        + delta(*b - four * c)
-                        + delta(*a - four * b)
-                        + delta(*d_next - four * a));
-
-            assert_eq!(k, F::zero(), "Check failed at gate {}", i,);
-        }
+            i,
+            self.q_m[i],
+            self.q_l[i],
+            self.q_r[i],
+            self.q_4[i],
+            self.q_o[i],
+            self.q_c[i],
+            self.q_arith[i],
+            self.q_range[i],
+            self.q_logic[i],
+            self.q_fixed_group_add[i],
+            self.q_variable_group_add[i],
+            self.variables[&self.w_l[i]],
+            self.variables[&self.w_r[i]],
+            self.variables[&self.w_o[i]],
+            self.variables[&self.w_4[i]],
+        );
     }
 }
 
@@ -873,6 +1723,80 @@ mod test {
         assert!(res.is_ok() && res2.is_ok())
     }
 
+    fn test_is_eq_with_output_soundness<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // A prover claiming that different inputs are equal, with the
+        // inverse hint matching its claim, is rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(2u64));
+                let b = composer.add_input(F::from(4u64));
+                let is_eq = composer.is_eq_with_output(a, b);
+                // The inverse hint is allocated right before the output.
+                let hint = Variable(is_eq.0 - 1);
+                composer.variables.insert(is_eq, F::one());
+                composer.variables.insert(hint, F::zero());
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // And so is a prover claiming that equal inputs are different.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(2u64));
+                let b = composer.add_input(F::from(2u64));
+                let is_eq = composer.is_eq_with_output(a, b);
+                composer.variables.insert(is_eq, F::zero());
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_assert_nonzero<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::from(3u64));
+                composer.assert_nonzero(x);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+
+        // Zero has no inverse, so the circuit is not satisfiable.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::zero());
+                composer.assert_nonzero(x);
+            },
+            32,
+        );
+        assert!(res.is_err());
+
+        // A prover supplying a wrong inverse is rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::from(3u64));
+                composer.assert_nonzero(x);
+                // The inverse hint is allocated right after the input.
+                let hint = Variable(x.0 + 1);
+                composer.variables.insert(hint, F::from(2u64));
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     fn test_conditional_select<F, P, PC>()
     where
         F: PrimeField,
@@ -900,6 +1824,91 @@ mod test {
         assert!(res.is_ok(), "{:?}", res.err().unwrap());
     }
 
+    fn test_add_gate_with_selectors<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // Verify that 3 * 4 + 5 * 2 = 22 only through explicit selectors.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let zero = composer.zero_var();
+                let three = composer.add_input(F::from(3u64));
+                let four = composer.add_input(F::from(4u64));
+                let five = composer.add_input(F::from(5u64));
+                let twenty_two = composer.add_input(F::from(22u64));
+                composer.add_gate_with_selectors(
+                    three,
+                    four,
+                    twenty_two,
+                    five,
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    -F::one(),
+                    F::from(2u64),
+                    F::zero(),
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    None,
+                );
+                composer.add_gate_with_selectors(
+                    twenty_two,
+                    zero,
+                    zero,
+                    zero,
+                    F::zero(),
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    -F::from(22u64),
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+
+        // A wrong output value must not verify.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let three = composer.add_input(F::from(3u64));
+                let four = composer.add_input(F::from(4u64));
+                let thirteen = composer.add_input(F::from(13u64));
+                composer.add_gate_with_selectors(
+                    three,
+                    four,
+                    thirteen,
+                    composer.zero_var(),
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    -F::one(),
+                    F::zero(),
+                    F::zero(),
+                    F::one(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    F::zero(),
+                    None,
+                );
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     // FIXME: Move this to integration tests
     fn test_multiple_proofs<F, P, PC>()
     where
@@ -948,10 +1957,502 @@ mod test {
         }
     }
 
+    fn test_permute_rows<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let two = composer.add_input(F::from(2u64));
+                let three = composer.add_input(F::from(3u64));
+                let six = composer.arithmetic_gate(|gate| {
+                    gate.witness(two, three, None).mul(F::one())
+                });
+                let nine = composer.arithmetic_gate(|gate| {
+                    gate.witness(six, three, None).add(F::one(), F::one())
+                });
+                let ten = composer.arithmetic_gate(|gate| {
+                    gate.witness(nine, two, None)
+                        .add(F::one(), F::zero())
+                        .pi(F::one())
+                });
+                composer.constrain_to_constant(ten, F::from(10u64), None);
+
+                // Reverse the rows of the whole circuit.
+                let order: Vec<_> =
+                    (0..composer.circuit_size()).rev().collect();
+                composer.permute_rows(&order).unwrap();
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_conflicting_public_inputs<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let one = composer.add_input(F::one());
+        let position = composer.circuit_size();
+        composer.arithmetic_gate(|gate| {
+            gate.witness(one, one, None)
+                .add(F::one(), F::one())
+                .pi(F::from(5u64))
+        });
+
+        // Assigning the same value again is allowed.
+        composer.insert_public_input(position, F::from(5u64));
+        assert!(composer.check_public_inputs().is_ok());
+
+        // Assigning a different value is a conflict.
+        composer.insert_public_input(position, F::from(6u64));
+        assert!(matches!(
+            composer.check_public_inputs(),
+            Err(Error::ConflictingPublicInput { position: p }) if p == position
+        ));
+
+        // The conflicting value does not overwrite the first one.
+        assert_eq!(
+            composer.public_inputs_sparse_store[&position],
+            F::from(5u64)
+        );
+    }
+
+    fn test_finalize<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        assert!(matches!(composer.finalize(), Err(Error::EmptyCircuit)));
+
+        let one = composer.add_input(F::one());
+        composer.arithmetic_gate(|gate| {
+            gate.witness(one, one, None)
+                .add(F::one(), F::one())
+                .pi(F::from(2u64))
+        });
+        let circuit = composer.finalize().unwrap();
+        assert_eq!(circuit.circuit_size, composer.circuit_size());
+        assert_eq!(circuit.num_public_inputs, 1);
+
+        let unused = composer.add_input(F::from(7u64));
+        assert!(matches!(
+            composer.finalize(),
+            Err(Error::DanglingVariable { variable }) if variable == unused
+        ));
+        composer.constrain_to_constant(unused, F::from(7u64), None);
+        assert!(composer.finalize().is_ok());
+
+        let n = composer.circuit_size();
+        composer.insert_public_input(n, F::one());
+        assert!(matches!(
+            composer.finalize(),
+            Err(Error::PublicInputOutOfRange {
+                position,
+                circuit_size,
+            }) if position == n && circuit_size == n
+        ));
+    }
+
+    fn test_first_unsatisfied_gate<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let mut outputs = Vec::new();
+        for value in 1..=6u64 {
+            let input = composer.add_input(F::from(value));
+            let gate_index = composer.circuit_size();
+            let output = composer.arithmetic_gate(|gate| {
+                gate.witness(input, input, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(value))
+            });
+            outputs.push((gate_index, output));
+            composer.range_gate(input, 8);
+        }
+        let first_sequential = |composer: &StandardComposer<F, P>| {
+            composer.first_unsatisfied_gate_sequential(
+                &composer.construct_dense_pi_vec(),
+            )
+        };
+        assert_eq!(composer.first_unsatisfied_gate(), None);
+        assert_eq!(first_sequential(&composer), None);
+
+        // Several gates are violated, the lowest one is reported.
+        for i in [4, 1, 5] {
+            let (_, output) = outputs[i];
+            *composer.variables.get_mut(&output).unwrap() += F::one();
+        }
+        let first = Some(outputs[1].0);
+        assert_eq!(composer.first_unsatisfied_gate(), first);
+        assert_eq!(first_sequential(&composer), first);
+    }
+
+    fn test_permute_rows_invalid_order<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let n = composer.circuit_size();
+
+        // Not a permutation of the rows.
+        assert!(matches!(
+            composer.permute_rows(&vec![0; n]),
+            Err(Error::InvalidRowOrder)
+        ));
+        assert!(matches!(
+            composer.permute_rows(&(0..n - 1).collect::<Vec<_>>()),
+            Err(Error::InvalidRowOrder)
+        ));
+
+        // Range gates read from the next row.
+        let witness = composer.add_input(F::from(5u64));
+        composer.range_gate(witness, 8);
+        let order = (0..composer.circuit_size()).rev().collect::<Vec<_>>();
+        assert!(matches!(
+            composer.permute_rows(&order),
+            Err(Error::InvalidRowOrder)
+        ));
+    }
+
+    fn test_permutation_mapping<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let n = composer.circuit_size();
+
+        let a = composer.add_input(F::one());
+        let b = composer.add_input(F::from(2u64));
+        let c = composer.arithmetic_gate(|gate| {
+            gate.witness(a, b, None).add(F::one(), F::one())
+        });
+        composer.arithmetic_gate(|gate| {
+            gate.witness(a, c, None).add(F::one(), F::one())
+        });
+
+        let mapping = composer.permutation_mapping();
+        let links_from = |wire| {
+            mapping
+                .iter()
+                .filter(|(from, _)| *from == wire)
+                .map(|(_, to)| *to)
+                .collect::<Vec<_>>()
+        };
+
+        // `a` is copied between both left wires.
+        assert_eq!(links_from(WireData::Left(n)), [WireData::Left(n + 1)]);
+        assert_eq!(links_from(WireData::Left(n + 1)), [WireData::Left(n)]);
+
+        // `c` is copied from the first output into the second right wire.
+        assert_eq!(links_from(WireData::Output(n)), [WireData::Right(n + 1)]);
+        assert_eq!(links_from(WireData::Right(n + 1)), [WireData::Output(n)]);
+
+        // `b` and the second output are used only once.
+        assert!(links_from(WireData::Right(n)).is_empty());
+        assert!(links_from(WireData::Output(n + 1)).is_empty());
+    }
+
+    /// Proves that `x * 10 + 3` is the Public Input `result`, computing `10`
+    /// with gates which [`StandardComposer::optimize`] can fold away.
+    fn foldable_gadget<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        x: F,
+        result: F,
+    ) where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let zero = composer.zero_var();
+        let two = composer.add_witness_to_circuit_description(F::from(2u64));
+        let five = composer.add_witness_to_circuit_description(F::from(5u64));
+
+        // Gates involving only constants.
+        let ten = composer.arithmetic_gate(|gate| {
+            gate.witness(two, five, None).mul(F::one())
+        });
+        composer.constrain_to_constant(ten, F::from(10u64), None);
+        let other_ten =
+            composer.add_witness_to_circuit_description(F::from(10u64));
+
+        // Identity gate.
+        let x = composer.add_input(x);
+        let x_copy = composer.arithmetic_gate(|gate| {
+            gate.witness(x, zero, None).add(F::one(), F::zero())
+        });
+
+        let output = composer.arithmetic_gate(|gate| {
+            gate.witness(x_copy, other_ten, None)
+                .mul(F::one())
+                .constant(F::from(3u64))
+        });
+        composer.constrain_to_constant(output, F::zero(), Some(-result));
+    }
+
+    fn test_optimize<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+        let result = F::from(43u64);
+
+        let mut prover = Prover::<F, P, PC>::new(b"optimize");
+        foldable_gadget(prover.mut_cs(), F::from(4u64), result);
+        let size = prover.circuit_size();
+
+        // The duplicated `10` constraint, the second `10` constant and the
+        // identity gate are removed.
+        assert_eq!(prover.mut_cs().optimize(), 3);
+        assert_eq!(prover.circuit_size(), size - 3);
+        assert_eq!(prover.mut_cs().optimize(), 0);
+        assert!(prover.mut_cs().finalize().is_ok());
+
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"optimize");
+        foldable_gadget(verifier.mut_cs(), F::zero(), result);
+        verifier.mut_cs().optimize();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // The optimized circuit still binds the Public Input.
+        let mut composer = StandardComposer::<F, P>::new();
+        foldable_gadget(&mut composer, F::zero(), result + F::one());
+        composer.optimize();
+        assert!(verifier
+            .verify(&proof, &vk, &composer.construct_dense_pi_vec())
+            .is_err());
+    }
+
+    /// Computes `out = a * b` for private `a` and `b`, exposing `out` as a
+    /// public output.
+    fn product_gadget<F, P>(composer: &mut StandardComposer<F, P>, a: F, b: F)
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let a = composer.add_input(a);
+        let b = composer.add_input(b);
+        let out = composer
+            .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+        composer.public_output(out);
+    }
+
+    fn test_public_output<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"public_output");
+        product_gadget(prover.mut_cs(), F::from(6u64), F::from(7u64));
+        let public_outputs = prover.cs.public_outputs();
+        assert_eq!(public_outputs, vec![F::from(42u64)]);
+        let positions = prover.cs.public_output_positions();
+        let proof = prover.prove(&ck).unwrap();
+
+        // The verifier builds the circuit without knowing the witnesses nor
+        // the output.
+        let mut verifier = Verifier::<F, P, PC>::new(b"public_output");
+        product_gadget(verifier.mut_cs(), F::zero(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+        assert_eq!(verifier.cs.public_output_positions(), positions);
+        let public_inputs = verifier.cs.construct_dense_pi_vec();
+
+        assert!(verifier
+            .verify_with_public_outputs(
+                &proof,
+                &vk,
+                &public_inputs,
+                &public_outputs
+            )
+            .is_ok());
+        assert!(verifier
+            .verify_with_public_outputs(
+                &proof,
+                &vk,
+                &public_inputs,
+                &[F::from(43u64)]
+            )
+            .is_err());
+        assert!(matches!(
+            verifier.verify_with_public_outputs(
+                &proof,
+                &vk,
+                &public_inputs,
+                &[]
+            ),
+            Err(Error::PublicOutputCountMismatch {
+                expected: 1,
+                found: 0
+            })
+        ));
+    }
+
+    /// Allocates the public inputs `values`, followed by a gate using them,
+    /// returning their positions.
+    fn public_inputs_gadget<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        values: [F; 3],
+    ) -> [usize; 3]
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let (a, a_position) = composer.add_public_input_variable(values[0]);
+        let b = composer.add_input(F::from(2u64));
+        let a_b = composer
+            .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+        let (c, c_position) = composer.add_public_input_variable(values[2]);
+        let (d, d_position) = composer.add_public_input_variable(values[1]);
+        composer.arithmetic_gate(|gate| {
+            gate.witness(a_b, c, None)
+                .add(F::one(), F::one())
+                .fan_in_3(F::one(), d)
+        });
+        [a_position, d_position, c_position]
+    }
+
+    fn test_add_public_input_variable<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+        let values = [3u64, 5, 7].map(F::from);
+
+        let mut prover = Prover::<F, P, PC>::new(b"pi_variable");
+        let positions = public_inputs_gadget(prover.mut_cs(), values);
+        let circuit_size = prover.circuit_size();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"pi_variable");
+        let verifier_positions =
+            public_inputs_gadget(verifier.mut_cs(), [F::zero(); 3]);
+        assert_eq!(verifier_positions, positions);
+        verifier.preprocess(&ck).unwrap();
+
+        // The Public Inputs are placed by position, in any order.
+        let build = |values: [F; 3]| {
+            let mut public_inputs = vec![F::zero(); circuit_size];
+            for i in [2, 0, 1] {
+                public_inputs[positions[i]] = values[i];
+            }
+            public_inputs
+        };
+        assert!(verifier.verify(&proof, &vk, &build(values)).is_ok());
+        assert!(verifier
+            .verify(&proof, &vk, &build([values[1], values[0], values[2]]))
+            .is_err());
+    }
+
+    fn test_add_constant_gate<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // Checks that `x * x = c` for the public constant `c`.
+        let gadget = |composer: &mut StandardComposer<F, P>, x: F, c: F| {
+            let x = composer.add_input(x);
+            let c = composer.add_constant_gate(c);
+            composer.arithmetic_gate(|gate| {
+                gate.witness(x, x, Some(c)).mul(F::one()).out(-F::one())
+            });
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"constant_gate");
+        gadget(prover.mut_cs(), F::from(3u64), F::from(9u64));
+        let proof = prover.prove(&ck).unwrap();
+
+        // The verifier reads the constant back from its own circuit.
+        let mut verifier = Verifier::<F, P, PC>::new(b"constant_gate");
+        gadget(verifier.mut_cs(), F::zero(), F::from(9u64));
+        verifier.preprocess(&ck).unwrap();
+        let public_inputs = verifier.cs.construct_dense_pi_vec();
+        assert!(public_inputs.contains(&F::from(9u64)));
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // A verifier expecting another constant rejects the proof, with the
+        // same verifier key.
+        let mut other = Verifier::<F, P, PC>::new(b"constant_gate");
+        gadget(other.mut_cs(), F::zero(), F::from(10u64));
+        other.preprocess(&ck).unwrap();
+        assert!(other.verifier_key == verifier.verifier_key);
+        let public_inputs = other.cs.construct_dense_pi_vec();
+        assert!(other.verify(&proof, &vk, &public_inputs).is_err());
+    }
+
+    fn test_set_blinding_factors<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        // Values of the two blinding rows of wires starting at `row`.
+        let blinding_rows = |composer: &StandardComposer<F, P>, row: usize| {
+            (row..row + 2)
+                .flat_map(|row| {
+                    [
+                        composer.w_l[row],
+                        composer.w_r[row],
+                        composer.w_o[row],
+                        composer.w_4[row],
+                    ]
+                })
+                .map(|var| composer.variables[&var])
+                .collect::<Vec<_>>()
+        };
+        let random_factors =
+            || [(); NUM_BLINDING_FACTORS].map(|_| F::rand(&mut OsRng));
+
+        // The rows added by `new` follow the zero gate.
+        let mut composer = StandardComposer::<F, P>::new();
+        let factors = random_factors();
+        composer.set_blinding_factors(factors);
+        assert_eq!(blinding_rows(&composer, 1), factors);
+
+        // Later blinding rows are set even if other variables were added
+        // before them.
+        composer.add_input(F::one());
+        composer.add_blinding_factors(&mut OsRng);
+        let factors = random_factors();
+        composer.set_blinding_factors(factors);
+        assert_eq!(blinding_rows(&composer, composer.n - 3), factors);
+    }
+
     // Tests for Bls12_381
     batch_test_field_params!(
         [
-            test_initial_circuit_size
+            test_initial_circuit_size,
+            test_permute_rows_invalid_order,
+            test_permutation_mapping,
+            test_conflicting_public_inputs,
+            test_finalize,
+            test_first_unsatisfied_gate,
+            test_set_blinding_factors
         ],
         [] => (
             Bls12_381,
@@ -963,7 +2464,13 @@ mod test {
     // Tests for Bls12_377
     batch_test_field_params!(
         [
-            test_initial_circuit_size
+            test_initial_circuit_size,
+            test_permute_rows_invalid_order,
+            test_permutation_mapping,
+            test_conflicting_public_inputs,
+            test_finalize,
+            test_first_unsatisfied_gate,
+            test_set_blinding_factors
         ],
         [] => (
             Bls12_377,
@@ -977,8 +2484,16 @@ mod test {
             test_prove_verify,
             test_correct_is_zero_with_output,
             test_correct_is_eq_with_output,
+            test_is_eq_with_output_soundness,
+            test_assert_nonzero,
             test_conditional_select,
-            test_multiple_proofs
+            test_add_gate_with_selectors,
+            test_permute_rows,
+            test_multiple_proofs,
+            test_optimize,
+            test_public_output,
+            test_add_public_input_variable,
+            test_add_constant_gate
         ],
         [] => (
             Bls12_381,
@@ -992,8 +2507,16 @@ mod test {
             test_prove_verify,
             test_correct_is_zero_with_output,
             test_correct_is_eq_with_output,
+            test_is_eq_with_output_soundness,
+            test_assert_nonzero,
             test_conditional_select,
-            test_multiple_proofs
+            test_add_gate_with_selectors,
+            test_permute_rows,
+            test_multiple_proofs,
+            test_optimize,
+            test_public_output,
+            test_add_public_input_variable,
+            test_add_constant_gate
         ],
         [] => (
             Bls12_377,
@@ -0,0 +1,219 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Integer Division Gates
+
+use crate::constraint_system::{StandardComposer, Variable};
+use ark_ec::TEModelParameters;
+use ark_ff::{BigInteger, PrimeField};
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Divides `a` by `b`, read as unsigned integers, and returns the
+    /// quotient `q` and the remainder `r` of the division.
+    ///
+    /// `q` and `r` are computed out of circuit and constrained so that
+    /// `a = q * b + r` with `q` and `r` both of `num_bits` bits and `r < b`.
+    /// The circuit is not satisfiable if `b` is zero, or if the quotient does
+    /// not fit in `num_bits` bits.
+    ///
+    /// This function adds one arithmetic gate for the division, one for the
+    /// `r < b` check, and the gates of three [`range_gate`]s of `num_bits`
+    /// bits.
+    ///
+    /// # Panics
+    /// This function will panic if `num_bits` is not even, or if `q * b + r`
+    /// might wrap around the field modulus, ie.
+    /// `2 * num_bits + 3 > F::size_in_bits()`.
+    ///
+    /// [`range_gate`]: StandardComposer::range_gate
+    pub fn div_rem(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        num_bits: usize,
+    ) -> (Variable, Variable) {
+        // `r < b` and `r < 2^num_bits` bound `b` by `2^(num_bits + 1)`, so
+        // `q * b + r` stays below `2^(2 * num_bits + 2)`.
+        assert!(
+            2 * num_bits + 3 <= F::size_in_bits(),
+            "the division of {} bit integers might wrap around the modulus",
+            num_bits
+        );
+
+        // Schoolbook long division over the bits of `a`.
+        let b_value = self.variables[&b];
+        let mut q_value = F::zero();
+        let mut r_value = F::zero();
+        for bit in self.variables[&a].into_repr().to_bits_be() {
+            q_value.double_in_place();
+            r_value.double_in_place();
+            if bit {
+                r_value += F::one();
+            }
+            if !b_value.is_zero() && r_value.into_repr() >= b_value.into_repr()
+            {
+                r_value -= b_value;
+                q_value += F::one();
+            }
+        }
+        let q = self.add_input(q_value);
+        let r = self.add_input(r_value);
+
+        // a = q * b + r
+        self.arithmetic_gate(|gate| {
+            gate.witness(q, b, Some(a))
+                .mul(F::one())
+                .fan_in_3(F::one(), r)
+        });
+
+        // r < b, ie. b - r - 1 >= 0
+        let gap = self.arithmetic_gate(|gate| {
+            gate.witness(b, r, None)
+                .add(F::one(), -F::one())
+                .constant(-F::one())
+        });
+
+        self.range_gate(q, num_bits);
+        self.range_gate(r, num_bits);
+        self.range_gate(gap, num_bits);
+
+        (q, r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    fn test_exact_division<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(42u64));
+                let b = composer.add_input(F::from(6u64));
+                let (q, r) = composer.div_rem(a, b, 8);
+                composer.constrain_to_constant(q, F::from(7u64), None);
+                composer.constrain_to_constant(r, F::zero(), None);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_non_exact_division<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(47u64));
+                let b = composer.add_input(F::from(6u64));
+                let (q, r) = composer.div_rem(a, b, 8);
+                composer.constrain_to_constant(q, F::from(7u64), None);
+                composer.constrain_to_constant(r, F::from(5u64), None);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+
+        // A dividend smaller than the divisor leaves it all as remainder.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(5u64));
+                let b = composer.add_input(F::from(6u64));
+                let (q, r) = composer.div_rem(a, b, 8);
+                composer.constrain_to_constant(q, F::zero(), None);
+                composer.constrain_to_constant(r, F::from(5u64), None);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_division_soundness<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // A prover claiming 47 = 6 * 6 + 11 is rejected, as the remainder is
+        // not smaller than the divisor.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(47u64));
+                let b = composer.add_input(F::from(6u64));
+                let (q, r) = composer.div_rem(a, b, 8);
+                composer.variables.insert(q, F::from(6u64));
+                composer.variables.insert(r, F::from(11u64));
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // And so is a prover claiming a wrong quotient.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(47u64));
+                let b = composer.add_input(F::from(6u64));
+                let (q, _) = composer.div_rem(a, b, 8);
+                composer.variables.insert(q, F::from(8u64));
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // Dividing by zero is not satisfiable.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_input(F::from(47u64));
+                let b = composer.zero_var();
+                composer.div_rem(a, b, 8);
+            },
+            200,
+        );
+        assert!(res.is_err());
+    }
+
+    // Test for Bls12_381
+    batch_test!(
+        [
+            test_exact_division,
+            test_non_exact_division,
+            test_division_soundness
+        ],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test!(
+        [
+            test_exact_division,
+            test_non_exact_division,
+            test_division_soundness
+        ],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -0,0 +1,389 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Links between Proofs Sharing a Witness
+//!
+//! Two circuits proven independently may share a private input, e.g. the
+//! same secret key used in both of them. A link is a copy constraint across
+//! the two proofs: it convinces the verifier that a [`Variable`] of the
+//! first circuit and a [`Variable`] of the second one hold the same value,
+//! without revealing it.
+//!
+//! # Protocol
+//!
+//! 1. The provers agree on the shared `value` and on a secret `blinding` factor
+//!    drawn uniformly at random among the scalars of the embedded curve.
+//! 2. Each circuit calls [`StandardComposer::link_witness`] on its
+//!    [`Variable`]s holding `value` and `blinding`. This constrains the
+//!    Pedersen commitment `C = value * G + blinding * H` over the embedded
+//!    curve, where `G` is the generator of the curve and `H` is the
+//!    [`blinding_generator`], and exposes the coordinates of `C` as public
+//!    outputs of the circuit.
+//! 3. The verifier verifies both proofs with their public outputs, and checks
+//!    with [`WitnessLink::check`] that both of them expose the same commitment
+//!    `C`.
+//!
+//! # Soundness
+//!
+//! Each proof shows that the prover knows an opening `(value, blinding)` of
+//! its commitment, with `value` wired into its circuit. The Pedersen
+//! commitment is binding under the discrete logarithm assumption on the
+//! embedded curve, since nobody knows the discrete logarithm of `H` with
+//! respect to `G`. Two proofs exposing the same `C` therefore hold the same
+//! `value`, up to the order of the prime subgroup of the embedded curve.
+//! Values of the circuit field below that order, e.g. range constrained
+//! values, are equal as field elements.
+//!
+//! The commitment is perfectly hiding as long as `blinding` is uniform and
+//! kept secret. The link does however reveal to the verifier that both
+//! proofs share a witness, and `blinding` must not be reused to link other
+//! values.
+
+use crate::{
+    constraint_system::{variable::Variable, StandardComposer},
+    error::Error,
+};
+use ark_ec::{
+    twisted_edwards_extended::{
+        GroupAffine as TEGroupAffine, GroupProjective as TEGroupProjective,
+    },
+    AffineCurve, ProjectiveCurve, TEModelParameters,
+};
+use ark_ff::{Field, PrimeField};
+use blake2::{Blake2b, Digest};
+use num_traits::Zero;
+
+/// Seed hashed into the `x` coordinate the [`blinding_generator`] is derived
+/// from.
+const BLINDING_GENERATOR_SEED: &[u8] = b"plonk-witness-link-blinding";
+
+/// Returns the generator `H` the blinding factor of the commitments of a
+/// [`WitnessLink`] is multiplied with.
+///
+/// `H` is derived by hashing a fixed seed to an `x` coordinate, incremented
+/// until it is the one of a point of the embedded curve, and clearing the
+/// cofactor of that point. Its discrete logarithm with respect to the
+/// generator of the curve is therefore unknown.
+pub fn blinding_generator<F, P>() -> TEGroupAffine<P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    let mut x =
+        F::from_le_bytes_mod_order(&Blake2b::digest(BLINDING_GENERATOR_SEED));
+    loop {
+        if let Some(point) = TEGroupAffine::<P>::get_point_from_x(x, false) {
+            let point = point.mul_by_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        x += F::one();
+    }
+}
+
+/// Computes out of the circuit the commitment `value * G + blinding * H`
+/// constrained by [`StandardComposer::link_witness`].
+pub fn commit_shared_witness<F, P>(value: F, blinding: F) -> TEGroupAffine<P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+    let generator = TEGroupProjective::from(TEGroupAffine::<P>::new(x, y));
+    let blinding_generator =
+        TEGroupProjective::from(blinding_generator::<F, P>());
+    (generator.mul(value.into_repr())
+        + blinding_generator.mul(blinding.into_repr()))
+    .into_affine()
+}
+
+/// Positions, among the dense Public Inputs of a circuit, of the coordinates
+/// of the commitment to a shared witness, as returned by
+/// [`StandardComposer::link_witness`].
+///
+/// The positions only depend on the circuit description, so that the
+/// verifier obtains them by building the circuit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WitnessLink {
+    /// Position of the `X`-coordinate of the commitment
+    x: usize,
+
+    /// Position of the `Y`-coordinate of the commitment
+    y: usize,
+}
+
+impl WitnessLink {
+    /// Returns the coordinates of the commitment to the shared witness read
+    /// from the dense `public_inputs` of a proof, with its public outputs
+    /// written in by [`StandardComposer::with_public_outputs`].
+    pub fn commitment<F>(&self, public_inputs: &[F]) -> Result<(F, F), Error>
+    where
+        F: Field,
+    {
+        let read = |position: usize| {
+            public_inputs.get(position).copied().ok_or(
+                Error::PublicInputOutOfRange {
+                    position,
+                    circuit_size: public_inputs.len(),
+                },
+            )
+        };
+        Ok((read(self.x)?, read(self.y)?))
+    }
+
+    /// Checks that the proof with the dense `public_inputs` and the proof
+    /// with the dense `other_public_inputs`, linked by `self` and `other`
+    /// respectively, commit to the same shared witness.
+    ///
+    /// Both proofs must have been verified against these Public Inputs for
+    /// the check to be meaningful. Returns [`Error::WitnessLinkMismatch`] if
+    /// the commitments differ.
+    pub fn check<F>(
+        &self,
+        public_inputs: &[F],
+        other: &WitnessLink,
+        other_public_inputs: &[F],
+    ) -> Result<(), Error>
+    where
+        F: Field,
+    {
+        if self.commitment(public_inputs)?
+            == other.commitment(other_public_inputs)?
+        {
+            Ok(())
+        } else {
+            Err(Error::WitnessLinkMismatch)
+        }
+    }
+}
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Links `value` to the witness of another proof, by constraining the
+    /// commitment `value * G + blinding * H` and exposing its coordinates as
+    /// public outputs of the circuit. See the [module documentation] for the
+    /// protocol.
+    ///
+    /// This function adds the gates of two [`fixed_base_scalar_mul`]s and of
+    /// a [`point_addition_gate`], and two public outputs.
+    ///
+    /// [module documentation]: crate::constraint_system::ecc::link
+    /// [`fixed_base_scalar_mul`]: StandardComposer::fixed_base_scalar_mul
+    /// [`point_addition_gate`]: StandardComposer::point_addition_gate
+    pub fn link_witness(
+        &mut self,
+        value: Variable,
+        blinding: Variable,
+    ) -> WitnessLink {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = TEGroupAffine::new(x, y);
+
+        let value_point = self.fixed_base_scalar_mul(value, generator);
+        let blinding_point =
+            self.fixed_base_scalar_mul(blinding, blinding_generator::<F, P>());
+        let commitment = self.point_addition_gate(value_point, blinding_point);
+
+        WitnessLink {
+            x: self.public_output(*commitment.x()),
+            y: self.public_output(*commitment.y()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test,
+        commitment::HomomorphicCommitment,
+        proof_system::{Prover, Verifier},
+        util,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ff::UniformRand;
+    use rand::rngs::OsRng;
+
+    fn random_blinding<F, P>() -> F
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        util::from_embedded_curve_scalar::<F, P>(P::ScalarField::rand(
+            &mut OsRng,
+        ))
+    }
+
+    /// Proves the knowledge of a square root of `49`.
+    fn square_root_circuit<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        secret: F,
+        blinding: F,
+    ) -> WitnessLink
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let secret = composer.add_input(secret);
+        let blinding = composer.add_input(blinding);
+        let square = composer.arithmetic_gate(|gate| {
+            gate.witness(secret, secret, None).mul(F::one())
+        });
+        composer.constrain_to_constant(square, F::from(49u64), None);
+        composer.link_witness(secret, blinding)
+    }
+
+    /// Proves the knowledge of a byte.
+    fn byte_circuit<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        secret: F,
+        blinding: F,
+    ) -> WitnessLink
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let secret = composer.add_input(secret);
+        let blinding = composer.add_input(blinding);
+        composer.range_gate(secret, 8);
+        composer.link_witness(secret, blinding)
+    }
+
+    /// Proves `circuit` over `secret` and verifies the proof, replacing its
+    /// public outputs with `public_outputs` if any, and returns the link of
+    /// the circuit together with the dense Public Inputs of the proof.
+    fn prove_and_verify<F, P, PC>(
+        circuit: fn(&mut StandardComposer<F, P>, F, F) -> WitnessLink,
+        secret: F,
+        blinding: F,
+        public_outputs: Option<Vec<F>>,
+    ) -> Result<(WitnessLink, Vec<F>), Error>
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 1024, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 1024, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"link");
+        circuit(prover.mut_cs(), secret, blinding);
+        let public_outputs =
+            public_outputs.unwrap_or_else(|| prover.cs.public_outputs());
+        let proof = prover.prove(&ck)?;
+
+        // The verifier builds the circuit without knowing the secret.
+        let mut verifier = Verifier::<F, P, PC>::new(b"link");
+        let link = circuit(verifier.mut_cs(), F::zero(), F::zero());
+        verifier.preprocess(&ck)?;
+        let public_inputs = verifier.cs.construct_dense_pi_vec();
+        verifier.verify_with_public_outputs(
+            &proof,
+            &vk,
+            &public_inputs,
+            &public_outputs,
+        )?;
+        let public_inputs = verifier
+            .cs
+            .with_public_outputs(&public_inputs, &public_outputs)?;
+        Ok((link, public_inputs))
+    }
+
+    fn test_witness_link<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let secret = F::from(7u64);
+        let blinding = random_blinding::<F, P>();
+
+        let (link_a, public_inputs_a) = prove_and_verify::<F, P, PC>(
+            square_root_circuit,
+            secret,
+            blinding,
+            None,
+        )
+        .unwrap();
+        let (link_b, public_inputs_b) =
+            prove_and_verify::<F, P, PC>(byte_circuit, secret, blinding, None)
+                .unwrap();
+
+        let commitment = commit_shared_witness::<F, P>(secret, blinding);
+        assert_eq!(
+            link_a.commitment(&public_inputs_a).unwrap(),
+            (commitment.x, commitment.y)
+        );
+        assert!(link_a
+            .check(&public_inputs_a, &link_b, &public_inputs_b)
+            .is_ok());
+    }
+
+    fn test_witness_link_tampered<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let secret = F::from(7u64);
+        let blinding = random_blinding::<F, P>();
+
+        let (link_a, public_inputs_a) = prove_and_verify::<F, P, PC>(
+            square_root_circuit,
+            secret,
+            blinding,
+            None,
+        )
+        .unwrap();
+
+        // A valid proof over another secret does not pass the link.
+        let other_secret = F::from(8u64);
+        let (link_b, public_inputs_b) = prove_and_verify::<F, P, PC>(
+            byte_circuit,
+            other_secret,
+            blinding,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            link_a.check(&public_inputs_a, &link_b, &public_inputs_b),
+            Err(Error::WitnessLinkMismatch)
+        ));
+
+        // And claiming the commitment of the other proof makes the proof
+        // over another secret fail.
+        let commitment = commit_shared_witness::<F, P>(secret, blinding);
+        assert!(prove_and_verify::<F, P, PC>(
+            byte_circuit,
+            other_secret,
+            blinding,
+            Some(vec![commitment.x, commitment.y]),
+        )
+        .is_err());
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [test_witness_link, test_witness_link_tampered],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [test_witness_link, test_witness_link_tampered],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
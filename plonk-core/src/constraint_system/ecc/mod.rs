@@ -7,7 +7,10 @@
 //! Elliptic Curve Gates
 
 pub mod curve_addition;
+pub mod link;
+pub mod scalar_field;
 pub mod scalar_mul;
+pub mod schnorr;
 
 use crate::constraint_system::{variable::Variable, StandardComposer};
 use ark_ec::{
@@ -92,6 +95,37 @@ where
         point
     }
 
+    /// Builds a [`Point`] from `x` and `y` coordinates, constraining them to
+    /// satisfy the twisted Edwards curve equation:
+    ///
+    /// ```text
+    /// a * x^2 + y^2 = 1 + d * x^2 * y^2
+    /// ```
+    ///
+    /// Unlike [`Point::new`], this guarantees that the resulting [`Point`] lies
+    /// on the embedded curve.
+    pub fn add_point(&mut self, x: Variable, y: Variable) -> Point<P> {
+        let x_sq =
+            self.arithmetic_gate(|gate| gate.witness(x, x, None).mul(F::one()));
+        let y_sq =
+            self.arithmetic_gate(|gate| gate.witness(y, y, None).mul(F::one()));
+        let x_sq_y_sq = self.arithmetic_gate(|gate| {
+            gate.witness(x_sq, y_sq, None).mul(F::one())
+        });
+
+        // a * x^2 + y^2 - d * x^2 * y^2 - 1 = 0
+        let zero = self.zero_var;
+        self.arithmetic_gate(|gate| {
+            gate.witness(x_sq, y_sq, Some(zero))
+                .add(P::COEFF_A, F::one())
+                .fan_in_3(-P::COEFF_D, x_sq_y_sq)
+                .constant(-F::one())
+                .out(F::zero())
+        });
+
+        Point::new(x, y)
+    }
+
     /// Add the provided affine point as a circuit description and return its
     /// constrained witness value
     pub fn add_affine_to_circuit_description(
@@ -215,6 +249,7 @@ mod test {
     };
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
+    use ark_ec::group::Group;
 
     fn test_conditional_select_point<F, P, PC>()
     where
@@ -278,11 +313,53 @@ mod test {
         .expect("test failed");
     }
 
+    fn test_add_point<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                let generator = TEGroupAffine::<P>::new(x, y);
+
+                let x = composer.add_input(x);
+                let y = composer.add_input(y);
+                let point = composer.add_point(x, y);
+
+                let double = composer.point_addition_gate(point, point);
+                composer.assert_equal_public_point(double, generator.double());
+            },
+            32,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_add_point_off_curve<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::from(10u64));
+                let y = composer.add_input(F::from(20u64));
+                composer.add_point(x, y);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     // Bls12-381 tests
     batch_test!(
         [
             test_conditional_select_point,
-            test_conditional_point_neg
+            test_conditional_point_neg,
+            test_add_point,
+            test_add_point_off_curve
         ],
         [] => (
             Bls12_381,
@@ -294,7 +371,9 @@ mod test {
     batch_test!(
         [
             test_conditional_select_point,
-            test_conditional_point_neg
+            test_conditional_point_neg,
+            test_add_point,
+            test_add_point_off_curve
         ],
         [] => (
             Bls12_377,
@@ -0,0 +1,442 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Embedded Curve Scalar Field Gates
+//!
+//! The scalars of the embedded curve are held by [`Variable`]s of the base
+//! field, the scalar field of the pairing friendly curve, whose modulus is
+//! larger. The gadgets of this module compute modulo the order of the
+//! scalar field of the embedded curve instead, returning canonical
+//! representatives, i.e. values less than this order.
+//!
+//! The integer equations behind the gadgets can exceed the base field
+//! modulus, so that they are checked on limbs of [`LIMB_BITS`] bits, with
+//! range constraints bounding every limb and carry for the equations on
+//! limbs to hold over the integers.
+
+use crate::{
+    constraint_system::{StandardComposer, Variable},
+    util::from_embedded_curve_scalar,
+};
+use ark_ec::{ModelParameters, TEModelParameters};
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+
+/// Number of bits of the limbs of the embedded curve scalars.
+pub const LIMB_BITS: usize = 64;
+
+/// Layout of the limbs of the embedded curve scalars.
+struct Layout {
+    /// Number of limbs of a scalar.
+    limbs: usize,
+
+    /// Number of bits of the most significant limb of a scalar.
+    top_bits: usize,
+
+    /// Number of bits of the shifted carries of the limbs of a product.
+    carry_bits: usize,
+}
+
+impl Layout {
+    /// Returns the layout of the limbs of the scalars of `P`, in the base
+    /// field `F`.
+    ///
+    /// # Panics
+    /// Panics if the scalars of `P` do not fit in `F`.
+    fn new<F, P>() -> Self
+    where
+        F: PrimeField,
+        P: ModelParameters<BaseField = F>,
+    {
+        // Range constraints are over an even number of bits.
+        let bits = (P::ScalarField::size_in_bits() + 1) & !1;
+        assert!(
+            bits < F::size_in_bits(),
+            "the embedded curve scalars do not fit in the base field"
+        );
+        let limbs = (bits + LIMB_BITS - 1) / LIMB_BITS;
+        // A limb of the product is the sum of at most `2 * limbs` products
+        // of limbs, so that its carry is less than `2 * limbs * 2^LIMB_BITS`
+        // in absolute value.
+        let log_limbs = usize::BITS as usize - limbs.leading_zeros() as usize;
+        let carry_bits = (LIMB_BITS + log_limbs + 3) & !1;
+        Self {
+            limbs,
+            top_bits: bits - LIMB_BITS * (limbs - 1),
+            carry_bits,
+        }
+    }
+
+    /// Returns the number of bits of the `i`-th limb of a scalar.
+    fn limb_bits(&self, i: usize) -> usize {
+        if i + 1 == self.limbs {
+            self.top_bits
+        } else {
+            LIMB_BITS
+        }
+    }
+}
+
+/// Returns the `count` limbs of [`LIMB_BITS`] bits of `value`, least
+/// significant first.
+fn to_limbs<F, B>(value: B, count: usize) -> Vec<F>
+where
+    F: PrimeField,
+    B: BigInteger,
+{
+    let mut bits = value.to_bits_le();
+    bits.resize(count * LIMB_BITS, false);
+    bits.chunks(LIMB_BITS)
+        .take(count)
+        .map(|limb| {
+            F::from_repr(F::BigInt::from_bits_le(limb))
+                .expect("a limb is less than the modulus")
+        })
+        .collect()
+}
+
+/// Returns the order of the scalar field of `P` as an element of `F`.
+fn embedded_modulus<F, P>() -> F
+where
+    F: PrimeField,
+    P: ModelParameters<BaseField = F>,
+{
+    let modulus = <P::ScalarField as PrimeField>::Params::MODULUS;
+    F::from_le_bytes_mod_order(&modulus.to_bytes_le())
+}
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Allocates a [`Variable`] holding the embedded curve scalar `value`,
+    /// constrained to be a canonical representative, so that it can be
+    /// given to [`StandardComposer::embedded_scalar_add`].
+    pub fn add_embedded_scalar(&mut self, value: P::ScalarField) -> Variable {
+        let scalar = self.add_input(from_embedded_curve_scalar::<F, P>(value));
+        let limbs = self.embedded_scalar_limbs(scalar);
+        self.embedded_scalar_canonical_gate(&limbs);
+        scalar
+    }
+
+    /// Returns a [`Variable`] constrained to `a + b` modulo the order of the
+    /// scalar field of the embedded curve.
+    ///
+    /// `a` and `b` must be canonical representatives, as the outputs of the
+    /// gadgets of this module are, otherwise the result is unconstrained.
+    /// The result is a canonical representative.
+    pub fn embedded_scalar_add(
+        &mut self,
+        a: Variable,
+        b: Variable,
+    ) -> Variable {
+        let modulus = embedded_modulus::<F, P>();
+        let sum = self.variables[&a] + self.variables[&b];
+        let wrapped = sum.into_repr() >= modulus.into_repr();
+        let c = self.add_input(if wrapped { sum - modulus } else { sum });
+        let k = self.add_input(F::from(wrapped));
+        self.boolean_gate(k);
+
+        // a + b - c - k * r = 0
+        self.arithmetic_gate(|gate| {
+            gate.witness(a, b, Some(c))
+                .add(F::one(), F::one())
+                .out(-F::one())
+                .fan_in_3(-modulus, k)
+        });
+        let limbs = self.embedded_scalar_limbs(c);
+        self.embedded_scalar_canonical_gate(&limbs);
+        c
+    }
+
+    /// Returns a [`Variable`] constrained to `a * b` modulo the order of the
+    /// scalar field of the embedded curve.
+    ///
+    /// `a` and `b` must be canonical representatives, as the outputs of the
+    /// gadgets of this module are. The result is a canonical representative.
+    ///
+    /// The gadget checks `a * b = q * r + c` over the integers, where `r` is
+    /// the order and `c` the result, on the limbs of the operands.
+    pub fn embedded_scalar_mul(
+        &mut self,
+        a: Variable,
+        b: Variable,
+    ) -> Variable {
+        let layout = Layout::new::<F, P>();
+        let m = layout.limbs;
+        let modulus = embedded_modulus::<F, P>();
+        let r = to_limbs::<F, _>(
+            <P::ScalarField as PrimeField>::Params::MODULUS,
+            m,
+        );
+
+        // Compute the quotient and remainder out of the circuit.
+        let (a_value, b_value) = (self.variables[&a], self.variables[&b]);
+        let to_scalar = |value: F| {
+            P::ScalarField::from_le_bytes_mod_order(
+                &value.into_repr().to_bytes_le(),
+            )
+        };
+        let c_value = from_embedded_curve_scalar::<F, P>(
+            to_scalar(a_value) * to_scalar(b_value),
+        );
+        // The operands being canonical, the quotient is less than the order,
+        // and thus than the base field modulus.
+        let q_value = (a_value * b_value - c_value)
+            * modulus.inverse().expect("the order is not zero");
+
+        let a_limbs = self.embedded_scalar_limbs(a);
+        let b_limbs = self.embedded_scalar_limbs(b);
+        let c = self.add_input(c_value);
+        let c_limbs = self.embedded_scalar_limbs(c);
+        self.embedded_scalar_canonical_gate(&c_limbs);
+        let q_limbs = to_limbs::<F, _>(q_value.into_repr(), m)
+            .into_iter()
+            .map(|limb| {
+                let limb = self.add_input(limb);
+                self.range_gate(limb, LIMB_BITS);
+                limb
+            })
+            .collect::<Vec<_>>();
+
+        let products = a_limbs
+            .iter()
+            .map(|a_i| {
+                b_limbs
+                    .iter()
+                    .map(|b_j| {
+                        self.arithmetic_gate(|gate| {
+                            gate.witness(*a_i, *b_j, None).mul(F::one())
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // For each limb `s` of the product, checks that
+        // `sum(a_i * b_j) - sum(q_i * r_j) - c_s + carry_{s - 1}` is
+        // `carry_s * 2^LIMB_BITS`, where `i + j = s`. The carries are shifted
+        // by `shift` to be range constrained, and the last one is zero.
+        let shift = F::from(2u64).pow([layout.carry_bits as u64 - 1]);
+        let base = F::from(2u64).pow([LIMB_BITS as u64]);
+        let base_inverse = base.inverse().expect("the base is not zero");
+        let mut carry = None;
+        for s in 0..(2 * m - 1) {
+            let mut terms = Vec::new();
+            let mut constant = F::zero();
+            for i in s.saturating_sub(m - 1)..=s.min(m - 1) {
+                let j = s - i;
+                terms.push((F::one(), products[i][j]));
+                terms.push((-r[j], q_limbs[i]));
+            }
+            if let Some(c_limb) = c_limbs.get(s) {
+                terms.push((-F::one(), *c_limb));
+            }
+            if let Some(shifted_carry) = carry {
+                terms.push((F::one(), shifted_carry));
+                constant -= shift;
+            }
+            carry = if s + 1 < 2 * m - 1 {
+                // The previous carry is among the terms.
+                let limb_value = terms
+                    .iter()
+                    .map(|(q, w)| *q * self.variables[w])
+                    .sum::<F>()
+                    + constant;
+                let carry_value = limb_value * base_inverse;
+                let shifted_carry = self.add_input(carry_value + shift);
+                self.range_gate(shifted_carry, layout.carry_bits);
+                terms.push((-base, shifted_carry));
+                constant += shift * base;
+                Some(shifted_carry)
+            } else {
+                None
+            };
+            self.wide_gate(|gate| {
+                for (q, w) in &terms {
+                    gate.wire(*q, *w);
+                }
+                gate.constant(constant)
+            });
+        }
+        c
+    }
+
+    /// Decomposes `x` into the limbs of the layout of the embedded curve
+    /// scalars, constrained to fit in their bits and to add up to `x`.
+    fn embedded_scalar_limbs(&mut self, x: Variable) -> Vec<Variable> {
+        let layout = Layout::new::<F, P>();
+        let base = F::from(2u64).pow([LIMB_BITS as u64]);
+        let value = self.variables[&x].into_repr();
+        let limbs = to_limbs::<F, _>(value, layout.limbs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, limb)| {
+                let limb = self.add_input(limb);
+                self.range_gate(limb, layout.limb_bits(i));
+                limb
+            })
+            .collect::<Vec<_>>();
+        self.wide_gate(|gate| {
+            let mut power = F::one();
+            for limb in &limbs {
+                gate.wire(power, *limb);
+                power *= base;
+            }
+            gate.wire(-F::one(), x)
+        });
+        limbs
+    }
+
+    /// Constrains the scalar of `limbs`, as returned by
+    /// [`StandardComposer::embedded_scalar_limbs`], to be less than the
+    /// order of the scalar field of the embedded curve.
+    ///
+    /// The gadget checks `c + d = r - 1` on the limbs, where `c` is the
+    /// scalar, `r` is the order and `d` is a non-negative integer.
+    fn embedded_scalar_canonical_gate(&mut self, limbs: &[Variable]) {
+        let m = limbs.len();
+        let modulus = embedded_modulus::<F, P>();
+        let bound = to_limbs::<F, _>((modulus - F::one()).into_repr(), m);
+        let base = F::from(2u64).pow([LIMB_BITS as u64]);
+        let base_inverse = base.inverse().expect("the base is not zero");
+
+        let c_value = limbs
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, limb| acc * base + self.variables[limb]);
+        let d_limbs =
+            to_limbs::<F, _>((modulus - F::one() - c_value).into_repr(), m);
+
+        // c_i + d_i + carry_{i - 1} - carry_i * 2^LIMB_BITS = (r - 1)_i
+        let mut carry = None;
+        let mut carry_value = F::zero();
+        for i in 0..m {
+            let d = self.add_input(d_limbs[i]);
+            self.range_gate(d, LIMB_BITS);
+            let mut terms = vec![(F::one(), limbs[i]), (F::one(), d)];
+            if let Some(carry) = carry {
+                terms.push((F::one(), carry));
+            }
+            carry = if i + 1 < m {
+                carry_value =
+                    (self.variables[&limbs[i]] + d_limbs[i] + carry_value
+                        - bound[i])
+                        * base_inverse;
+                let next = self.add_input(carry_value);
+                self.boolean_gate(next);
+                terms.push((-base, next));
+                Some(next)
+            } else {
+                None
+            };
+            self.wide_gate(|gate| {
+                for (q, w) in &terms {
+                    gate.wire(*q, *w);
+                }
+                gate.constant(-bound[i])
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ff::Field;
+
+    fn test_embedded_scalar_arithmetic<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                // The verifier builds the circuit again, so the scalars are
+                // fixed, spanning all of the limbs.
+                let x = -P::ScalarField::from(7u64).inverse().unwrap();
+                let y = P::ScalarField::from(u64::MAX).square();
+                for (x, y) in [(x, y), (-P::ScalarField::from(1u64), -x)] {
+                    let a = composer.add_embedded_scalar(x);
+                    let b = composer.add_embedded_scalar(y);
+
+                    let product = composer.embedded_scalar_mul(a, b);
+                    let expected = from_embedded_curve_scalar::<F, P>(x * y);
+                    assert_eq!(composer.variables[&product], expected);
+                    composer.constrain_to_constant(product, expected, None);
+
+                    let sum = composer.embedded_scalar_add(a, b);
+                    let expected = from_embedded_curve_scalar::<F, P>(x + y);
+                    assert_eq!(composer.variables[&sum], expected);
+                    composer.constrain_to_constant(sum, expected, None);
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_embedded_scalar_soundness<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // A non-canonical representative of the product is rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let a = composer.add_embedded_scalar(2u64.into());
+                let b = composer.add_embedded_scalar(3u64.into());
+                let product = composer.embedded_scalar_mul(a, b);
+                let wrapped = F::from(6u64) + embedded_modulus::<F, P>();
+                composer.variables.insert(product, wrapped);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+
+        // And so is a non-canonical scalar with consistent limbs.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let wrapped = F::from(2u64) + embedded_modulus::<F, P>();
+                let a = composer.add_input(wrapped);
+                let limbs = composer.embedded_scalar_limbs(a);
+                composer.embedded_scalar_canonical_gate(&limbs);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+    }
+
+    // Test for Bls12_381
+    batch_test!(
+        [
+            test_embedded_scalar_arithmetic,
+            test_embedded_scalar_soundness
+        ],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test!(
+        [
+            test_embedded_scalar_arithmetic,
+            test_embedded_scalar_soundness
+        ],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
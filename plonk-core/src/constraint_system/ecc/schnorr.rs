@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Schnorr Signature Verification Gadget
+
+use crate::constraint_system::{
+    ecc::Point, variable::Variable, StandardComposer,
+};
+use ark_ec::{
+    twisted_edwards_extended::GroupAffine as TEGroupAffine, TEModelParameters,
+};
+use ark_ff::PrimeField;
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Adds to the circuit description the verification of a Schnorr
+    /// signature `(s, R)` over the embedded curve, i.e. the constraint:
+    ///
+    /// ```text
+    /// s * G == R + e * public_key
+    /// ```
+    ///
+    /// where `G` is the generator of the embedded curve and `e` is the
+    /// `challenge`.
+    ///
+    /// # Note
+    ///
+    /// This gadget does **NOT** hash anything. The `challenge` is expected to
+    /// be the Fiat-Shamir challenge `e = H(R || public_key || message)`
+    /// computed by the caller, either outside of the circuit or with a
+    /// dedicated hash gadget. It is the responsibility of the caller to bind
+    /// the `challenge` to the signed message.
+    pub fn schnorr_verify(
+        &mut self,
+        public_key: Point<P>,
+        challenge: Variable,
+        signature: (Variable, Point<P>),
+    ) {
+        let (s, r) = signature;
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = TEGroupAffine::new(x, y);
+
+        let lhs = self.fixed_base_scalar_mul(s, generator);
+        let e_public_key = self.variable_base_scalar_mul(challenge, public_key);
+        let rhs = self.point_addition_gate(r, e_public_key);
+
+        self.assert_equal_point(lhs, rhs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*, util,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::AffineCurve;
+
+    /// Signs with fixed secret key and nonce, returning the public key, the
+    /// challenge and the signature `(s, R)`.
+    fn sign<F, P>() -> (TEGroupAffine<P>, F, (F, TEGroupAffine<P>))
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = TEGroupAffine::<P>::new(x, y);
+
+        let secret_key = util::to_embedded_curve_scalar::<F, P>(F::from(
+            0x1234_5678_9abc_def0u64,
+        ));
+        let nonce =
+            util::to_embedded_curve_scalar::<F, P>(F::from(0xdead_beefu64));
+        let challenge = F::from(0x0bad_cafeu64);
+
+        let public_key: TEGroupAffine<P> =
+            AffineCurve::mul(&generator, secret_key).into();
+        let r: TEGroupAffine<P> = AffineCurve::mul(&generator, nonce).into();
+        let s = nonce
+            + util::to_embedded_curve_scalar::<F, P>(challenge) * secret_key;
+
+        (
+            public_key,
+            challenge,
+            (util::from_embedded_curve_scalar::<F, P>(s), r),
+        )
+    }
+
+    fn test_schnorr_verify<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let (public_key, challenge, (s, r)) = sign::<F, P>();
+
+                let public_key = composer.add_public_affine(public_key);
+                let challenge = composer.add_input(challenge);
+                let s = composer.add_input(s);
+                let r = composer.add_public_affine(r);
+
+                composer.schnorr_verify(public_key, challenge, (s, r));
+            },
+            4096,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_schnorr_verify_tampered<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let (public_key, challenge, (s, r)) = sign::<F, P>();
+
+                let public_key = composer.add_public_affine(public_key);
+                let challenge = composer.add_input(challenge);
+                // Tamper with the signature
+                let s = composer.add_input(s + F::one());
+                let r = composer.add_public_affine(r);
+
+                composer.schnorr_verify(public_key, challenge, (s, r));
+            },
+            4096,
+        );
+        assert!(res.is_err());
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [test_schnorr_verify, test_schnorr_verify_tampered],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [test_schnorr_verify, test_schnorr_verify_tampered],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
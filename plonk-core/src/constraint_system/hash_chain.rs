@@ -0,0 +1,140 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Hash Chain Gadget
+
+use crate::constraint_system::{StandardComposer, Variable};
+use ark_ec::ModelParameters;
+use ark_ff::PrimeField;
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: ModelParameters<BaseField = F>,
+{
+    /// Folds `inputs` into the accumulator `init` through the compression
+    /// gadget `compress`, and returns the final accumulator.
+    ///
+    /// Each step calls `compress(composer, accumulator, input)`, in order,
+    /// so that the result for inputs `[x_0, x_1]` is
+    /// `compress(compress(init, x_0), x_1)`. With no inputs, `init` is
+    /// returned and no gate is added.
+    ///
+    /// This function adds no gates of its own: the constraints are those
+    /// added by `compress`.
+    pub fn hash_chain<C>(
+        &mut self,
+        init: Variable,
+        inputs: &[Variable],
+        mut compress: C,
+    ) -> Variable
+    where
+        C: FnMut(&mut Self, Variable, Variable) -> Variable,
+    {
+        inputs
+            .iter()
+            .fold(init, |acc, input| compress(self, acc, *input))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+
+    /// Compresses `(acc, input)` into `2 * acc + input`.
+    fn compress<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        acc: Variable,
+        input: Variable,
+    ) -> Variable
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        composer.arithmetic_gate(|gate| {
+            gate.witness(acc, input, None).add(F::from(2u64), F::one())
+        })
+    }
+
+    fn test_hash_chain<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let init = composer.add_input(F::from(5u64));
+                let inputs = [3u64, 1, 4]
+                    .iter()
+                    .map(|x| composer.add_input(F::from(*x)))
+                    .collect::<Vec<_>>();
+
+                // ((2 * 5 + 3) * 2 + 1) * 2 + 4 = 58
+                let output = composer.hash_chain(init, &inputs, compress);
+                composer.constrain_to_constant(output, F::from(58u64), None);
+
+                let empty = composer.hash_chain(init, &[], compress);
+                assert_eq!(empty, init);
+            },
+            32,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_hash_chain_input_change<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // Changing one input changes the output of the chain.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let init = composer.add_input(F::from(5u64));
+                let inputs = [3u64, 2, 4]
+                    .iter()
+                    .map(|x| composer.add_input(F::from(*x)))
+                    .collect::<Vec<_>>();
+
+                let output = composer.hash_chain(init, &inputs, compress);
+                assert_eq!(composer.variables[&output], F::from(60u64));
+                composer.constrain_to_constant(output, F::from(58u64), None);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    // Test for Bls12_381
+    batch_test!(
+        [
+            test_hash_chain,
+            test_hash_chain_input_change
+        ],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test!(
+        [
+            test_hash_chain,
+            test_hash_chain_input_change
+        ],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
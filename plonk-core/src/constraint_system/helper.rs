@@ -8,7 +8,7 @@ use super::StandardComposer;
 use crate::{
     commitment::HomomorphicCommitment,
     error::{to_pc_error, Error},
-    proof_system::{Prover, Verifier},
+    proof_system::{Proof, Prover, Verifier},
 };
 use ark_ec::TEModelParameters;
 use rand::rngs::OsRng;
@@ -39,7 +39,7 @@ pub(crate) fn dummy_gadget<F, P>(
 pub(crate) fn gadget_tester<F, P, PC>(
     gadget: fn(&mut StandardComposer<F, P>),
     n: usize,
-) -> Result<crate::proof_system::Proof<F, PC>, Error>
+) -> Result<Proof<F, PC>, Error>
 where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
@@ -106,3 +106,84 @@ where
     verifier.verify(&proof, &vk, &public_inputs)?;
     Ok(proof)
 }
+
+/// Adds a gate whose output depends on the public input `value`.
+#[allow(dead_code)]
+pub(crate) fn pi_gadget<F, P>(composer: &mut StandardComposer<F, P>, value: F)
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    let one = composer.add_input(F::one());
+    composer.arithmetic_gate(|gate| {
+        gate.witness(one, one, None)
+            .add(F::one(), F::one())
+            .pi(value)
+    });
+}
+
+/// Generates the committer and verifier keys of `PC` for the small circuits
+/// of the tests.
+#[allow(dead_code)]
+pub(crate) fn test_keys<F, PC>() -> (PC::CommitterKey, PC::VerifierKey)
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    let universal_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+    PC::trim(&universal_params, 2 * 16, 0, None).unwrap()
+}
+
+/// Keys, [`Proof`] and preprocessed [`Verifier`] of the circuit of
+/// [`pi_gadget`], as returned by [`pi_circuit`].
+#[allow(dead_code)]
+pub(crate) struct PiCircuit<F, P, PC>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Committer key the circuit was preprocessed and proven with.
+    pub ck: PC::CommitterKey,
+
+    /// Verifier key of the commitment scheme.
+    pub vk: PC::VerifierKey,
+
+    /// Proof of the circuit for the public input `3`.
+    pub proof: Proof<F, PC>,
+
+    /// Public inputs of the proof.
+    pub public_inputs: Vec<F>,
+
+    /// Verifier which preprocessed the circuit.
+    pub verifier: Verifier<F, P, PC>,
+}
+
+/// Proves the circuit of [`pi_gadget`] for the public input `3`, with
+/// transcripts labeled `label`, and preprocesses it for verification.
+#[allow(dead_code)]
+pub(crate) fn pi_circuit<F, P, PC>(label: &'static [u8]) -> PiCircuit<F, P, PC>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    PC: HomomorphicCommitment<F>,
+{
+    let (ck, vk) = test_keys::<F, PC>();
+
+    let mut prover = Prover::<F, P, PC>::new(label);
+    pi_gadget(prover.mut_cs(), F::from(3u64));
+    let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+    let proof = prover.prove(&ck).unwrap();
+
+    let mut verifier = Verifier::<F, P, PC>::new(label);
+    pi_gadget(verifier.mut_cs(), F::zero());
+    verifier.preprocess(&ck).unwrap();
+
+    PiCircuit {
+        ck,
+        vk,
+        proof,
+        public_inputs,
+        verifier,
+    }
+}
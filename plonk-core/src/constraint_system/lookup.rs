@@ -0,0 +1,249 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Gates over fixed tables
+
+use crate::constraint_system::{StandardComposer, Variable};
+use ark_ec::TEModelParameters;
+use ark_ff::PrimeField;
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Selects the row of the fixed `(input, output)` `table` whose input is
+    /// `input`, returning a [`Variable`] constrained to the output of that
+    /// row.
+    ///
+    /// The prover supplies the row as one boolean selector per row of the
+    /// table, constrained so that exactly one of them is set and that the
+    /// input and the returned output are the ones of the selected row. The
+    /// circuit is not satisfiable if `input` is not in the table. When the
+    /// table holds the same input more than once, nothing constrains which
+    /// of its rows is selected, so that a prover may return the output of
+    /// any of them: the inputs of the table should be distinct.
+    ///
+    /// This is a selection over every row of the table, not a lookup
+    /// argument, which this crate does not implement: its cost grows
+    /// linearly with the size of the table.
+    ///
+    /// This function adds one boolean gate per row of the table, and the
+    /// gates of three [`wide_gate`]s over all of the selectors, so that a
+    /// table of `k` rows costs about `2.5 * k` gates.
+    ///
+    /// [`wide_gate`]: StandardComposer::wide_gate
+    pub fn select_from_table(
+        &mut self,
+        table: &[(F, F)],
+        input: Variable,
+    ) -> Variable {
+        let input_value = self.variables[&input];
+        let row = table.iter().position(|(x, _)| *x == input_value);
+        let output_value = row.map(|row| table[row].1).unwrap_or_default();
+
+        let selectors = (0..table.len())
+            .map(|i| {
+                let selector = self.add_input(F::from(row == Some(i)));
+                self.boolean_gate(selector)
+            })
+            .collect::<Vec<_>>();
+        let output = self.add_input(output_value);
+
+        // Exactly one row is selected.
+        self.wide_gate(|gate| {
+            for selector in &selectors {
+                gate.wire(F::one(), *selector);
+            }
+            gate.constant(-F::one())
+        });
+
+        // The input and the output are the ones of the selected row.
+        for (column, var) in [(0, input), (1, output)] {
+            self.wide_gate(|gate| {
+                for (entry, selector) in table.iter().zip(&selectors) {
+                    let value = if column == 0 { entry.0 } else { entry.1 };
+                    gate.wire(value, *selector);
+                }
+                gate.wire(-F::one(), var)
+            });
+        }
+
+        output
+    }
+
+    /// Asserts that `value` is none of the entries of the fixed `table`.
+    ///
+    /// The product of the differences between `value` and each entry of the
+    /// table is accumulated gate by gate and then constrained to be nonzero
+    /// with [`assert_nonzero`], the product being zero exactly when one of
+    /// the differences is. The circuit is not satisfiable if `value` is in
+    /// the table, while an empty table adds no gates.
+    ///
+    /// This is a product of differences check, not the complement of a
+    /// lookup argument, which this crate does not implement: its cost grows
+    /// linearly with the size of the table.
+    ///
+    /// This function adds one arithmetic gate per entry of the table and the
+    /// gate of [`assert_nonzero`], so that a table of `k` entries costs
+    /// `k + 1` gates.
+    ///
+    /// [`assert_nonzero`]: StandardComposer::assert_nonzero
+    pub fn assert_not_in_table(&mut self, table: &[F], value: Variable) {
+        let (first, rest) = match table.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        let zero = self.zero_var();
+        let first_difference = self.arithmetic_gate(|gate| {
+            gate.witness(value, zero, None)
+                .add(F::one(), F::zero())
+                .constant(-*first)
+        });
+        // product * (value - entry) = product * value - entry * product
+        let product = rest.iter().fold(first_difference, |product, entry| {
+            self.arithmetic_gate(|gate| {
+                gate.witness(product, value, None)
+                    .mul(F::one())
+                    .add(-*entry, F::zero())
+            })
+        });
+        self.assert_nonzero(product);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    /// 4-bit S-box of the PRESENT block cipher.
+    const SBOX: [u64; 16] = [
+        0xC, 0x5, 0x6, 0xB, 0x9, 0x0, 0xA, 0xD, 0x3, 0xE, 0xF, 0x8, 0x4, 0x7,
+        0x1, 0x2,
+    ];
+
+    fn sbox_table<F>() -> Vec<(F, F)>
+    where
+        F: PrimeField,
+    {
+        SBOX.iter()
+            .enumerate()
+            .map(|(x, y)| (F::from(x as u64), F::from(*y)))
+            .collect()
+    }
+
+    fn test_select_from_table<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let table = sbox_table::<F>();
+                for (x, y) in [(0u64, 0xCu64), (3, 0xB), (15, 0x2)] {
+                    let input = composer.add_input(F::from(x));
+                    let output = composer.select_from_table(&table, input);
+                    composer.constrain_to_constant(output, F::from(y), None);
+                }
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_select_from_table_soundness<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // A prover claiming an output which is not the one of the row is
+        // rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let table = sbox_table::<F>();
+                let input = composer.add_input(F::from(3u64));
+                let output = composer.select_from_table(&table, input);
+                composer.variables.insert(output, F::from(0xCu64));
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // And so is an input which is not in the table.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let table = sbox_table::<F>();
+                let input = composer.add_input(F::from(16u64));
+                composer.select_from_table(&table, input);
+            },
+            200,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_assert_not_in_table<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let table = SBOX.map(F::from);
+                for x in [16u64, 17, 1 << 40] {
+                    let value = composer.add_input(F::from(x));
+                    composer.assert_not_in_table(&table, value);
+                    composer.assert_not_in_table(&[], value);
+                }
+            },
+            200,
+        );
+        assert!(res.is_ok());
+
+        // A value in the table is rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let table = SBOX.map(F::from);
+                let value = composer.add_input(F::from(0xBu64));
+                composer.assert_not_in_table(&table, value);
+            },
+            200,
+        );
+        assert!(res.is_err());
+    }
+
+    // Test for Bls12_381
+    batch_test!(
+        [
+            test_select_from_table,
+            test_select_from_table_soundness,
+            test_assert_not_in_table
+        ],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test!(
+        [
+            test_select_from_table,
+            test_select_from_table_soundness,
+            test_assert_not_in_table
+        ],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -10,16 +10,21 @@
 
 mod arithmetic;
 mod boolean;
+mod division;
+mod hash_chain;
 mod logic;
+mod lookup;
+mod r1cs;
 mod range;
+mod shift;
 
 pub(crate) mod composer;
+#[cfg(test)]
 pub(crate) mod helper;
 pub(crate) mod variable;
 
 pub mod ecc;
 
-pub(crate) use variable::WireData;
-
-pub use composer::StandardComposer;
-pub use variable::Variable;
+pub use composer::{FinalizedCircuit, StandardComposer, NUM_BLINDING_FACTORS};
+pub use r1cs::{R1CSRow, R1CS};
+pub use variable::{Variable, WireData};
@@ -0,0 +1,270 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Export of a circuit to a Rank-1 Constraint System.
+
+use crate::{
+    constraint_system::{StandardComposer, Variable},
+    error::Error,
+};
+use ark_ec::ModelParameters;
+use ark_ff::{Field, PrimeField};
+
+/// Sparse row of an [`R1CS`] matrix, as `(coefficient, index)` pairs over
+/// the assignment vector. An index can appear more than once, in which case
+/// its coefficients add up.
+pub type R1CSRow<F> = Vec<(F, usize)>;
+
+/// Rank-1 Constraint System `(A z) * (B z) = C z`, exported from a
+/// [`StandardComposer`] by [`StandardComposer::to_r1cs`].
+///
+/// The assignment vector `z` is laid out as:
+/// - the constant `1` at index `0`,
+/// - the public inputs, in the order of their positions in the circuit,
+/// - the [`Variable`]s of the composer, the [`Variable`] `v` sitting at index
+///   `num_instance_variables + v`. [`Variable`]s merged away by the composer
+///   are left unconstrained and assigned zero.
+///
+/// The copy constraints of the circuit need no translation, as every wire
+/// reading the same [`Variable`] reads the same entry of `z`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct R1CS<F>
+where
+    F: Field,
+{
+    /// Number of instance variables, the constant `1` and the public inputs.
+    pub num_instance_variables: usize,
+
+    /// Number of witness variables.
+    pub num_witness_variables: usize,
+
+    /// Rows of the `A` matrix, one per constraint.
+    pub a: Vec<R1CSRow<F>>,
+
+    /// Rows of the `B` matrix, one per constraint.
+    pub b: Vec<R1CSRow<F>>,
+
+    /// Rows of the `C` matrix, one per constraint.
+    pub c: Vec<R1CSRow<F>>,
+
+    /// Assignment vector `z` of the witness held by the composer.
+    pub assignment: Vec<F>,
+}
+
+impl<F> R1CS<F>
+where
+    F: Field,
+{
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Returns the index of the first constraint not satisfied by
+    /// `assignment`, if any.
+    pub fn which_is_unsatisfied(&self, assignment: &[F]) -> Option<usize> {
+        let evaluate = |row: &R1CSRow<F>| -> F {
+            row.iter()
+                .map(|(coeff, index)| *coeff * assignment[*index])
+                .sum()
+        };
+        (0..self.num_constraints()).find(|&i| {
+            evaluate(&self.a[i]) * evaluate(&self.b[i]) != evaluate(&self.c[i])
+        })
+    }
+
+    /// Checks that [`R1CS::assignment`] satisfies every constraint.
+    pub fn is_satisfied(&self) -> bool {
+        self.which_is_unsatisfied(&self.assignment).is_none()
+    }
+}
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: ModelParameters<BaseField = F>,
+{
+    /// Exports the constraints of the circuit to an [`R1CS`], together with
+    /// the assignment of the witness held by the composer.
+    ///
+    /// Every arithmetic gate
+    /// `q_arith * (q_m * a * b + q_l * a + q_r * b + q_o * c + q_4 * d + q_c)
+    /// + PI = 0` is translated to the single constraint
+    /// `(q_arith * q_m * a) * b = -(q_arith * (q_l * a + ...) + PI)`.
+    ///
+    /// Range, logic and elliptic curve gates have no such translation, and
+    /// make this function return [`Error::UnsupportedR1CSGate`].
+    pub fn to_r1cs(&self) -> Result<R1CS<F>, Error> {
+        if let Some(&position) = self.conflicting_public_inputs.iter().next() {
+            return Err(Error::ConflictingPublicInput { position });
+        }
+
+        let num_instance_variables = 1 + self.public_inputs_sparse_store.len();
+        let num_witness_variables = self
+            .variables
+            .keys()
+            .map(|var| var.0 + 1)
+            .max()
+            .unwrap_or_default();
+        let witness_index =
+            |var: Variable| -> usize { num_instance_variables + var.0 };
+
+        let mut assignment =
+            vec![F::zero(); num_instance_variables + num_witness_variables];
+        assignment[0] = F::one();
+        for (i, value) in self.public_inputs_sparse_store.values().enumerate() {
+            assignment[1 + i] = *value;
+        }
+        for (var, value) in self.variables.iter() {
+            assignment[witness_index(*var)] = *value;
+        }
+
+        let mut r1cs = R1CS {
+            num_instance_variables,
+            num_witness_variables,
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+            assignment,
+        };
+        let mut public_inputs = self.public_inputs_sparse_store.keys();
+        let mut next_public_input = public_inputs.next();
+        let mut num_public_inputs = 0;
+        for gate_index in 0..self.n {
+            if !self.q_range[gate_index].is_zero()
+                || !self.q_logic[gate_index].is_zero()
+                || !self.q_fixed_group_add[gate_index].is_zero()
+                || !self.q_variable_group_add[gate_index].is_zero()
+            {
+                return Err(Error::UnsupportedR1CSGate { gate_index });
+            }
+
+            let q_arith = self.q_arith[gate_index];
+            let mut c = [
+                (self.q_l[gate_index], witness_index(self.w_l[gate_index])),
+                (self.q_r[gate_index], witness_index(self.w_r[gate_index])),
+                (self.q_o[gate_index], witness_index(self.w_o[gate_index])),
+                (self.q_4[gate_index], witness_index(self.w_4[gate_index])),
+                (self.q_c[gate_index], 0),
+            ]
+            .into_iter()
+            .map(|(selector, index)| (-q_arith * selector, index))
+            .filter(|(coeff, _)| !coeff.is_zero())
+            .collect::<Vec<_>>();
+            if next_public_input == Some(&gate_index) {
+                num_public_inputs += 1;
+                c.push((-F::one(), num_public_inputs));
+                next_public_input = public_inputs.next();
+            }
+
+            let q_m = q_arith * self.q_m[gate_index];
+            if q_m.is_zero() && c.is_empty() {
+                continue;
+            }
+            let a = if q_m.is_zero() {
+                Vec::new()
+            } else {
+                vec![(q_m, witness_index(self.w_l[gate_index]))]
+            };
+            r1cs.a.push(a);
+            r1cs.b
+                .push(vec![(F::one(), witness_index(self.w_r[gate_index]))]);
+            r1cs.c.push(c);
+        }
+        Ok(r1cs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_field_params;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+
+    fn test_r1cs_export<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let a = composer.add_input(F::from(3u64));
+        let b = composer.add_input(F::from(5u64));
+        // a * b + a = 18
+        let prod = composer.arithmetic_gate(|gate| {
+            gate.witness(a, b, None)
+                .mul(F::one())
+                .add(F::one(), F::zero())
+        });
+        composer.constrain_to_constant(prod, F::from(18u64), None);
+        // a + b - 10 + PI = 0
+        let zero = composer.zero_var();
+        composer.arithmetic_gate(|gate| {
+            gate.witness(a, b, Some(zero))
+                .add(F::one(), F::one())
+                .constant(-F::from(10u64))
+                .pi(F::from(2u64))
+        });
+
+        let r1cs = composer.to_r1cs().unwrap();
+        assert_eq!(r1cs.num_instance_variables, 2);
+        assert_eq!(r1cs.assignment[0], F::one());
+        assert_eq!(r1cs.assignment[1], F::from(2u64));
+        assert!(r1cs.num_constraints() >= 3);
+        assert!(r1cs.is_satisfied());
+
+        // A wrong witness or public input does not satisfy the matrices.
+        let mut assignment = r1cs.assignment.clone();
+        assignment[r1cs.num_instance_variables + a.0] = F::from(4u64);
+        assert!(r1cs.which_is_unsatisfied(&assignment).is_some());
+        let mut assignment = r1cs.assignment.clone();
+        assignment[1] = F::from(3u64);
+        assert!(r1cs.which_is_unsatisfied(&assignment).is_some());
+    }
+
+    fn test_r1cs_unsupported_gate<F, P>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let mut composer = StandardComposer::<F, P>::new();
+        let a = composer.add_input(F::from(3u64));
+        let gate_index = composer.circuit_size();
+        composer.range_gate(a, 8);
+        assert!(matches!(
+            composer.to_r1cs(),
+            Err(Error::UnsupportedR1CSGate { gate_index: index })
+                if index == gate_index
+        ));
+    }
+
+    // Test for Bls12_381
+    batch_test_field_params!(
+        [
+            test_r1cs_export,
+            test_r1cs_unsupported_gate
+        ],
+        []
+        => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test_field_params!(
+        [
+            test_r1cs_export,
+            test_r1cs_unsupported_gate
+        ],
+        []
+        => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
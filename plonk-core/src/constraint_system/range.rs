@@ -192,6 +192,97 @@ where
         self.assert_equal(accumulators[last_accumulator], witness);
         accumulators[last_accumulator] = witness;
     }
+
+    /// Adds a range-constraint that checks and constrains a [`Variable`] to
+    /// be inside of the range \[0, base^num_digits\), by decomposing it into
+    /// `num_digits` digits in the given `base`.
+    ///
+    /// For `base == 4` this is exactly [`StandardComposer::range_gate`] over
+    /// `2 * num_digits` bits, using the dedicated range gate. Any other base
+    /// is enforced with arithmetic gates: every digit `d` is constrained by
+    /// `d(d-1)...(d-(base-1)) = 0`, which costs `base - 1` gates per digit,
+    /// plus one gate per digit to accumulate them.
+    ///
+    /// # Panics
+    /// This function will panic if `base < 2`.
+    pub fn range_gate_base(
+        &mut self,
+        witness: Variable,
+        num_digits: usize,
+        base: u64,
+    ) {
+        assert!(base >= 2, "the decomposition base must be at least 2");
+
+        if base == 4 {
+            self.range_gate(witness, 2 * num_digits);
+            return;
+        }
+
+        // Decompose the witness, most significant digit first. If the witness
+        // does not fit in `num_digits` digits, the accumulated value will not
+        // match it and the final equality constraint will fail.
+        let mut limbs = self.variables[&witness].into_repr();
+        let mut digits = (0..num_digits)
+            .map(|_| divide_limbs(limbs.as_mut(), base))
+            .collect::<Vec<_>>();
+        digits.reverse();
+
+        let digits = digits
+            .into_iter()
+            .map(|digit| self.add_input(F::from(digit)))
+            .collect::<Vec<_>>();
+        let accumulator = self.base_digits_gate(&digits, base);
+        self.assert_equal(accumulator, witness);
+    }
+
+    /// Constrains every variable in `digits` to be in \[0, base\) and returns
+    /// the value they represent in `base`, the most significant digit coming
+    /// first.
+    pub(crate) fn base_digits_gate(
+        &mut self,
+        digits: &[Variable],
+        base: u64,
+    ) -> Variable {
+        let zero = self.zero_var;
+        let base_scalar = F::from(base);
+        let mut accumulator = zero;
+        for &digit in digits {
+            // product = d(d-1)...(d-k) for k = 1, ..., base - 2
+            let mut product = digit;
+            for k in 1..(base - 1) {
+                product = self.arithmetic_gate(|gate| {
+                    gate.witness(product, digit, None)
+                        .mul(F::one())
+                        .add(-F::from(k), F::zero())
+                });
+            }
+            // product * (d - (base - 1)) = 0
+            self.arithmetic_gate(|gate| {
+                gate.witness(product, digit, Some(zero))
+                    .mul(F::one())
+                    .add(-F::from(base - 1), F::zero())
+            });
+
+            accumulator = self.arithmetic_gate(|gate| {
+                gate.witness(accumulator, digit, None)
+                    .add(base_scalar, F::one())
+            });
+        }
+        accumulator
+    }
+}
+
+/// Divides the little-endian `limbs` in place by `divisor`, returning the
+/// remainder.
+fn divide_limbs(limbs: &mut [u64], divisor: u64) -> u64 {
+    let divisor = divisor as u128;
+    let mut remainder = 0u128;
+    for limb in limbs.iter_mut().rev() {
+        let current = (remainder << 64) | (*limb as u128);
+        *limb = (current / divisor) as u64;
+        remainder = current % divisor;
+    }
+    remainder as u64
 }
 
 #[cfg(test)]
@@ -243,6 +334,134 @@ mod test {
         assert!(res.is_ok());
     }
 
+    fn test_range_gate_base<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // Base 4 behaves like the range gate over twice as many bits
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness =
+                    composer.add_input(F::from(u32::max_value() as u64));
+                composer.range_gate_base(witness, 16, 4);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness =
+                    composer.add_input(F::from((u32::max_value() as u64) + 1));
+                composer.range_gate_base(witness, 16, 4);
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // Should pass as 99999 has 5 decimal digits
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness = composer.add_input(F::from(99_999u64));
+                composer.range_gate_base(witness, 5, 10);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+
+        // Should fail as 100000 has 6 decimal digits
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness = composer.add_input(F::from(100_000u64));
+                composer.range_gate_base(witness, 5, 10);
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // Should pass in base 3
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness = composer.add_input(F::from(3u64.pow(7) - 1));
+                composer.range_gate_base(witness, 7, 3);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_range_gate_base_last_row<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // The gates of `range_gate_base` end the circuit, so the last of
+        // them is checked against the first row, which follows it.
+        for base in [4, 10] {
+            let gadget = |composer: &mut StandardComposer<F, P>, value: u64| {
+                let witness = composer.add_input(F::from(value));
+                composer.range_gate_base(witness, 4, base);
+            };
+
+            let mut composer = StandardComposer::<F, P>::new();
+            gadget(&mut composer, base.pow(4) - 1);
+            let last_row = composer.circuit_size() - 1;
+            assert_eq!(composer.next_row(last_row), 0);
+            assert_eq!(composer.first_unsatisfied_gate(), None);
+
+            let mut composer = StandardComposer::<F, P>::new();
+            gadget(&mut composer, base.pow(4));
+            assert!(composer.first_unsatisfied_gate().is_some());
+        }
+
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let witness = composer.add_input(F::from(9_999u64));
+                composer.range_gate_base(witness, 4, 10);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_base_digit_out_of_range<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // 25 = 2 * 10 + 5 with valid decimal digits
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let digits = [2u64, 5]
+                    .iter()
+                    .map(|d| composer.add_input(F::from(*d)))
+                    .collect::<Vec<_>>();
+                let value = composer.base_digits_gate(&digits, 10);
+                composer.constrain_to_constant(value, F::from(25u64), None);
+            },
+            200,
+        );
+        assert!(res.is_ok());
+
+        // 25 = 1 * 10 + 15 accumulates correctly but 15 is not a digit
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let digits = [1u64, 15]
+                    .iter()
+                    .map(|d| composer.add_input(F::from(*d)))
+                    .collect::<Vec<_>>();
+                let value = composer.base_digits_gate(&digits, 10);
+                composer.constrain_to_constant(value, F::from(25u64), None);
+            },
+            200,
+        );
+        assert!(res.is_err());
+    }
+
     fn test_odd_bit_range<F, P, PC>()
     where
         F: PrimeField,
@@ -262,7 +481,12 @@ mod test {
 
     // Test on Bls12-381
     batch_test!(
-        [test_range_constraint],
+        [
+            test_range_constraint,
+            test_range_gate_base,
+            test_range_gate_base_last_row,
+            test_base_digit_out_of_range
+        ],
         [test_odd_bit_range]
         => (
             Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
@@ -271,7 +495,12 @@ mod test {
 
     // Test on Bls12-377
     batch_test!(
-        [test_range_constraint],
+        [
+            test_range_constraint,
+            test_range_gate_base,
+            test_range_gate_base_last_row,
+            test_base_digit_out_of_range
+        ],
         [test_odd_bit_range]
         => (
             Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
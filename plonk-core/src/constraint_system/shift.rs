@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Bit Shift Gates
+
+use crate::constraint_system::{StandardComposer, Variable};
+use ark_ec::TEModelParameters;
+use ark_ff::{BigInteger, PrimeField};
+
+impl<F, P> StandardComposer<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Shifts `x`, read as an unsigned integer of `width` bits, left by
+    /// `bits` bits, and returns a [`Variable`] constrained to the result.
+    ///
+    /// The bits shifted past `width` are dropped, so that the result is
+    /// `(x << bits) mod 2^width`, and is itself constrained to `width` bits.
+    /// The circuit is not satisfiable if `x` does not fit in `width` bits.
+    ///
+    /// This function adds one boolean gate per bit of `x`, and the gates of
+    /// two [`wide_gate`]s over those bits.
+    ///
+    /// # Panics
+    /// This function will panic if `width` bits integers might wrap around
+    /// the field modulus, ie. `width >= F::size_in_bits()`.
+    ///
+    /// [`wide_gate`]: StandardComposer::wide_gate
+    pub fn shl(&mut self, x: Variable, bits: usize, width: usize) -> Variable {
+        let x_bits = self.decompose_bits(x, width);
+        let kept = width.saturating_sub(bits);
+        self.compose_bits(&x_bits[..kept], bits)
+    }
+
+    /// Shifts `x`, read as an unsigned integer of `width` bits, right by
+    /// `bits` bits, and returns a [`Variable`] constrained to the result.
+    ///
+    /// The circuit is not satisfiable if `x` does not fit in `width` bits.
+    ///
+    /// This function adds one boolean gate per bit of `x`, and the gates of
+    /// two [`wide_gate`]s over those bits.
+    ///
+    /// # Panics
+    /// This function will panic if `width` bits integers might wrap around
+    /// the field modulus, ie. `width >= F::size_in_bits()`.
+    ///
+    /// [`wide_gate`]: StandardComposer::wide_gate
+    pub fn shr(&mut self, x: Variable, bits: usize, width: usize) -> Variable {
+        let x_bits = self.decompose_bits(x, width);
+        let dropped = bits.min(width);
+        self.compose_bits(&x_bits[dropped..], 0)
+    }
+
+    /// Decomposes `x` into `width` boolean [`Variable`]s, least significant
+    /// first, constrained to add up to `x`.
+    fn decompose_bits(&mut self, x: Variable, width: usize) -> Vec<Variable> {
+        assert!(
+            width < F::size_in_bits(),
+            "{} bit integers might wrap around the modulus",
+            width
+        );
+        let x_bits = self.variables[&x].into_repr().to_bits_le();
+        let bits = (0..width)
+            .map(|i| {
+                let bit = self.add_input(F::from(x_bits[i]));
+                self.boolean_gate(bit)
+            })
+            .collect::<Vec<_>>();
+        self.bits_gate(&bits, 0, x);
+        bits
+    }
+
+    /// Returns a [`Variable`] constrained to `sum(bits[i] * 2^(offset + i))`.
+    fn compose_bits(&mut self, bits: &[Variable], offset: usize) -> Variable {
+        let two = F::from(2u64);
+        let value = bits
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| {
+                self.variables[bit] * two.pow([(offset + i) as u64])
+            })
+            .sum();
+        let output = self.add_input(value);
+        self.bits_gate(bits, offset, output);
+        output
+    }
+
+    /// Constrains `sum(bits[i] * 2^(offset + i)) - target = 0`.
+    fn bits_gate(
+        &mut self,
+        bits: &[Variable],
+        offset: usize,
+        target: Variable,
+    ) {
+        let two = F::from(2u64);
+        self.wide_gate(|gate| {
+            for (i, bit) in bits.iter().enumerate() {
+                gate.wire(two.pow([(offset + i) as u64]), *bit);
+            }
+            gate.wire(-F::one(), target)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment,
+        constraint_system::helper::*,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    fn test_shift<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                for (x, bits, shl, shr) in [
+                    (0b1011u64, 2, 0b101100u64, 0b10u64),
+                    (0xF0, 4, 0x00, 0x0F),
+                    (0xFF, 1, 0xFE, 0x7F),
+                    (0xA5, 0, 0xA5, 0xA5),
+                    (0xA5, 8, 0x00, 0x00),
+                    (0xA5, 9, 0x00, 0x00),
+                ] {
+                    let x = composer.add_input(F::from(x));
+                    let left = composer.shl(x, bits, 8);
+                    composer.constrain_to_constant(left, F::from(shl), None);
+                    let right = composer.shr(x, bits, 8);
+                    composer.constrain_to_constant(right, F::from(shr), None);
+                }
+            },
+            400,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_shift_soundness<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // An input wider than `width` is rejected.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::from(0x100u64));
+                composer.shr(x, 1, 8);
+            },
+            200,
+        );
+        assert!(res.is_err());
+
+        // And so is a prover claiming the overflow was not truncated.
+        let res = gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let x = composer.add_input(F::from(0xF0u64));
+                let output = composer.shl(x, 4, 8);
+                composer.variables.insert(output, F::from(0xF00u64));
+            },
+            200,
+        );
+        assert!(res.is_err());
+    }
+
+    // Test for Bls12_381
+    batch_test!(
+        [
+            test_shift,
+            test_shift_soundness
+        ],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Test for Bls12_377
+    batch_test!(
+        [
+            test_shift,
+            test_shift_soundness
+        ],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -28,3 +28,25 @@ pub enum WireData {
     /// Fourth Wire of n'th gate
     Fourth(usize),
 }
+
+impl WireData {
+    /// Returns the index of the gate this wire belongs to.
+    pub(crate) fn row(&self) -> usize {
+        match *self {
+            Self::Left(row)
+            | Self::Right(row)
+            | Self::Output(row)
+            | Self::Fourth(row) => row,
+        }
+    }
+
+    /// Returns the same wire of the gate at `row`.
+    pub(crate) fn with_row(self, row: usize) -> Self {
+        match self {
+            Self::Left(_) => Self::Left(row),
+            Self::Right(_) => Self::Right(row),
+            Self::Output(_) => Self::Output(row),
+            Self::Fourth(_) => Self::Fourth(row),
+        }
+    }
+}
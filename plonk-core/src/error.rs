@@ -6,6 +6,11 @@
 
 //! A collection of all possible errors encountered in PLONK.
 
+use crate::{
+    constraint_system::Variable,
+    proof_system::{dynamic::CommitmentScheme, GateType},
+};
+
 /// Defines all possible errors that can be encountered in PLONK.
 #[derive(Debug)]
 pub enum Error {
@@ -18,10 +23,22 @@ pub enum Error {
         /// Two adicity generated
         adicity: u32,
     },
+    /// This error occurs when the evaluation domain of a circuit is not a
+    /// radix-2 domain.
+    UnsupportedDomainType,
+    /// This error occurs when the domain size of a circuit key is not a power
+    /// of two.
+    NonPowerOfTwoDomain {
+        /// Domain size of the key
+        n: usize,
+    },
 
     // Prover/Verifier errors
     /// This error occurs when a proof verification fails.
     ProofVerificationError,
+    /// This error occurs when a proof fails the verification run by the
+    /// prover before returning it.
+    SelfCheckFailed,
     /// This error occurs when the circuit is not provided with all of the
     /// required inputs.
     CircuitInputsNotFound,
@@ -30,14 +47,161 @@ pub enum Error {
     UninitializedPIGenerator,
     /// PublicInput serialization error
     InvalidPublicInputBytes,
+    /// This error occurs when the same public input position is assigned two
+    /// different values.
+    ConflictingPublicInput {
+        /// Position of the conflicting public input
+        position: usize,
+    },
+    /// This error occurs when more public inputs are provided to the verifier
+    /// than either the evaluation domain or the configured limit allow.
+    TooManyPublicInputs {
+        /// Number of public inputs provided
+        count: usize,
+        /// Maximum number of public inputs allowed
+        limit: usize,
+    },
+    /// This error occurs when none of the precomputed evaluations of the
+    /// public input polynomial given to the verifier is at the evaluation
+    /// challenge of the proof.
+    MissingPublicInputEvaluation,
+    /// This error occurs when the number of values or limbs given to a
+    /// [`PublicInputLayout`](crate::circuit::PublicInputLayout) differs from
+    /// the number it describes.
+    PublicInputLayoutMismatch {
+        /// Number of values or limbs described by the layout
+        expected: usize,
+        /// Number of values or limbs provided
+        found: usize,
+    },
+    /// This error occurs when a value or limb does not fit in the number of
+    /// bits assigned to it by a
+    /// [`PublicInputLayout`](crate::circuit::PublicInputLayout).
+    PublicInputValueTooLarge {
+        /// Index of the value or limb
+        index: usize,
+    },
+    /// This error occurs when a proof was created with a version of the
+    /// proving algorithm this crate cannot verify.
+    UnsupportedProofVersion {
+        /// Version of the proof
+        version: u8,
+    },
+    /// This error occurs when a proof is tagged with an unknown commitment
+    /// scheme.
+    UnsupportedCommitmentScheme {
+        /// Tag of the proof
+        tag: u8,
+    },
+    /// This error occurs when a proof is tagged with a commitment scheme the
+    /// verifier was not set up for.
+    MissingCommitmentScheme {
+        /// Commitment scheme of the proof
+        scheme: CommitmentScheme,
+    },
+    /// This error occurs when the number of public outputs provided to the
+    /// verifier differs from the number of public outputs of the circuit.
+    PublicOutputCountMismatch {
+        /// Number of public outputs of the circuit
+        expected: usize,
+        /// Number of public outputs provided
+        found: usize,
+    },
+    /// This error occurs when two proofs linked by a shared witness expose
+    /// different commitments to it.
+    WitnessLinkMismatch,
     /// This error occurs when the Prover structure already contains a
     /// preprocessed circuit inside, but you call preprocess again.
     CircuitAlreadyPreprocessed,
+    /// This error occurs when the Prover structure does not contain a
+    /// preprocessed circuit yet, but it is needed.
+    CircuitNotPreprocessed,
+    /// This error occurs when proving a circuit whose blinding rows were
+    /// never given blinding factors, which happens without the `prover`
+    /// feature until they are set with
+    /// [`StandardComposer::set_blinding_factors`].
+    ///
+    /// [`StandardComposer::set_blinding_factors`]:
+    /// crate::constraint_system::StandardComposer::set_blinding_factors
+    MissingBlindingFactors,
+    /// This error occurs when a batch of proofs is not given as many sets of
+    /// public inputs and auxiliary data as it has proofs.
+    BatchLengthMismatch {
+        /// Number of proofs in the batch.
+        proofs: usize,
+        /// Number of sets of public inputs given.
+        public_inputs: usize,
+        /// Number of auxiliary data given.
+        aux_data: usize,
+    },
+    /// This error occurs when the evaluation challenge `z` lands on an element
+    /// of the evaluation domain, making the vanishing polynomial evaluate to
+    /// zero at it.
+    ChallengeInDomain,
+    /// This error occurs when a proof does not carry one of the evaluations
+    /// needed to verify it.
+    MissingEvaluation {
+        /// Label of the missing evaluation
+        label: &'static str,
+    },
+    /// This error occurs when the evaluations of a proof do not match the
+    /// evaluations schema of the circuit they are serialized with.
+    EvaluationsSchemaMismatch,
+    /// This error occurs when a proof does not carry as many commitments to
+    /// the pieces of the quotient polynomial as the verifier key expects.
+    QuotientPieceCountMismatch {
+        /// Number of quotient commitments expected by the verifier key
+        expected: usize,
+        /// Number of quotient commitments carried by the proof
+        found: usize,
+    },
+    /// This error occurs when a proof being deserialized exceeds the number
+    /// of bytes or of custom evaluations it is allowed to hold.
+    ProofTooLarge,
+    /// This error occurs when a verifier key does not commit to the selector
+    /// of a gate type used by the circuit.
+    MissingSelectorCommitment {
+        /// Label of the missing selector commitment
+        selector: &'static str,
+    },
+    /// This error occurs when a verifier key commits to the selector of a
+    /// gate type the verifier does not allow.
+    DisallowedGateType {
+        /// Type of the disallowed gate
+        gate_type: GateType,
+    },
 
     // Preprocessing errors
     /// This error occurs when an error triggers during the preprocessing
     /// stage.
     MismatchedPolyLen,
+    /// This error occurs when a circuit has no gates besides the ones added
+    /// to every circuit.
+    EmptyCircuit,
+    /// This error occurs when a public input is placed outside of the rows of
+    /// the circuit.
+    PublicInputOutOfRange {
+        /// Position of the public input
+        position: usize,
+        /// Number of gates of the circuit
+        circuit_size: usize,
+    },
+    /// This error occurs when a variable is added to a circuit but not used
+    /// by any of its gates.
+    DanglingVariable {
+        /// Unused variable
+        variable: Variable,
+    },
+    /// This error occurs when the gates of a circuit are reordered with an
+    /// ordering which is not a permutation of its rows, or which separates a
+    /// gate from the next row it reads from.
+    InvalidRowOrder,
+    /// This error occurs when a circuit is exported to an R1CS but one of its
+    /// gates has no R1CS translation.
+    UnsupportedR1CSGate {
+        /// Index of the gate
+        gate_index: usize,
+    },
 
     /// Polynomial Commitment errors
     PCError {
@@ -59,6 +223,14 @@ pub enum Error {
     /// This error occurs when the user tries to commit to a polynomial whose
     /// degree is larger than the supported degree for that proving key.
     PolynomialDegreeTooLarge,
+    /// This error occurs when the commit key given to the prover does not
+    /// support the degree of the polynomials of the circuit.
+    InsufficientSRS {
+        /// Degree of the largest polynomial committed to by the prover
+        required: usize,
+        /// Maximum degree supported by the commit key
+        available: usize,
+    },
     /// This error occurs when the user tries to commit to a polynomial whose
     /// degree is zero.
     PolynomialDegreeIsZero,
@@ -74,6 +246,11 @@ pub enum Error {
     /// This error occurs when a malformed scalar is decoded from a byte
     /// array.
     ScalarMalformed,
+    /// Canonical (de)serialization errors
+    SerializationError {
+        /// Canonical (de)serialization errors
+        error: String,
+    },
 }
 
 impl From<ark_poly_commit::error::Error> for Error {
@@ -84,6 +261,21 @@ impl From<ark_poly_commit::error::Error> for Error {
     }
 }
 
+impl From<ark_serialize::SerializationError> for Error {
+    fn from(error: ark_serialize::SerializationError) -> Self {
+        match error {
+            ark_serialize::SerializationError::IoError(error)
+                if error.kind() == ark_std::io::ErrorKind::UnexpectedEof =>
+            {
+                Self::NotEnoughBytes
+            }
+            error => Self::SerializationError {
+                error: format!("Serialization Error: {:?}", error),
+            },
+        }
+    }
+}
+
 /// Convert an ark_poly_commit error
 pub fn to_pc_error<F, PC>(error: PC::Error) -> Error
 where
@@ -110,9 +302,18 @@ impl std::fmt::Display for Error {
             Size: {:?} > TWO_ADICITY = {:?}",
                 log_size_of_group, adicity
             ),
+            Self::UnsupportedDomainType => {
+                write!(f, "only radix-2 evaluation domains are supported")
+            }
+            Self::NonPowerOfTwoDomain { n } => {
+                write!(f, "domain size {} of the key is not a power of two", n)
+            }
             Self::ProofVerificationError => {
                 write!(f, "proof verification failed")
             }
+            Self::SelfCheckFailed => {
+                write!(f, "proof failed the self-check of the prover")
+            }
             Self::CircuitInputsNotFound => {
                 write!(f, "circuit inputs not found")
             }
@@ -122,15 +323,123 @@ impl std::fmt::Display for Error {
             Self::InvalidPublicInputBytes => {
                 write!(f, "invalid public input bytes")
             }
+            Self::ConflictingPublicInput { position } => write!(
+                f,
+                "conflicting values assigned to public input at position {}",
+                position
+            ),
+            Self::TooManyPublicInputs { count, limit } => write!(
+                f,
+                "too many public inputs: {} provided but at most {} allowed",
+                count, limit
+            ),
+            Self::MissingPublicInputEvaluation => write!(
+                f,
+                "no public input evaluation at the evaluation challenge"
+            ),
+            Self::PublicInputLayoutMismatch { expected, found } => write!(
+                f,
+                "public input layout expects {} values or limbs but {} were \
+                 provided",
+                expected, found
+            ),
+            Self::PublicInputValueTooLarge { index } => write!(
+                f,
+                "public input value or limb {} does not fit in its layout",
+                index
+            ),
+            Self::UnsupportedProofVersion { version } => {
+                write!(f, "unsupported proof version {}", version)
+            }
+            Self::UnsupportedCommitmentScheme { tag } => {
+                write!(f, "unsupported commitment scheme tag {}", tag)
+            }
+            Self::MissingCommitmentScheme { scheme } => write!(
+                f,
+                "no verifier set up for proofs over {:?} commitments",
+                scheme
+            ),
+            Self::PublicOutputCountMismatch { expected, found } => write!(
+                f,
+                "expected {} public outputs but {} were provided",
+                expected, found
+            ),
+            Self::WitnessLinkMismatch => {
+                write!(f, "the linked proofs commit to different witnesses")
+            }
             Self::MismatchedPolyLen => {
                 write!(f, "the length of the wires is not the same")
             }
+            Self::EmptyCircuit => {
+                write!(f, "the circuit has no gates")
+            }
+            Self::PublicInputOutOfRange {
+                position,
+                circuit_size,
+            } => write!(
+                f,
+                "public input at position {} is outside of a circuit of {} \
+                 gates",
+                position, circuit_size
+            ),
+            Self::DanglingVariable { variable } => {
+                write!(f, "{:?} is not used by any gate", variable)
+            }
+            Self::InvalidRowOrder => {
+                write!(f, "invalid ordering of the circuit rows")
+            }
+            Self::UnsupportedR1CSGate { gate_index } => {
+                write!(f, "gate {} cannot be expressed in R1CS", gate_index)
+            }
             Self::PCError { error } => {
                 write!(f, "{:?}", error)
             }
             Self::CircuitAlreadyPreprocessed => {
                 write!(f, "circuit has already been preprocessed")
             }
+            Self::CircuitNotPreprocessed => {
+                write!(f, "circuit has not been preprocessed")
+            }
+            Self::MissingBlindingFactors => {
+                write!(f, "blinding factors of the circuit have not been set")
+            }
+            Self::BatchLengthMismatch {
+                proofs,
+                public_inputs,
+                aux_data,
+            } => write!(
+                f,
+                "batch of {} proofs given {} sets of public inputs and {} \
+                 auxiliary data",
+                proofs, public_inputs, aux_data
+            ),
+            Self::ChallengeInDomain => {
+                write!(f, "evaluation challenge lies in the evaluation domain")
+            }
+            Self::MissingEvaluation { label } => {
+                write!(f, "proof is missing the evaluation {}", label)
+            }
+            Self::EvaluationsSchemaMismatch => {
+                write!(f, "evaluations do not match the evaluations schema")
+            }
+            Self::QuotientPieceCountMismatch { expected, found } => write!(
+                f,
+                "expected {} quotient commitments but the proof has {}",
+                expected, found
+            ),
+            Self::ProofTooLarge => {
+                write!(f, "proof exceeds the allowed size")
+            }
+            Self::MissingSelectorCommitment { selector } => write!(
+                f,
+                "verifier key is missing the selector commitment {}",
+                selector
+            ),
+            Self::DisallowedGateType { gate_type } => write!(
+                f,
+                "verifier key uses the disallowed gate type {:?}",
+                gate_type
+            ),
             Self::DegreeIsZero => {
                 write!(f, "cannot create PublicParameters with max degree 0")
             }
@@ -141,6 +450,14 @@ impl std::fmt::Display for Error {
                 f,
                 "cannot trim PublicParameters to a maximum size of zero"
             ),
+            Self::InsufficientSRS {
+                required,
+                available,
+            } => write!(
+                f,
+                "commit key supports degree {} but the circuit requires {}",
+                available, required
+            ),
             Self::PolynomialDegreeTooLarge => write!(
                 f,
                 "proving key is not large enough to commit to said polynomial"
@@ -152,6 +469,9 @@ impl std::fmt::Display for Error {
             Self::NotEnoughBytes => write!(f, "not enough bytes left to read"),
             Self::PointMalformed => write!(f, "point bytes malformed"),
             Self::ScalarMalformed => write!(f, "scalar bytes malformed"),
+            Self::SerializationError { error } => {
+                write!(f, "{:?}", error)
+            }
         }
     }
 }
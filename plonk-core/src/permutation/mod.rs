@@ -100,7 +100,7 @@ impl Permutation {
     /// Performs shift by one permutation and computes `sigma_1`, `sigma_2` and
     /// `sigma_3`, `sigma_4` permutations from the variable maps.
     pub(super) fn compute_sigma_permutations(
-        &mut self,
+        &self,
         n: usize,
     ) -> [Vec<WireData>; 4] {
         let sigma_1 = (0..n).map(WireData::Left).collect::<Vec<_>>();
@@ -169,6 +169,25 @@ impl Permutation {
         lagrange_poly
     }
 
+    /// Computes the evaluations over `domain` of the sigma polynomials of the
+    /// first `n` rows.
+    pub(crate) fn compute_sigma_evaluations<F: FftField>(
+        &self,
+        n: usize,
+        domain: &GeneralEvaluationDomain<F>,
+    ) -> [Vec<F>; 4] {
+        // Compute sigma mappings
+        let sigmas = self.compute_sigma_permutations(n);
+
+        assert_eq!(sigmas[0].len(), n);
+        assert_eq!(sigmas[1].len(), n);
+        assert_eq!(sigmas[2].len(), n);
+        assert_eq!(sigmas[3].len(), n);
+
+        // define the sigma permutations using two non quadratic residues
+        sigmas.map(|sigma| self.compute_permutation_lagrange(&sigma, domain))
+    }
+
     /// Computes the sigma polynomials which are used to build the permutation
     /// polynomial.
     pub fn compute_sigma_polynomials<F: FftField>(
@@ -181,20 +200,8 @@ impl Permutation {
         DensePolynomial<F>,
         DensePolynomial<F>,
     ) {
-        // Compute sigma mappings
-        let sigmas = self.compute_sigma_permutations(n);
-
-        assert_eq!(sigmas[0].len(), n);
-        assert_eq!(sigmas[1].len(), n);
-        assert_eq!(sigmas[2].len(), n);
-        assert_eq!(sigmas[3].len(), n);
-
-        // define the sigma permutations using two non quadratic residues
-        let left_sigma = self.compute_permutation_lagrange(&sigmas[0], domain);
-        let right_sigma = self.compute_permutation_lagrange(&sigmas[1], domain);
-        let out_sigma = self.compute_permutation_lagrange(&sigmas[2], domain);
-        let fourth_sigma =
-            self.compute_permutation_lagrange(&sigmas[3], domain);
+        let [left_sigma, right_sigma, out_sigma, fourth_sigma] =
+            self.compute_sigma_evaluations(n, domain);
 
         let left_sigma_poly =
             DensePolynomial::from_coefficients_vec(domain.ifft(&left_sigma));
@@ -0,0 +1,361 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Aggregation of the evaluations checked against a batch opening proof.
+
+use crate::{
+    commitment::{check_each, HomomorphicCommitment, OpeningGroup},
+    error::{to_pc_error, Error},
+};
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use rand::RngCore;
+
+/// Commitments to several polynomials together with their claimed
+/// evaluations at a single point, which are checked at once against a batch
+/// opening proof.
+///
+/// Both the prover and the verifier build one [`AggregateProof`] for the
+/// polynomials opened at the evaluation challenge `z`, and another one for
+/// the polynomials opened at the shifted challenge `z * omega`. The prover
+/// [`open`](Self::open)s it and the verifier [`verify`](Self::verify)s the
+/// opening, so both sides agree on the order of the polynomials.
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Default(bound = ""))]
+pub struct AggregateProof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Commitments to the aggregated polynomials.
+    #[derivative(Debug = "ignore")]
+    commitments: Vec<LabeledCommitment<PC::Commitment>>,
+
+    /// Claimed evaluations of the aggregated polynomials, in the same order
+    /// as their commitments.
+    evaluations: Vec<F>,
+}
+
+impl<F, PC> AggregateProof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Creates an empty [`AggregateProof`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `commitment` to the aggregate, claiming that the committed
+    /// polynomial evaluates to `eval` at the opening point.
+    pub fn add(&mut self, commitment: &PC::Commitment, eval: F) {
+        let label = format!("aggregated_{}", self.commitments.len());
+        self.commitments.push(LabeledCommitment::new(
+            label,
+            commitment.clone(),
+            None,
+        ));
+        self.evaluations.push(eval);
+    }
+
+    /// Computes the batch opening at `point`, combined with `challenge`, of
+    /// the polynomials added so far.
+    ///
+    /// `polynomials` and `rands` are the committed polynomials and the
+    /// randomness of their commitments, in the order in which the
+    /// commitments were added. When the commitments are hiding, `rng`
+    /// blinds the opening.
+    pub fn open<'a>(
+        &'a self,
+        commit_key: &PC::CommitterKey,
+        polynomials: &[&'a LabeledPolynomial<F, DensePolynomial<F>>],
+        rands: &[&'a PC::Randomness],
+        point: &'a F,
+        challenge: F,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<PC::Proof, Error> {
+        debug_assert_eq!(polynomials.len(), self.commitments.len());
+        debug_assert_eq!(rands.len(), self.commitments.len());
+        debug_assert!(polynomials
+            .iter()
+            .zip(&self.evaluations)
+            .all(|(p, eval)| p.evaluate(point) == *eval));
+        // The commitment scheme matches the polynomials with their
+        // commitments by label.
+        let commitments = polynomials
+            .iter()
+            .zip(&self.commitments)
+            .map(|(polynomial, commitment)| {
+                LabeledCommitment::new(
+                    polynomial.label().clone(),
+                    commitment.commitment().clone(),
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        PC::open(
+            commit_key,
+            polynomials.iter().copied(),
+            &commitments,
+            point,
+            challenge,
+            rands.iter().copied(),
+            rng,
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+
+    /// Returns the [`OpeningGroup`] checking `opening` as a batch opening at
+    /// `point`, combined with `challenge`, of the polynomials added so far.
+    pub fn opening_group<'a>(
+        &'a self,
+        point: F,
+        opening: &'a PC::Proof,
+        challenge: F,
+    ) -> OpeningGroup<'a, F, PC> {
+        OpeningGroup {
+            point,
+            commitments: &self.commitments,
+            evaluations: &self.evaluations,
+            opening,
+            challenge,
+        }
+    }
+
+    /// Checks that `opening` is a valid batch opening at `point`, combined
+    /// with `challenge`, of the polynomials added so far to their claimed
+    /// evaluations.
+    ///
+    /// Returns [`Error::ProofVerificationError`] if it is not.
+    ///
+    /// # Note
+    ///
+    /// Hiding openings, produced from blinded commitments like the ones of
+    /// [`Prover::prove_hiding`](crate::proof_system::Prover::prove_hiding),
+    /// are checked in the same way since the prover folds the blinding into
+    /// the opening proof. The randomness argument of `PC::check` only
+    /// randomizes batched checks, so it is left empty.
+    pub fn verify(
+        &self,
+        pc_vk: &PC::VerifierKey,
+        point: &F,
+        opening: &PC::Proof,
+        challenge: F,
+    ) -> Result<(), Error> {
+        let valid = check_each::<F, PC>(
+            pc_vk,
+            &[self.opening_group(*point, opening, challenge)],
+        )
+        .map_err(to_pc_error::<F, PC>)?;
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::ProofVerificationError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_kzg;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+    use ark_poly::UVPolynomial;
+    use ark_serialize::CanonicalSerialize;
+    use rand::rngs::OsRng;
+
+    fn test_aggregate_proof<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // Open three random polynomials at a random point.
+        let polys = (0..3)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("poly_{}", i),
+                    DensePolynomial::rand(16, &mut OsRng),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, &polys, None).unwrap();
+        let point = F::rand(&mut OsRng);
+        let challenge = F::rand(&mut OsRng);
+
+        let mut aggregate = AggregateProof::<F, PC>::new();
+        for (poly, commitment) in polys.iter().zip(&commitments) {
+            aggregate.add(commitment.commitment(), poly.evaluate(&point));
+        }
+        let opening = aggregate
+            .open(
+                &ck,
+                &polys.iter().collect::<Vec<_>>(),
+                &rands.iter().collect::<Vec<_>>(),
+                &point,
+                challenge,
+                None,
+            )
+            .unwrap();
+        assert!(aggregate.verify(&vk, &point, &opening, challenge).is_ok());
+
+        // It is the same opening as the one computed by hand.
+        let by_hand = PC::open(
+            &ck,
+            &polys,
+            &commitments,
+            &point,
+            challenge,
+            &rands,
+            None,
+        )
+        .unwrap();
+        let to_bytes = |opening: &PC::Proof| {
+            let mut bytes = Vec::new();
+            opening.serialize(&mut bytes).unwrap();
+            bytes
+        };
+        assert_eq!(to_bytes(&opening), to_bytes(&by_hand));
+
+        // The opening only holds for the same point and challenge.
+        assert!(matches!(
+            aggregate.verify(&vk, &(point + F::one()), &opening, challenge),
+            Err(Error::ProofVerificationError)
+        ));
+        assert!(matches!(
+            aggregate.verify(&vk, &point, &opening, challenge + F::one()),
+            Err(Error::ProofVerificationError)
+        ));
+
+        // A wrong evaluation is rejected.
+        let mut wrong = AggregateProof::<F, PC>::new();
+        for (i, poly) in polys.iter().enumerate() {
+            let mut eval = poly.evaluate(&point);
+            if i == 1 {
+                eval += F::one();
+            }
+            wrong.add(commitments[i].commitment(), eval);
+        }
+        assert!(matches!(
+            wrong.verify(&vk, &point, &opening, challenge),
+            Err(Error::ProofVerificationError)
+        ));
+    }
+
+    fn test_hiding_aggregate_proof<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 1, None).unwrap();
+
+        let polys = (0..2)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("poly_{}", i),
+                    DensePolynomial::rand(16, &mut OsRng),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let point = F::rand(&mut OsRng);
+        let challenge = F::rand(&mut OsRng);
+
+        // An opening of the polynomials, threading the randomness through the
+        // aggregate like the prover does when `hiding`.
+        let open = |hiding: bool| {
+            let polys = polys
+                .iter()
+                .map(|p| {
+                    LabeledPolynomial::new(
+                        p.label().clone(),
+                        p.polynomial().clone(),
+                        None,
+                        hiding.then(|| 1),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let (mut commit_rng, mut open_rng) = (OsRng, OsRng);
+            let (commit_rng, open_rng): (
+                Option<&mut dyn RngCore>,
+                Option<&mut dyn RngCore>,
+            ) = if hiding {
+                (Some(&mut commit_rng), Some(&mut open_rng))
+            } else {
+                (None, None)
+            };
+            let (commitments, rands) =
+                PC::commit(&ck, &polys, commit_rng).unwrap();
+            let mut aggregate = AggregateProof::<F, PC>::new();
+            for (poly, commitment) in polys.iter().zip(&commitments) {
+                aggregate.add(commitment.commitment(), poly.evaluate(&point));
+            }
+            let opening = aggregate
+                .open(
+                    &ck,
+                    &polys.iter().collect::<Vec<_>>(),
+                    &rands.iter().collect::<Vec<_>>(),
+                    &point,
+                    challenge,
+                    open_rng,
+                )
+                .unwrap();
+            (aggregate, opening)
+        };
+        let (first, first_opening) = open(true);
+        let (second, second_opening) = open(true);
+        let (plain, plain_opening) = open(false);
+
+        assert!(first.verify(&vk, &point, &first_opening, challenge).is_ok());
+        assert!(second
+            .verify(&vk, &point, &second_opening, challenge)
+            .is_ok());
+        assert!(plain.verify(&vk, &point, &plain_opening, challenge).is_ok());
+
+        // The blinding is actually used: the hiding openings differ from each
+        // other and from the plain one, and none of them verifies against
+        // the commitments of another.
+        let to_bytes = |opening: &PC::Proof| {
+            let mut bytes = Vec::new();
+            opening.serialize(&mut bytes).unwrap();
+            bytes
+        };
+        assert_ne!(to_bytes(&first_opening), to_bytes(&second_opening));
+        assert_ne!(to_bytes(&first_opening), to_bytes(&plain_opening));
+        assert!(first
+            .verify(&vk, &point, &second_opening, challenge)
+            .is_err());
+        assert!(plain
+            .verify(&vk, &point, &first_opening, challenge)
+            .is_err());
+    }
+
+    // Bls12-381 tests
+    batch_test_kzg!(
+        [test_aggregate_proof, test_hiding_aggregate_proof],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+    // Bls12-377 tests
+    batch_test_kzg!(
+        [test_aggregate_proof, test_hiding_aggregate_proof],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
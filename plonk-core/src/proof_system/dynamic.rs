@@ -0,0 +1,317 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Runtime selection of the commitment scheme a [`Proof`] is verified with.
+
+use crate::{
+    commitment::{HomomorphicCommitment, IPA, KZG10},
+    error::Error,
+    proof_system::{Proof, Verifier},
+};
+use ark_ec::{AffineCurve, PairingEngine, TEModelParameters};
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::PolynomialCommitment;
+use blake2::digest::Digest;
+
+/// Commitment scheme a [`Proof`] was created with.
+///
+/// It is stored as a one byte tag in front of the proofs serialized with
+/// [`Proof::to_tagged_bytes`], so that a [`DynVerifier`] can pick the scheme
+/// at runtime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentScheme {
+    /// KZG10 commitments, see [`KZG10`].
+    Kzg,
+
+    /// Inner product argument commitments, see [`IPA`].
+    Ipa,
+}
+
+impl CommitmentScheme {
+    /// Returns the tag of the scheme.
+    pub fn tag(self) -> u8 {
+        match self {
+            Self::Kzg => 0,
+            Self::Ipa => 1,
+        }
+    }
+
+    /// Returns the scheme with the given `tag`, or
+    /// [`Error::UnsupportedCommitmentScheme`] if there is none.
+    pub fn from_tag(tag: u8) -> Result<Self, Error> {
+        match tag {
+            0 => Ok(Self::Kzg),
+            1 => Ok(Self::Ipa),
+            tag => Err(Error::UnsupportedCommitmentScheme { tag }),
+        }
+    }
+
+    /// Returns the scheme of a proof serialized with
+    /// [`Proof::to_tagged_bytes`].
+    pub fn of_tagged_proof(tagged_proof: &[u8]) -> Result<Self, Error> {
+        let tag = tagged_proof.first().ok_or(Error::NotEnoughBytes)?;
+        Self::from_tag(*tag)
+    }
+}
+
+/// [`HomomorphicCommitment`] scheme which can be selected at runtime by a
+/// [`DynVerifier`].
+pub trait TaggedCommitment<F>: HomomorphicCommitment<F>
+where
+    F: PrimeField,
+    Self::VerifierKey: std::fmt::Debug,
+{
+    /// Scheme of the commitments.
+    const SCHEME: CommitmentScheme;
+}
+
+impl<E> TaggedCommitment<E::Fr> for KZG10<E>
+where
+    E: PairingEngine,
+{
+    const SCHEME: CommitmentScheme = CommitmentScheme::Kzg;
+}
+
+impl<G, D> TaggedCommitment<G::ScalarField> for IPA<G, D>
+where
+    G: AffineCurve,
+    D: Digest,
+{
+    const SCHEME: CommitmentScheme = CommitmentScheme::Ipa;
+}
+
+impl<F, PC> Proof<F, PC>
+where
+    F: PrimeField,
+    PC: TaggedCommitment<F>,
+{
+    /// Serializes the [`Proof`] like [`Proof::to_bytes`], preceded by the
+    /// tag of its [`CommitmentScheme`].
+    pub fn to_tagged_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![PC::SCHEME.tag()];
+        bytes.extend(self.to_bytes());
+        bytes
+    }
+}
+
+/// Verifier key of the polynomial commitment scheme `PC`.
+type PCVerifierKey<F, PC> =
+    <PC as PolynomialCommitment<F, DensePolynomial<F>>>::VerifierKey;
+
+/// [`Verifier`] of a preprocessed circuit over the commitment schemes of
+/// this crate, selected at runtime.
+///
+/// The proofs it verifies are serialized with [`Proof::to_tagged_bytes`],
+/// and each of them is verified with the scheme given by its tag. A service
+/// verifying proofs of several schemes sets up the verifier of each scheme
+/// once and hands every proof to the same [`DynVerifier`].
+#[allow(clippy::type_complexity)] // NOTE: Pairs of verifiers and keys.
+pub struct DynVerifier<E, P, D>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    D: Digest,
+{
+    /// Verifier of proofs over [`KZG10`] commitments, together with the
+    /// verifier key of the commitment scheme.
+    kzg: Option<(Verifier<E::Fr, P, KZG10<E>>, PCVerifierKey<E::Fr, KZG10<E>>)>,
+
+    /// Verifier of proofs over [`IPA`] commitments, together with the
+    /// verifier key of the commitment scheme.
+    ipa: Option<(
+        Verifier<E::Fr, P, IPA<E::G1Affine, D>>,
+        PCVerifierKey<E::Fr, IPA<E::G1Affine, D>>,
+    )>,
+}
+
+impl<E, P, D> DynVerifier<E, P, D>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    D: Digest,
+{
+    /// Creates a [`DynVerifier`] which is not set up for any commitment
+    /// scheme yet.
+    pub fn new() -> Self {
+        Self {
+            kzg: None,
+            ipa: None,
+        }
+    }
+
+    /// Sets up the verification of proofs over [`KZG10`] commitments with
+    /// the preprocessed `verifier` and the `verifier_key` of the scheme,
+    /// replacing any previous setup of that scheme.
+    pub fn set_kzg(
+        &mut self,
+        verifier: Verifier<E::Fr, P, KZG10<E>>,
+        verifier_key: PCVerifierKey<E::Fr, KZG10<E>>,
+    ) {
+        self.kzg = Some((verifier, verifier_key));
+    }
+
+    /// Sets up the verification of proofs over [`IPA`] commitments with the
+    /// preprocessed `verifier` and the `verifier_key` of the scheme,
+    /// replacing any previous setup of that scheme.
+    pub fn set_ipa(
+        &mut self,
+        verifier: Verifier<E::Fr, P, IPA<E::G1Affine, D>>,
+        verifier_key: PCVerifierKey<E::Fr, IPA<E::G1Affine, D>>,
+    ) {
+        self.ipa = Some((verifier, verifier_key));
+    }
+
+    /// Returns `true` if this verifier is set up for proofs over `scheme`.
+    pub fn supports(&self, scheme: CommitmentScheme) -> bool {
+        match scheme {
+            CommitmentScheme::Kzg => self.kzg.is_some(),
+            CommitmentScheme::Ipa => self.ipa.is_some(),
+        }
+    }
+
+    /// Verifies a proof serialized with [`Proof::to_tagged_bytes`] against
+    /// `public_inputs`, with the commitment scheme given by its tag.
+    ///
+    /// Returns [`Error::MissingCommitmentScheme`] if this verifier was not
+    /// set up for the commitment scheme of the proof.
+    pub fn verify(
+        &self,
+        tagged_proof: &[u8],
+        public_inputs: &[E::Fr],
+    ) -> Result<(), Error> {
+        let scheme = CommitmentScheme::of_tagged_proof(tagged_proof)?;
+        let proof_bytes = &tagged_proof[1..];
+        let missing = Error::MissingCommitmentScheme { scheme };
+        match scheme {
+            CommitmentScheme::Kzg => {
+                let (verifier, verifier_key) =
+                    self.kzg.as_ref().ok_or(missing)?;
+                verifier.verify(
+                    &Proof::from_bytes(proof_bytes)?,
+                    verifier_key,
+                    public_inputs,
+                )
+            }
+            CommitmentScheme::Ipa => {
+                let (verifier, verifier_key) =
+                    self.ipa.as_ref().ok_or(missing)?;
+                verifier.verify(
+                    &Proof::from_bytes(proof_bytes)?,
+                    verifier_key,
+                    public_inputs,
+                )
+            }
+        }
+    }
+}
+
+impl<E, P, D> Default for DynVerifier<E, P, D>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    D: Digest,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constraint_system::{helper::*, StandardComposer};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use blake2::Blake2s;
+
+    /// Returns a tagged proof over `PC` for the circuit of [`pi_circuit`],
+    /// together with the preprocessed verifier of the circuit and the
+    /// verifier key of `PC`.
+    fn tagged_proof<F, P, PC>(
+    ) -> (Vec<u8>, Verifier<F, P, PC>, PCVerifierKey<F, PC>)
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: TaggedCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"dynamic");
+        (proof.to_tagged_bytes(), verifier, vk)
+    }
+
+    fn test_dyn_verifier<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // The public inputs of the proofs of `pi_circuit`.
+        let mut composer = StandardComposer::<E::Fr, P>::new();
+        pi_gadget(&mut composer, E::Fr::from(3u64));
+        let public_inputs = composer.construct_dense_pi_vec();
+
+        let (kzg_proof, kzg_verifier, kzg_key) =
+            tagged_proof::<_, P, KZG10<E>>();
+        let (ipa_proof, ipa_verifier, ipa_key) =
+            tagged_proof::<_, P, IPA<E::G1Affine, Blake2s>>();
+        assert_eq!(kzg_proof[0], CommitmentScheme::Kzg.tag());
+        assert_eq!(ipa_proof[0], CommitmentScheme::Ipa.tag());
+
+        // A verifier only set up for KZG rejects IPA proofs.
+        let mut verifier = DynVerifier::<E, P, Blake2s>::new();
+        verifier.set_kzg(kzg_verifier, kzg_key);
+        assert!(verifier.supports(CommitmentScheme::Kzg));
+        assert!(!verifier.supports(CommitmentScheme::Ipa));
+        assert!(verifier.verify(&kzg_proof, &public_inputs).is_ok());
+        assert!(matches!(
+            verifier.verify(&ipa_proof, &public_inputs),
+            Err(Error::MissingCommitmentScheme {
+                scheme: CommitmentScheme::Ipa,
+            })
+        ));
+
+        // Once set up for both, each proof is verified with the scheme of
+        // its tag.
+        verifier.set_ipa(ipa_verifier, ipa_key);
+        for proof in [&kzg_proof, &ipa_proof] {
+            assert!(verifier.verify(proof, &public_inputs).is_ok());
+            assert!(verifier.verify(proof, &[E::Fr::from(4u64)]).is_err());
+        }
+
+        // A proof tagged with the wrong scheme does not deserialize into the
+        // other one.
+        let mut retagged = kzg_proof.clone();
+        retagged[0] = CommitmentScheme::Ipa.tag();
+        assert!(verifier.verify(&retagged, &public_inputs).is_err());
+
+        let mut unknown = kzg_proof;
+        unknown[0] = 2;
+        assert!(matches!(
+            verifier.verify(&unknown, &public_inputs),
+            Err(Error::UnsupportedCommitmentScheme { tag: 2 })
+        ));
+        assert!(matches!(
+            verifier.verify(&[], &public_inputs),
+            Err(Error::NotEnoughBytes)
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_dyn_verifier_on_Bls12_381() {
+        test_dyn_verifier::<Bls12_381, ark_ed_on_bls12_381::EdwardsParameters>()
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_dyn_verifier_on_Bls12_377() {
+        test_dyn_verifier::<Bls12_377, ark_ed_on_bls12_377::EdwardsParameters>()
+    }
+}
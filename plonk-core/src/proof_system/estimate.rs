@@ -0,0 +1,288 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Static estimation of the resources needed to prove a circuit.
+
+use crate::{
+    commitment::HomomorphicCommitment,
+    proof_system::{Proof, VerifierKey},
+};
+use ark_ff::PrimeField;
+use core::mem::size_of;
+
+/// Number of FFTs over the evaluation domain of size `n` performed while
+/// proving: the public inputs, the four wires, the four sigmas and the
+/// permutation polynomial.
+const DOMAIN_FFTS: usize = 10;
+
+/// Number of FFTs over the `4n` extended domain performed while proving: the
+/// four wires, the permutation polynomial, the public inputs and the quotient
+/// polynomial.
+const EXTENDED_DOMAIN_FFTS: usize = 7;
+
+/// Number of polynomials of degree `n` committed to or opened while proving:
+/// the four wires, the permutation polynomial, the four pieces of the
+/// quotient polynomial, the linearisation polynomial, three sigmas and the
+/// two opening proofs.
+const COMMITTED_POLYNOMIALS: usize = 15;
+
+/// Number of vectors of `n` field elements held by a [`ProverKey`] using all
+/// of the gate types: the polynomials and `4n` evaluations of the eleven
+/// selectors and four sigmas, the `4n` linear evaluations of the permutation
+/// and the `4n` evaluations of the vanishing polynomial and the first
+/// Lagrange polynomial.
+///
+/// [`ProverKey`]: crate::proof_system::ProverKey
+const PROVER_KEY_VECTORS: usize = 15 * 5 + 4 + 4 + 4;
+
+/// Number of vectors of `n` field elements computed while proving: the
+/// polynomials and `4n` evaluations of the four wires, the permutation
+/// polynomial and the public inputs, and the `4n` evaluations of the quotient
+/// polynomial.
+const PROVING_VECTORS: usize = 6 * 5 + 4;
+
+/// Number of commitments opened at the evaluation challenge `z`: the
+/// linearisation commitment, the first three sigmas and the four wires.
+const OPENED_AT_Z: usize = 8;
+
+/// Number of commitments opened at the shifted challenge `z * omega`: the
+/// permutation polynomial and the three wires whose next values are used by
+/// the custom gates.
+const OPENED_AT_SHIFTED_Z: usize = 4;
+
+/// Number of operations performed by the verification of a [`Proof`], as
+/// returned by [`Proof::operation_counts`].
+///
+/// The counts cover the group operations of the PLONK verifier, which
+/// dominate its cost, so that they can be priced by an on-chain verifier.
+/// The field operations of the verifier, the evaluation of the Public Inputs
+/// and the Fiat-Shamir transcript are left out.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OperationCounts {
+    /// Number of terms of the multi-scalar multiplications over the group:
+    /// the terms of the linearisation commitment and the ones computed by
+    /// the commitment scheme to check the two batch openings.
+    pub num_g1_msm_terms: usize,
+
+    /// Number of pairings computed to check the two batch openings, which
+    /// are checked at once when the commitment scheme permits it.
+    pub num_pairings: usize,
+}
+
+impl<F, PC> Proof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Returns the [`OperationCounts`] of the verification of this [`Proof`]
+    /// against `plonk_verifier_key` and the commitment scheme `verifier_key`.
+    ///
+    /// The counts are derived statically from the verification algorithm:
+    /// they depend on the gate types present in the circuit, which determine
+    /// the size of the linearisation commitment, and on the cost of the
+    /// opening checks of the commitment scheme.
+    pub fn operation_counts(
+        &self,
+        plonk_verifier_key: &VerifierKey<F, PC>,
+        verifier_key: &PC::VerifierKey,
+    ) -> OperationCounts {
+        // One selector commitment per gate type on top of the six arithmetic
+        // ones, the permutation polynomial, the fourth sigma and the
+        // quotient pieces.
+        let linearisation_terms = 6
+            + (plonk_verifier_key.gate_types().len() - 1)
+            + 2
+            + self.t_comms.len();
+        let opening_terms = PC::multi_point_msm_terms(
+            verifier_key,
+            &[OPENED_AT_Z, OPENED_AT_SHIFTED_Z],
+        );
+
+        OperationCounts {
+            num_g1_msm_terms: linearisation_terms + opening_terms,
+            num_pairings: PC::multi_point_pairings(2),
+        }
+    }
+}
+
+/// Rough estimate of the resources needed to prove a circuit, computed from
+/// the structure of the proving algorithm by [`estimate_resources`].
+///
+/// This is a static calculation meant to compare circuit designs, not a
+/// measurement.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResourceEstimate {
+    /// Size `n` of the evaluation domain of the circuit.
+    pub domain_size: usize,
+
+    /// Number of FFTs performed while proving, over either the evaluation
+    /// domain or its `4n` extension. The work of each of them grows as
+    /// `n log n`.
+    pub num_ffts: usize,
+
+    /// Memory used by the prover key and the polynomials computed while
+    /// proving, in bytes.
+    ///
+    /// It assumes that the circuit uses all of the gate types, and is within
+    /// a factor 4 of the peak memory measured while preprocessing and
+    /// proving a circuit only made of arithmetic gates.
+    pub peak_memory_bytes: usize,
+
+    /// Total number of points of the multi-scalar multiplications performed
+    /// by the commitments and opening proofs.
+    pub est_msm_size: usize,
+}
+
+/// Estimates the resources needed to prove a circuit of `num_gates` gates,
+/// as returned by [`StandardComposer::circuit_size`].
+///
+/// [`StandardComposer::circuit_size`]:
+/// crate::constraint_system::StandardComposer::circuit_size
+pub fn estimate_resources<F>(num_gates: usize) -> ResourceEstimate
+where
+    F: PrimeField,
+{
+    let domain_size = num_gates.next_power_of_two();
+    ResourceEstimate {
+        domain_size,
+        num_ffts: DOMAIN_FFTS + EXTENDED_DOMAIN_FFTS,
+        peak_memory_bytes: (PROVER_KEY_VECTORS + PROVING_VECTORS)
+            * domain_size
+            * size_of::<F>(),
+        est_msm_size: COMMITTED_POLYNOMIALS * domain_size,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_field;
+    #[cfg(feature = "tracing")]
+    use crate::{
+        batch_test,
+        constraint_system::StandardComposer,
+        proof_system::{Prover, Verifier},
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    #[cfg(feature = "tracing")]
+    use ark_ec::TEModelParameters;
+    #[cfg(feature = "tracing")]
+    use rand::rngs::OsRng;
+
+    fn test_estimate_scaling<F>()
+    where
+        F: PrimeField,
+    {
+        let small = estimate_resources::<F>(1000);
+        let large = estimate_resources::<F>(2000);
+        assert_eq!(small.domain_size, 1024);
+        assert_eq!(large.domain_size, 2 * small.domain_size);
+
+        // Doubling the gates doubles the work of each FFT, and the memory and
+        // multi-scalar multiplications.
+        assert_eq!(large.num_ffts, small.num_ffts);
+        assert_eq!(large.peak_memory_bytes, 2 * small.peak_memory_bytes);
+        assert_eq!(large.est_msm_size, 2 * small.est_msm_size);
+
+        // Circuits fitting in the same domain cost the same.
+        assert_eq!(estimate_resources::<F>(1024), small);
+    }
+
+    #[cfg(feature = "tracing")]
+    fn test_operation_counts<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        use crate::test::SpanRecorder;
+
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let range = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(42u64));
+            composer.range_gate(a, 8);
+        };
+        let arithmetic = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None).add(F::one(), F::one())
+            });
+        };
+        let check = |gadget: &dyn Fn(&mut StandardComposer<F, P>)| {
+            let mut prover = Prover::<F, P, PC>::new(b"counts");
+            gadget(prover.mut_cs());
+            let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+            let proof = prover.prove(&ck).unwrap();
+
+            let mut verifier = Verifier::<F, P, PC>::new(b"counts");
+            gadget(verifier.mut_cs());
+            verifier.preprocess(&ck).unwrap();
+            let plonk_verifier_key = verifier.verifier_key.as_ref().unwrap();
+            let counts = proof.operation_counts(plonk_verifier_key, &vk);
+
+            // Tally the group operations of an actual verification.
+            let (spans, verified) = SpanRecorder::record(|| {
+                verifier.verify(&proof, &vk, &public_inputs)
+            });
+            assert!(verified.is_ok());
+            let group_ops = spans
+                .iter()
+                .filter(|span| span.name == "group_ops")
+                .collect::<Vec<_>>();
+            let msm_terms = group_ops
+                .iter()
+                .map(|span| span.field("msm_terms"))
+                .sum::<u64>();
+            let pairings = group_ops
+                .iter()
+                .map(|span| span.field("pairings"))
+                .sum::<u64>();
+            assert_eq!(counts.num_g1_msm_terms as u64, msm_terms);
+            assert_eq!(counts.num_pairings as u64, pairings);
+        };
+        check(&range);
+        check(&arithmetic);
+    }
+
+    // Tests for Bls12_381
+    batch_test_field!(
+        [test_estimate_scaling],
+        [] => (
+            Bls12_381
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test_field!(
+        [test_estimate_scaling],
+        [] => (
+            Bls12_377
+        )
+    );
+
+    // Tests for Bls12_381
+    #[cfg(feature = "tracing")]
+    batch_test!(
+        [test_operation_counts],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    #[cfg(feature = "tracing")]
+    batch_test!(
+        [test_operation_counts],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -0,0 +1,198 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Shareable handle over a preprocessed circuit.
+
+use crate::{
+    commitment::HomomorphicCommitment,
+    constraint_system::StandardComposer,
+    error::Error,
+    proof_system::{Proof, Prover, ProverKey},
+};
+use alloc::sync::Arc;
+use ark_ec::TEModelParameters;
+use ark_ff::PrimeField;
+use core::marker::PhantomData;
+use merlin::Transcript;
+
+/// Cheaply clonable handle holding everything needed to prove a
+/// preprocessed circuit: the [`ProverKey`], the commit key and the
+/// preprocessed [`Transcript`].
+///
+/// The keys are shared behind an [`Arc`], so a [`ProverHandle`] can be cloned
+/// into as many threads as needed, each of them proving its own witness for
+/// the same circuit. The handle is `Send + Sync` as long as the commit key of
+/// `PC` is, which is the case for the commitment schemes of this crate.
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct ProverHandle<F, P, PC>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Proving Key of the circuit.
+    prover_key: Arc<ProverKey<F>>,
+
+    /// Commit Key used to prove the circuit.
+    commit_key: Arc<PC::CommitterKey>,
+
+    /// Transcript seeded with the circuit description.
+    preprocessed_transcript: Transcript,
+
+    _phantom: PhantomData<P>,
+}
+
+impl<F, P, PC> ProverHandle<F, P, PC>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Builds a [`ProverHandle`] out of `prover`, preprocessing its circuit
+    /// with `commit_key` if this was not done yet.
+    pub fn new(
+        mut prover: Prover<F, P, PC>,
+        commit_key: Arc<PC::CommitterKey>,
+    ) -> Result<Self, Error> {
+        if prover.prover_key.is_none() {
+            prover.preprocess(&commit_key)?;
+        }
+        Ok(Self {
+            prover_key: Arc::new(
+                prover
+                    .prover_key
+                    .expect("the circuit was just preprocessed"),
+            ),
+            commit_key,
+            preprocessed_transcript: prover.preprocessed_transcript,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the [`ProverKey`] of the circuit.
+    pub fn prover_key(&self) -> &ProverKey<F> {
+        &self.prover_key
+    }
+
+    /// Returns the commit key used to prove the circuit.
+    pub fn commit_key(&self) -> &PC::CommitterKey {
+        &self.commit_key
+    }
+
+    /// Creates a [`Proof`] for the witness filled in by `gadget`, which must
+    /// describe the same circuit as the one this handle was built from.
+    pub fn prove<G>(&self, gadget: G) -> Result<Proof<F, PC>, Error>
+    where
+        G: FnOnce(&mut StandardComposer<F, P>) -> Result<(), Error>,
+    {
+        let mut prover = Prover::<F, P, PC>::new(b"plonk");
+        prover.preprocessed_transcript = self.preprocessed_transcript.clone();
+        gadget(prover.mut_cs())?;
+        prover.prove_with_preprocessed(
+            &self.commit_key,
+            &self.prover_key,
+            PhantomData,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{batch_test, proof_system::Verifier};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use rand::rngs::OsRng;
+    use std::thread;
+
+    fn assert_send_sync<T>()
+    where
+        T: Send + Sync,
+    {
+    }
+
+    /// Adds a gate whose output depends on the public input `value`.
+    fn pi_gadget<F, P>(
+        composer: &mut StandardComposer<F, P>,
+        value: F,
+    ) -> Result<(), Error>
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let one = composer.add_input(F::one());
+        composer.arithmetic_gate(|gate| {
+            gate.witness(one, one, None)
+                .add(F::one(), F::one())
+                .pi(value)
+        });
+        Ok(())
+    }
+
+    fn test_concurrent_proving<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F> + 'static,
+        PC::CommitterKey: Send + Sync,
+        ProverHandle<F, P, PC>: Send + Sync,
+        Proof<F, PC>: Send,
+    {
+        assert_send_sync::<ProverKey<F>>();
+        assert_send_sync::<ProverHandle<F, P, PC>>();
+
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"handle");
+        pi_gadget(prover.mut_cs(), F::zero()).unwrap();
+        let handle = ProverHandle::new(prover, Arc::new(ck.clone())).unwrap();
+
+        let threads = (1..=4u64)
+            .map(|value| {
+                let handle = handle.clone();
+                thread::spawn(move || {
+                    let value = F::from(value);
+                    let proof = handle
+                        .prove(|composer| pi_gadget(composer, value))
+                        .unwrap();
+                    (value, proof)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"handle");
+        pi_gadget(verifier.mut_cs(), F::zero()).unwrap();
+        verifier.preprocess(&ck).unwrap();
+
+        for thread in threads {
+            let (value, proof) = thread.join().unwrap();
+            let mut composer = StandardComposer::<F, P>::new();
+            pi_gadget(&mut composer, value).unwrap();
+            let public_inputs = composer.construct_dense_pi_vec();
+            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+        }
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [test_concurrent_proving],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [test_concurrent_proving],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
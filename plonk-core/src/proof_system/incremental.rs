@@ -0,0 +1,328 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Incremental preprocessing of the verifier key of a circuit.
+
+use crate::{
+    commitment::HomomorphicCommitment,
+    constraint_system::StandardComposer,
+    error::{to_pc_error, Error},
+    proof_system::widget::VerifierKey,
+    util::radix2_domain,
+};
+use alloc::collections::BTreeSet;
+use ark_ec::TEModelParameters;
+use ark_ff::{PrimeField, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
+    UVPolynomial,
+};
+use hashbrown::HashMap;
+
+/// Number of columns of the circuit description committed to in the
+/// [`VerifierKey`]: eleven selectors and four sigma permutations.
+const NUM_COLUMNS: usize = 15;
+
+/// [`VerifierKey`] of a circuit which is kept up to date as the circuit
+/// changes, instead of being preprocessed again from scratch.
+///
+/// The [`VerifierKey`] holds commitments to the polynomials interpolating
+/// the selectors and the sigma permutations of the circuit over its
+/// evaluation domain. When the values of such a column change at some rows,
+/// the commitment scheme being homomorphic, its commitment is updated by
+/// adding the commitments to the Lagrange polynomials of those rows, scaled
+/// by the differences of the values.
+///
+/// # Complexity
+///
+/// For a domain of size `n`, preprocessing from scratch costs one IFFT and
+/// one commitment, an MSM of size `n`, for each of the 15 columns.
+/// [`IncrementalVerifierKey::update`] instead costs `O(n)` to find the
+/// changed rows, one IFFT and one commitment for each row which never
+/// changed before, whose commitment to its Lagrange polynomial is then
+/// cached, and an MSM as small as the number of changed rows for each
+/// changed column.
+///
+/// Appending a gate changes its row, and the rows of the previous last wire
+/// of each [`Variable`](crate::constraint_system::Variable) it reuses, so
+/// that updating after a few gates are appended is much cheaper than
+/// preprocessing again. This comes at the price of `O(n)` commitments kept
+/// in memory, and a first update of many rows at once is slower than
+/// preprocessing again. When the circuit outgrows its domain, all of the
+/// polynomials change and the key is computed again from scratch.
+pub struct IncrementalVerifierKey<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Evaluation domain of the circuit.
+    domain: GeneralEvaluationDomain<F>,
+
+    /// Values over `domain` of the columns committed to.
+    columns: Vec<Vec<F>>,
+
+    /// Commitments to the polynomials interpolating `columns`.
+    commitments: Vec<PC::Commitment>,
+
+    /// Commitments to the Lagrange polynomials of the rows of `domain`
+    /// computed so far.
+    lagrange_commitments: HashMap<usize, PC::Commitment>,
+}
+
+impl<F, PC> IncrementalVerifierKey<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Preprocesses the circuit of `composer` from scratch.
+    ///
+    /// Unlike [`Verifier::preprocess`](super::Verifier::preprocess), the
+    /// circuit is left unpadded, so that gates can still be appended to it.
+    pub fn new<P>(
+        composer: &StandardComposer<F, P>,
+        commit_key: &PC::CommitterKey,
+    ) -> Result<Self, Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        let domain = radix2_domain::<F>(composer.circuit_size())?;
+        let columns = columns(composer, &domain)?;
+        let polys = columns
+            .iter()
+            .map(|column| {
+                DensePolynomial::from_coefficients_vec(domain.ifft(column))
+            })
+            .collect::<Vec<_>>();
+        let commitments = PC::batch_commit(commit_key, &polys)
+            .map_err(to_pc_error::<F, PC>)?;
+        Ok(Self {
+            domain,
+            columns,
+            commitments,
+            lagrange_commitments: HashMap::new(),
+        })
+    }
+
+    /// Updates the key to the current circuit of `composer`, only committing
+    /// to the changes since the last update.
+    ///
+    /// If the circuit no longer fits in the evaluation domain, the key is
+    /// computed again from scratch with [`IncrementalVerifierKey::new`].
+    pub fn update<P>(
+        &mut self,
+        composer: &StandardComposer<F, P>,
+        commit_key: &PC::CommitterKey,
+    ) -> Result<(), Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        let domain = radix2_domain::<F>(composer.circuit_size())?;
+        if domain.size() != self.domain.size() {
+            *self = Self::new(composer, commit_key)?;
+            return Ok(());
+        }
+
+        let columns = columns(composer, &domain)?;
+        let changes = self
+            .columns
+            .iter()
+            .zip(&columns)
+            .map(|(old, new)| {
+                old.iter()
+                    .zip(new)
+                    .enumerate()
+                    .filter(|(_, (old, new))| old != new)
+                    .map(|(row, (old, new))| (row, *new - old))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Commit to the Lagrange polynomials of the rows changed for the
+        // first time.
+        let rows = changes
+            .iter()
+            .flatten()
+            .map(|(row, _)| *row)
+            .filter(|row| !self.lagrange_commitments.contains_key(row))
+            .collect::<BTreeSet<_>>();
+        let polys = rows
+            .iter()
+            .map(|row| {
+                let mut values = vec![F::zero(); domain.size()];
+                values[*row] = F::one();
+                DensePolynomial::from_coefficients_vec(domain.ifft(&values))
+            })
+            .collect::<Vec<_>>();
+        let lagrange_commitments = PC::batch_commit(commit_key, &polys)
+            .map_err(to_pc_error::<F, PC>)?;
+        self.lagrange_commitments
+            .extend(rows.into_iter().zip(lagrange_commitments));
+
+        for (commitment, changes) in self.commitments.iter_mut().zip(changes) {
+            if changes.is_empty() {
+                continue;
+            }
+            let (bases, scalars): (Vec<_>, Vec<_>) =
+                core::iter::once((commitment.clone(), F::one()))
+                    .chain(changes.into_iter().map(|(row, delta)| {
+                        (self.lagrange_commitments[&row].clone(), delta)
+                    }))
+                    .unzip();
+            *commitment = PC::multi_scalar_mul(&bases, &scalars);
+        }
+        self.columns = columns;
+        Ok(())
+    }
+
+    /// Returns the [`VerifierKey`] of the circuit as of the last update.
+    ///
+    /// The key is the same as the one computed by
+    /// [`Verifier::preprocess`](super::Verifier::preprocess) for the same
+    /// circuit.
+    pub fn verifier_key(&self) -> Result<VerifierKey<F, PC>, Error> {
+        let commitment = |i: usize| self.commitments[i].clone();
+        // Only the selectors of the gates used by the circuit are committed
+        // to.
+        let sparse_commitment = |i: usize| {
+            (!self.columns[i].iter().all(Zero::is_zero)).then(|| commitment(i))
+        };
+        VerifierKey::from_polynomial_commitments(
+            self.domain.size(),
+            commitment(0),
+            commitment(1),
+            commitment(2),
+            commitment(3),
+            commitment(4),
+            commitment(5),
+            commitment(6),
+            sparse_commitment(7),
+            sparse_commitment(8),
+            sparse_commitment(9),
+            sparse_commitment(10),
+            commitment(11),
+            commitment(12),
+            commitment(13),
+            commitment(14),
+        )
+    }
+}
+
+/// Returns the values over `domain` of the columns of the circuit of
+/// `composer` committed to in the [`VerifierKey`], in the order of
+/// [`VerifierKey::from_polynomial_commitments`], the rows past the end of
+/// the circuit being padded like in preprocessing.
+fn columns<F, P>(
+    composer: &StandardComposer<F, P>,
+    domain: &GeneralEvaluationDomain<F>,
+) -> Result<Vec<Vec<F>>, Error>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    composer.check_poly_same_len()?;
+    let n = domain.size();
+    let sigmas = composer.perm.compute_sigma_evaluations(n, domain);
+    let columns = [
+        &composer.q_m,
+        &composer.q_l,
+        &composer.q_r,
+        &composer.q_o,
+        &composer.q_4,
+        &composer.q_c,
+        &composer.q_arith,
+        &composer.q_range,
+        &composer.q_logic,
+        &composer.q_fixed_group_add,
+        &composer.q_variable_group_add,
+    ]
+    .into_iter()
+    .map(|selector| {
+        let mut values = selector.clone();
+        values.resize(n, F::zero());
+        values
+    })
+    .chain(sigmas)
+    .collect::<Vec<_>>();
+    debug_assert_eq!(columns.len(), NUM_COLUMNS);
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{batch_test, proof_system::Verifier};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use rand::rngs::OsRng;
+
+    /// Adds the gates of the `step`-th step of a circuit built step by step.
+    fn step_gadget<F, P>(composer: &mut StandardComposer<F, P>, step: u64)
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let x = composer.add_input(F::from(step % 2));
+        composer.boolean_gate(x);
+        let zero = composer.zero_var();
+        composer.arithmetic_gate(|gate| {
+            gate.witness(x, zero, None).add(F::one(), F::one())
+        });
+        if step == 3 {
+            // Turns the range selector on.
+            let y = composer.add_input(F::from(200u64));
+            composer.range_gate(y, 8);
+        }
+    }
+
+    fn test_incremental_verifier_key<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut composer = StandardComposer::<F, P>::new();
+        step_gadget(&mut composer, 0);
+        let mut key =
+            IncrementalVerifierKey::<F, PC>::new(&composer, &ck).unwrap();
+
+        let mut domain_sizes = BTreeSet::new();
+        for steps in 1..8 {
+            step_gadget(&mut composer, steps);
+            key.update(&composer, &ck).unwrap();
+            domain_sizes.insert(key.domain.size());
+
+            let mut verifier = Verifier::<F, P, PC>::new(b"incremental");
+            for step in 0..=steps {
+                step_gadget(verifier.mut_cs(), step);
+            }
+            verifier.preprocess(&ck).unwrap();
+            let verifier_key = key.verifier_key().unwrap();
+            assert!(verifier.verifier_key == Some(verifier_key));
+        }
+        // The circuit outgrew its domain along the way.
+        assert!(domain_sizes.len() > 1);
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [test_incremental_verifier_key],
+        [] => (
+            Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [test_incremental_verifier_key],
+        [] => (
+            Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -12,12 +12,13 @@ use crate::{
         logic::{Logic, LogicVals},
         range::{Range, RangeVals},
         widget::GateConstraint,
-        CustomValues, ProverKey, WitnessValues,
+        CustomValues, GateType, ProverKey, WitnessValues,
     },
+    transcript::TranscriptProtocol,
     util::EvaluationDomainExt,
 };
 use ark_ec::TEModelParameters;
-use ark_ff::{Field, PrimeField};
+use ark_ff::{Field, PrimeField, Zero};
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
     Polynomial,
@@ -25,6 +26,7 @@ use ark_poly::{
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write,
 };
+use merlin::Transcript;
 
 /// Subset of the [`ProofEvaluations`]. Evaluations at `z` of the
 /// wire polynomials
@@ -103,6 +105,157 @@ where
             let _ = &self.vals.push((label.to_string(), eval));
         }
     }
+
+    /// Returns the evaluations sorted by label, independently of the order in
+    /// which they were added.
+    pub fn sorted(&self) -> Vec<(&str, F)> {
+        let mut vals = self
+            .vals
+            .iter()
+            .map(|(label, eval)| (label.as_str(), *eval))
+            .collect::<Vec<_>>();
+        vals.sort_by(|left, right| left.0.cmp(right.0));
+        vals
+    }
+
+    /// Appends the evaluations to `transcript`, each one under its own label.
+    ///
+    /// The evaluations are appended in the order of
+    /// [`CustomEvaluations::sorted`], so that the prover and the verifier
+    /// derive the same challenges however they built the evaluations.
+    pub(crate) fn append_to_transcript(&self, transcript: &mut Transcript) {
+        self.sorted().into_iter().for_each(|(label, eval)| {
+            let static_label = Box::leak(label.to_owned().into_boxed_str());
+            transcript.append(static_label.as_bytes(), &eval);
+        });
+    }
+}
+
+/// Labels of the custom gate evaluations carried by every
+/// [`ProofEvaluations`]: the arithmetic selector, and the shifted wires
+/// checked by the opening at `z * omega`.
+const BASE_CUSTOM_EVALUATION_LABELS: [&str; 4] =
+    ["q_arith_eval", "a_next_eval", "b_next_eval", "d_next_eval"];
+
+/// Labels of the wire evaluations carried by every [`ProofEvaluations`].
+const WIRE_EVALUATION_LABELS: [&str; 4] =
+    ["a_eval", "b_eval", "c_eval", "d_eval"];
+
+/// Labels of the permutation evaluations carried by every
+/// [`ProofEvaluations`].
+const PERMUTATION_EVALUATION_LABELS: [&str; 4] = [
+    "left_sig_eval",
+    "right_sig_eval",
+    "out_sig_eval",
+    "perm_eval",
+];
+
+/// Set and canonical order of the evaluations carried by the proofs of a
+/// circuit, as returned by [`VerifierKey::evaluations_schema`].
+///
+/// The compact encoding of [`EvaluationsSchema::to_compact_bytes`] does not
+/// carry the labels of the evaluations, so it can only be read back with the
+/// schema of the same circuit.
+///
+/// [`VerifierKey::evaluations_schema`]:
+/// crate::proof_system::VerifierKey::evaluations_schema
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvaluationsSchema {
+    /// Labels of the custom gate evaluations, sorted.
+    custom_labels: Vec<&'static str>,
+}
+
+impl EvaluationsSchema {
+    /// Creates the schema of evaluations with the given custom gate
+    /// evaluation labels.
+    pub(crate) fn new<I>(custom_labels: I) -> Self
+    where
+        I: IntoIterator<Item = &'static str>,
+    {
+        let mut custom_labels = custom_labels.into_iter().collect::<Vec<_>>();
+        custom_labels.sort_unstable();
+        custom_labels.dedup();
+        Self { custom_labels }
+    }
+
+    /// Creates the schema of the proofs of a circuit using the gates of
+    /// `gate_types`, holding the custom gate evaluations they read besides
+    /// the ones carried by every proof.
+    pub(crate) fn for_gate_types(gate_types: &[GateType]) -> Self {
+        let mut labels = BASE_CUSTOM_EVALUATION_LABELS.to_vec();
+        for gate_type in gate_types {
+            match gate_type {
+                GateType::Logic => labels.push("q_c_eval"),
+                GateType::FixedBaseScalarMul => {
+                    labels.extend(["q_l_eval", "q_r_eval", "q_c_eval"])
+                }
+                GateType::Arithmetic
+                | GateType::Range
+                | GateType::CurveAddition => {}
+            }
+        }
+        Self::new(labels)
+    }
+
+    /// Returns the labels of the custom gate evaluations, sorted.
+    pub fn custom_labels(&self) -> &[&'static str] {
+        &self.custom_labels
+    }
+
+    /// Returns the labels of all of the evaluations, in the order of
+    /// [`ProofEvaluations::to_flat_vec`].
+    pub fn labels(&self) -> Vec<&'static str> {
+        WIRE_EVALUATION_LABELS
+            .into_iter()
+            .chain(PERMUTATION_EVALUATION_LABELS)
+            .chain(self.custom_labels.iter().copied())
+            .collect()
+    }
+
+    /// Serializes `evaluations` without their labels, in the order of
+    /// [`EvaluationsSchema::labels`].
+    ///
+    /// Returns [`Error::EvaluationsSchemaMismatch`] if the custom gate
+    /// evaluations are not exactly the ones of the schema.
+    pub fn to_compact_bytes<F>(
+        &self,
+        evaluations: &ProofEvaluations<F>,
+    ) -> Result<Vec<u8>, Error>
+    where
+        F: Field,
+    {
+        if !evaluations.custom_labels().iter().eq(&self.custom_labels) {
+            return Err(Error::EvaluationsSchemaMismatch);
+        }
+        let mut bytes = Vec::new();
+        for eval in evaluations.to_flat_vec() {
+            eval.serialize(&mut bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Deserializes evaluations serialized with
+    /// [`EvaluationsSchema::to_compact_bytes`] against the same schema.
+    ///
+    /// Returns [`Error::EvaluationsSchemaMismatch`] if `bytes` does not hold
+    /// exactly one evaluation per label of the schema.
+    pub fn from_compact_bytes<F>(
+        &self,
+        bytes: &[u8],
+    ) -> Result<ProofEvaluations<F>, Error>
+    where
+        F: Field,
+    {
+        let count = self.labels().len();
+        if bytes.len() != count * F::zero().serialized_size() {
+            return Err(Error::EvaluationsSchemaMismatch);
+        }
+        let mut reader = bytes;
+        let values = (0..count)
+            .map(|_| F::deserialize(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        ProofEvaluations::from_flat_vec(&values, &self.custom_labels)
+    }
 }
 
 /// Set of evaluations that form the [`Proof`](super::Proof).
@@ -123,6 +276,167 @@ where
     pub custom_evals: CustomEvaluations<F>,
 }
 
+impl<F> ProofEvaluations<F>
+where
+    F: Field,
+{
+    /// Returns the evaluation of the left wire polynomial at `z`.
+    pub fn a_eval(&self) -> F {
+        self.wire_evals.a_eval
+    }
+
+    /// Returns the evaluation of the right wire polynomial at `z`.
+    pub fn b_eval(&self) -> F {
+        self.wire_evals.b_eval
+    }
+
+    /// Returns the evaluation of the output wire polynomial at `z`.
+    pub fn c_eval(&self) -> F {
+        self.wire_evals.c_eval
+    }
+
+    /// Returns the evaluation of the fourth wire polynomial at `z`.
+    pub fn d_eval(&self) -> F {
+        self.wire_evals.d_eval
+    }
+
+    /// Returns the evaluation of the left sigma polynomial at `z`.
+    pub fn left_sigma_eval(&self) -> F {
+        self.perm_evals.left_sigma_eval
+    }
+
+    /// Returns the evaluation of the right sigma polynomial at `z`.
+    pub fn right_sigma_eval(&self) -> F {
+        self.perm_evals.right_sigma_eval
+    }
+
+    /// Returns the evaluation of the out sigma polynomial at `z`.
+    pub fn out_sigma_eval(&self) -> F {
+        self.perm_evals.out_sigma_eval
+    }
+
+    /// Returns the evaluation of the permutation polynomial at `z * omega`
+    /// where `omega` is a root of unity.
+    pub fn permutation_eval(&self) -> F {
+        self.perm_evals.permutation_eval
+    }
+
+    /// Returns the custom gate evaluation stored under `label`, if any.
+    pub fn custom(&self, label: &str) -> Option<F> {
+        self.custom_evals
+            .vals
+            .iter()
+            .find(|entry| entry.0 == label)
+            .map(|entry| entry.1)
+    }
+
+    /// Checks that all of the custom gate evaluations of `schema`, read by
+    /// the verifier, are present, returning [`Error::MissingEvaluation`] with
+    /// the first missing label otherwise.
+    pub(crate) fn check_custom_evaluations(
+        &self,
+        schema: &EvaluationsSchema,
+    ) -> Result<(), Error> {
+        match schema
+            .custom_labels()
+            .iter()
+            .copied()
+            .find(|label| self.custom(label).is_none())
+        {
+            Some(label) => Err(Error::MissingEvaluation { label }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the labels of the custom gate evaluations, sorted.
+    ///
+    /// This is the schema needed by [`ProofEvaluations::from_flat_vec`] to
+    /// rebuild the evaluations out of [`ProofEvaluations::to_flat_vec`].
+    pub fn custom_labels(&self) -> Vec<String> {
+        let mut labels = self
+            .custom_evals
+            .vals
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    /// Returns all of the evaluations in their canonical order:
+    ///
+    /// 1. the wire evaluations `a`, `b`, `c` and `d`,
+    /// 2. the permutation evaluations: left, right and out sigmas, then the
+    ///    permutation polynomial,
+    /// 3. the custom gate evaluations, sorted by label.
+    ///
+    /// The order does not depend on the order in which the custom gate
+    /// evaluations were computed, so it can be used to absorb the
+    /// evaluations in a hash or in a recursive circuit.
+    pub fn to_flat_vec(&self) -> Vec<F> {
+        let custom = self.custom_evals.sorted();
+        [
+            self.a_eval(),
+            self.b_eval(),
+            self.c_eval(),
+            self.d_eval(),
+            self.left_sigma_eval(),
+            self.right_sigma_eval(),
+            self.out_sigma_eval(),
+            self.permutation_eval(),
+        ]
+        .into_iter()
+        .chain(custom.into_iter().map(|(_, eval)| eval))
+        .collect()
+    }
+
+    /// Rebuilds the evaluations out of `values`, given in the order of
+    /// [`ProofEvaluations::to_flat_vec`], and the `custom_labels` of the
+    /// custom gate evaluations, as returned by
+    /// [`ProofEvaluations::custom_labels`].
+    ///
+    /// Returns an error if `values` does not hold exactly one evaluation per
+    /// label on top of the wire and permutation evaluations, or if a label is
+    /// repeated.
+    pub fn from_flat_vec<S>(
+        values: &[F],
+        custom_labels: &[S],
+    ) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let mut labels = custom_labels
+            .iter()
+            .map(|label| label.as_ref().to_owned())
+            .collect::<Vec<_>>();
+        labels.sort();
+        let has_duplicates = labels.windows(2).any(|pair| pair[0] == pair[1]);
+        if has_duplicates || values.len() != 8 + labels.len() {
+            return Err(SerializationError::InvalidData.into());
+        }
+        Ok(Self {
+            wire_evals: WireEvaluations {
+                a_eval: values[0],
+                b_eval: values[1],
+                c_eval: values[2],
+                d_eval: values[3],
+            },
+            perm_evals: PermutationEvaluations {
+                left_sigma_eval: values[4],
+                right_sigma_eval: values[5],
+                out_sigma_eval: values[6],
+                permutation_eval: values[7],
+            },
+            custom_evals: CustomEvaluations {
+                vals: labels
+                    .into_iter()
+                    .zip(values[8..].iter().copied())
+                    .collect(),
+            },
+        })
+    }
+}
+
 /// Compute the linearisation polynomial.
 pub fn compute<F, P>(
     domain: &GeneralEvaluationDomain<F>,
@@ -150,68 +464,38 @@ where
     P: TEModelParameters<BaseField = F>,
 {
     let n = domain.size();
-    let omega = domain.group_gen();
-    let shifted_z_challenge = *z_challenge * omega;
-
-    // Wire evaluations
-    let a_eval = w_l_poly.evaluate(z_challenge);
-    let b_eval = w_r_poly.evaluate(z_challenge);
-    let c_eval = w_o_poly.evaluate(z_challenge);
-    let d_eval = w_4_poly.evaluate(z_challenge);
-    let wire_evals = WireEvaluations {
+    let evaluations = compute_evaluations(
+        domain,
+        prover_key,
+        z_challenge,
+        w_l_poly,
+        w_r_poly,
+        w_o_poly,
+        w_4_poly,
+        z_poly,
+    );
+    let WireEvaluations {
         a_eval,
         b_eval,
         c_eval,
         d_eval,
-    };
-
-    // Permutation evaluations
-    let left_sigma_eval =
-        prover_key.permutation.left_sigma.0.evaluate(z_challenge);
-    let right_sigma_eval =
-        prover_key.permutation.right_sigma.0.evaluate(z_challenge);
-    let out_sigma_eval =
-        prover_key.permutation.out_sigma.0.evaluate(z_challenge);
-    let permutation_eval = z_poly.evaluate(&shifted_z_challenge);
-    let perm_evals = PermutationEvaluations {
+    } = evaluations.wire_evals;
+    let PermutationEvaluations {
         left_sigma_eval,
         right_sigma_eval,
         out_sigma_eval,
         permutation_eval,
-    };
-
-    // Arith selector evaluation
-    let q_arith_eval = prover_key.arithmetic.q_arith.0.evaluate(z_challenge);
-
-    // Custom gate evaluations
-    let q_c_eval = prover_key.arithmetic.q_c.0.evaluate(z_challenge);
-    let q_l_eval = prover_key.arithmetic.q_l.0.evaluate(z_challenge);
-    let q_r_eval = prover_key.arithmetic.q_r.0.evaluate(z_challenge);
-
-    let a_next_eval = w_l_poly.evaluate(&shifted_z_challenge);
-    let b_next_eval = w_r_poly.evaluate(&shifted_z_challenge);
-    let d_next_eval = w_4_poly.evaluate(&shifted_z_challenge);
-
-    let custom_evals = CustomEvaluations {
-        vals: vec![
-            label_eval!(q_arith_eval),
-            label_eval!(q_c_eval),
-            label_eval!(q_l_eval),
-            label_eval!(q_r_eval),
-            label_eval!(a_next_eval),
-            label_eval!(b_next_eval),
-            label_eval!(d_next_eval),
-        ],
-    };
+    } = evaluations.perm_evals;
+    let q_arith_eval = evaluations.custom_evals.get("q_arith_eval");
 
     let gate_constraints = compute_gate_constraint_satisfiability::<F, P>(
         range_separation_challenge,
         logic_separation_challenge,
         fixed_base_separation_challenge,
         var_base_separation_challenge,
-        &wire_evals,
+        &evaluations.wire_evals,
         q_arith_eval,
-        &custom_evals,
+        &evaluations.custom_evals,
         prover_key,
     );
 
@@ -244,14 +528,86 @@ where
 
     let linearisation_polynomial =
         gate_constraints + permutation + negative_quotient_term;
-    Ok((
-        linearisation_polynomial,
-        ProofEvaluations {
-            wire_evals,
-            perm_evals,
-            custom_evals,
-        },
-    ))
+    Ok((linearisation_polynomial, evaluations))
+}
+
+/// Evaluates the wire, permutation and selector polynomials needed by the
+/// [`ProofEvaluations`] at `z_challenge`, and at `z_challenge * omega` for the
+/// shifted ones.
+pub(crate) fn compute_evaluations<F>(
+    domain: &GeneralEvaluationDomain<F>,
+    prover_key: &ProverKey<F>,
+    z_challenge: &F,
+    w_l_poly: &DensePolynomial<F>,
+    w_r_poly: &DensePolynomial<F>,
+    w_o_poly: &DensePolynomial<F>,
+    w_4_poly: &DensePolynomial<F>,
+    z_poly: &DensePolynomial<F>,
+) -> ProofEvaluations<F>
+where
+    F: PrimeField,
+{
+    let omega = domain.group_gen();
+    let shifted_z_challenge = *z_challenge * omega;
+
+    // Wire evaluations
+    let a_eval = w_l_poly.evaluate(z_challenge);
+    let b_eval = w_r_poly.evaluate(z_challenge);
+    let c_eval = w_o_poly.evaluate(z_challenge);
+    let d_eval = w_4_poly.evaluate(z_challenge);
+    let wire_evals = WireEvaluations {
+        a_eval,
+        b_eval,
+        c_eval,
+        d_eval,
+    };
+
+    // Permutation evaluations
+    let left_sigma_eval =
+        prover_key.permutation.left_sigma.0.evaluate(z_challenge);
+    let right_sigma_eval =
+        prover_key.permutation.right_sigma.0.evaluate(z_challenge);
+    let out_sigma_eval =
+        prover_key.permutation.out_sigma.0.evaluate(z_challenge);
+    let permutation_eval = z_poly.evaluate(&shifted_z_challenge);
+    let perm_evals = PermutationEvaluations {
+        left_sigma_eval,
+        right_sigma_eval,
+        out_sigma_eval,
+        permutation_eval,
+    };
+
+    // Arith selector evaluation
+    let q_arith_eval = prover_key.arithmetic.q_arith.0.evaluate(z_challenge);
+
+    // Custom gate evaluations
+    let q_c_eval = prover_key.arithmetic.q_c.0.evaluate(z_challenge);
+    let q_l_eval = prover_key.arithmetic.q_l.0.evaluate(z_challenge);
+    let q_r_eval = prover_key.arithmetic.q_r.0.evaluate(z_challenge);
+
+    let a_next_eval = w_l_poly.evaluate(&shifted_z_challenge);
+    let b_next_eval = w_r_poly.evaluate(&shifted_z_challenge);
+    let d_next_eval = w_4_poly.evaluate(&shifted_z_challenge);
+
+    // Only the evaluations read by the gates of the circuit are kept.
+    let schema = EvaluationsSchema::for_gate_types(&prover_key.gate_types());
+    let mut vals = vec![
+        label_eval!(q_arith_eval),
+        label_eval!(q_c_eval),
+        label_eval!(q_l_eval),
+        label_eval!(q_r_eval),
+        label_eval!(a_next_eval),
+        label_eval!(b_next_eval),
+        label_eval!(d_next_eval),
+    ];
+    vals.retain(|(label, _)| schema.custom_labels().contains(&label.as_str()));
+    let custom_evals = CustomEvaluations { vals };
+
+    ProofEvaluations {
+        wire_evals,
+        perm_evals,
+        custom_evals,
+    }
 }
 
 /// Computes the gate constraint satisfiability portion of the linearisation
@@ -285,33 +641,250 @@ where
         q_arith_eval,
     );
 
-    let range = Range::linearisation_term(
-        &prover_key.range_selector.0,
-        *range_separation_challenge,
-        wit_vals,
-        RangeVals::from_evaluations(custom_evals),
+    let range = prover_key.range_selector.as_ref().map_or_else(
+        DensePolynomial::zero,
+        |(selector, _)| {
+            Range::linearisation_term(
+                selector,
+                *range_separation_challenge,
+                wit_vals,
+                RangeVals::from_evaluations(custom_evals),
+            )
+        },
     );
 
-    let logic = Logic::linearisation_term(
-        &prover_key.logic_selector.0,
-        *logic_separation_challenge,
-        wit_vals,
-        LogicVals::from_evaluations(custom_evals),
+    let logic = prover_key.logic_selector.as_ref().map_or_else(
+        DensePolynomial::zero,
+        |(selector, _)| {
+            Logic::linearisation_term(
+                selector,
+                *logic_separation_challenge,
+                wit_vals,
+                LogicVals::from_evaluations(custom_evals),
+            )
+        },
     );
 
-    let fixed_base_scalar_mul = FixedBaseScalarMul::<F, P>::linearisation_term(
-        &prover_key.fixed_group_add_selector.0,
-        *fixed_base_separation_challenge,
-        wit_vals,
-        FBSMVals::from_evaluations(custom_evals),
-    );
+    let fixed_base_scalar_mul = prover_key
+        .fixed_group_add_selector
+        .as_ref()
+        .map_or_else(DensePolynomial::zero, |(selector, _)| {
+            FixedBaseScalarMul::<F, P>::linearisation_term(
+                selector,
+                *fixed_base_separation_challenge,
+                wit_vals,
+                FBSMVals::from_evaluations(custom_evals),
+            )
+        });
+
+    let curve_addition = prover_key
+        .variable_group_add_selector
+        .as_ref()
+        .map_or_else(DensePolynomial::zero, |(selector, _)| {
+            CurveAddition::<F, P>::linearisation_term(
+                selector,
+                *var_base_separation_challenge,
+                wit_vals,
+                CAVals::from_evaluations(custom_evals),
+            )
+        });
+
+    arithmetic + range + logic + fixed_base_scalar_mul + curve_addition
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_field;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use rand::rngs::OsRng;
+
+    /// Returns random evaluations with the custom gate evaluations stored
+    /// under `labels`, in that order.
+    fn random_evaluations<F>(labels: &[&str]) -> ProofEvaluations<F>
+    where
+        F: PrimeField,
+    {
+        ProofEvaluations {
+            wire_evals: WireEvaluations {
+                a_eval: F::rand(&mut OsRng),
+                b_eval: F::rand(&mut OsRng),
+                c_eval: F::rand(&mut OsRng),
+                d_eval: F::rand(&mut OsRng),
+            },
+            perm_evals: PermutationEvaluations {
+                left_sigma_eval: F::rand(&mut OsRng),
+                right_sigma_eval: F::rand(&mut OsRng),
+                out_sigma_eval: F::rand(&mut OsRng),
+                permutation_eval: F::rand(&mut OsRng),
+            },
+            custom_evals: CustomEvaluations {
+                vals: labels
+                    .iter()
+                    .map(|label| (label.to_string(), F::rand(&mut OsRng)))
+                    .collect(),
+            },
+        }
+    }
+
+    fn test_flat_vec_round_trip<F>()
+    where
+        F: PrimeField,
+    {
+        let evaluations =
+            random_evaluations::<F>(&["q_c_eval", "a_next_eval", "q_l_eval"]);
+        let flat = evaluations.to_flat_vec();
+        let labels = evaluations.custom_labels();
+        assert_eq!(labels, ["a_next_eval", "q_c_eval", "q_l_eval"]);
+
+        let rebuilt = ProofEvaluations::from_flat_vec(&flat, &labels).unwrap();
+        assert_eq!(rebuilt.to_flat_vec(), flat);
+        assert_eq!(rebuilt.a_eval(), evaluations.a_eval());
+        assert_eq!(rebuilt.permutation_eval(), evaluations.permutation_eval());
+        for label in &labels {
+            assert_eq!(rebuilt.custom(label), evaluations.custom(label));
+        }
+
+        // The schema must match the values.
+        assert!(ProofEvaluations::from_flat_vec(&flat, &labels[1..]).is_err());
+        assert!(ProofEvaluations::<F>::from_flat_vec(
+            &flat,
+            &["q_c_eval", "q_c_eval", "q_l_eval"]
+        )
+        .is_err());
+    }
+
+    fn test_custom_evaluations_transcript_order<F>()
+    where
+        F: PrimeField,
+    {
+        let evaluations =
+            random_evaluations::<F>(&["q_l_eval", "a_next_eval", "q_c_eval"]);
+        let mut shuffled = evaluations.custom_evals.clone();
+        shuffled.vals.swap(0, 2);
+        shuffled.vals.swap(1, 2);
+        assert_ne!(shuffled.vals, evaluations.custom_evals.vals);
+
+        let challenge = |custom_evals: &CustomEvaluations<F>| {
+            let mut transcript = Transcript::new(b"custom_evals");
+            custom_evals.append_to_transcript(&mut transcript);
+            transcript.challenge_scalar::<F>(b"challenge")
+        };
+        assert_eq!(challenge(&shuffled), challenge(&evaluations.custom_evals));
+
+        // The evaluations are still bound to their labels.
+        let mut relabeled = evaluations.custom_evals.clone();
+        relabeled.vals[0].0 = "q_r_eval".to_string();
+        assert_ne!(challenge(&relabeled), challenge(&evaluations.custom_evals));
+    }
 
-    let curve_addition = CurveAddition::<F, P>::linearisation_term(
-        &prover_key.variable_group_add_selector.0,
-        *var_base_separation_challenge,
-        wit_vals,
-        CAVals::from_evaluations(custom_evals),
+    fn test_flat_vec_order<F>()
+    where
+        F: PrimeField,
+    {
+        let evaluations = random_evaluations::<F>(&["q_l_eval", "a_next_eval"]);
+        let mut reordered = evaluations.clone();
+        reordered.custom_evals.vals.reverse();
+
+        let flat = evaluations.to_flat_vec();
+        assert_eq!(reordered.to_flat_vec(), flat);
+        assert_eq!(evaluations.to_flat_vec(), flat);
+        assert_eq!(
+            flat,
+            [
+                evaluations.a_eval(),
+                evaluations.b_eval(),
+                evaluations.c_eval(),
+                evaluations.d_eval(),
+                evaluations.left_sigma_eval(),
+                evaluations.right_sigma_eval(),
+                evaluations.out_sigma_eval(),
+                evaluations.permutation_eval(),
+                evaluations.custom("a_next_eval").unwrap(),
+                evaluations.custom("q_l_eval").unwrap(),
+            ]
+        );
+    }
+
+    fn test_evaluations_schema<F>()
+    where
+        F: PrimeField,
+    {
+        let schema = EvaluationsSchema::for_gate_types(&[
+            GateType::Arithmetic,
+            GateType::FixedBaseScalarMul,
+        ]);
+        assert_eq!(
+            schema.custom_labels(),
+            [
+                "a_next_eval",
+                "b_next_eval",
+                "d_next_eval",
+                "q_arith_eval",
+                "q_c_eval",
+                "q_l_eval",
+                "q_r_eval"
+            ]
+        );
+        assert_eq!(
+            EvaluationsSchema::for_gate_types(&[
+                GateType::Arithmetic,
+                GateType::Range
+            ])
+            .custom_labels(),
+            ["a_next_eval", "b_next_eval", "d_next_eval", "q_arith_eval"]
+        );
+
+        // The evaluations round trip through their compact encoding, whatever
+        // the order of their custom evaluations.
+        let evaluations = random_evaluations::<F>(schema.custom_labels());
+        let mut reordered = evaluations.clone();
+        reordered.custom_evals.vals.reverse();
+        let bytes = schema.to_compact_bytes(&evaluations).unwrap();
+        assert_eq!(bytes, schema.to_compact_bytes(&reordered).unwrap());
+        assert_eq!(
+            bytes.len(),
+            schema.labels().len() * F::zero().serialized_size()
+        );
+        let rebuilt = schema.from_compact_bytes::<F>(&bytes).unwrap();
+        assert_eq!(rebuilt.to_flat_vec(), evaluations.to_flat_vec());
+
+        // Evaluations of another schema are rejected both ways.
+        let other = EvaluationsSchema::new(["q_l_eval", "a_next_eval"]);
+        assert!(matches!(
+            other.to_compact_bytes(&evaluations),
+            Err(Error::EvaluationsSchemaMismatch)
+        ));
+        assert!(matches!(
+            other.from_compact_bytes::<F>(&bytes),
+            Err(Error::EvaluationsSchemaMismatch)
+        ));
+    }
+
+    // Tests for Bls12_381
+    batch_test_field!(
+        [
+            test_flat_vec_round_trip,
+            test_flat_vec_order,
+            test_custom_evaluations_transcript_order,
+            test_evaluations_schema
+        ],
+        [] => (
+            Bls12_381
+        )
     );
 
-    arithmetic + range + logic + fixed_base_scalar_mul + curve_addition
+    // Tests for Bls12_377
+    batch_test_field!(
+        [
+            test_flat_vec_round_trip,
+            test_flat_vec_order,
+            test_custom_evaluations_transcript_order,
+            test_evaluations_schema
+        ],
+        [] => (
+            Bls12_377
+        )
+    );
 }
@@ -12,11 +12,31 @@ mod preprocess;
 mod quotient_poly;
 mod widget;
 
+pub mod aggregate;
+pub mod dynamic;
+pub mod estimate;
+pub mod handle;
+pub mod incremental;
 pub mod proof;
 pub mod prover;
 pub mod verifier;
 
+pub use aggregate::AggregateProof;
+pub use dynamic::{CommitmentScheme, DynVerifier, TaggedCommitment};
+pub use estimate::{estimate_resources, OperationCounts, ResourceEstimate};
+pub use handle::ProverHandle;
+pub use incremental::IncrementalVerifierKey;
+pub use linearisation_poly::{
+    EvaluationsSchema, PermutationEvaluations, ProofEvaluations,
+    WireEvaluations,
+};
 pub use proof::*;
-pub use prover::Prover;
-pub use verifier::Verifier;
+#[cfg(any(test, feature = "quotient-poly"))]
+pub use prover::QuotientPoly;
+pub use prover::{
+    ProofMetadata, Prover, ProverConfig, ProvingCheckpoint, ProvingStage,
+    WITNESS_HIDING_BOUND,
+};
+pub use quotient_poly::{ArkworksFft, CosetFft, GateContributions};
+pub use verifier::{VerificationReport, Verifier};
 pub use widget::*;
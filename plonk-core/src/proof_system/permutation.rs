@@ -10,6 +10,7 @@ use crate::{
     error::Error,
     permutation::constants::{K1, K2, K3},
     proof_system::linearisation_poly::ProofEvaluations,
+    util::radix2_domain,
 };
 use ark_ff::FftField;
 use ark_poly::{
@@ -177,11 +178,7 @@ where
             (alpha, beta, gamma),
             &self.fourth_sigma.0,
         );
-        let domain = GeneralEvaluationDomain::new(n).ok_or(Error::InvalidEvalDomainSize {
-            log_size_of_group: n.trailing_zeros(),
-            adicity:
-                <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-        })?;
+        let domain = radix2_domain::<F>(n)?;
         let c = self.compute_lineariser_check_is_one(
             &domain,
             z_challenge,
@@ -11,14 +11,18 @@ use crate::{
     constraint_system::StandardComposer,
     error::{to_pc_error, Error},
     label_polynomial,
-    proof_system::{widget, ProverKey},
+    proof_system::{
+        quotient_poly::compute_first_lagrange_poly_scaled, widget, ProverKey,
+    },
+    util::radix2_domain,
 };
 use ark_ec::TEModelParameters;
-use ark_ff::{FftField, PrimeField};
+use ark_ff::{FftField, PrimeField, Zero};
 use ark_poly::{
     polynomial::univariate::DensePolynomial, EvaluationDomain, Evaluations,
     GeneralEvaluationDomain, UVPolynomial,
 };
+use ark_poly_commit::LabeledPolynomial;
 use core::marker::PhantomData;
 use merlin::Transcript;
 
@@ -37,10 +41,10 @@ where
     q_c: DensePolynomial<F>,
     q_4: DensePolynomial<F>,
     q_arith: DensePolynomial<F>,
-    q_range: DensePolynomial<F>,
-    q_logic: DensePolynomial<F>,
-    q_fixed_group_add: DensePolynomial<F>,
-    q_variable_group_add: DensePolynomial<F>,
+    q_range: Option<DensePolynomial<F>>,
+    q_logic: Option<DensePolynomial<F>>,
+    q_fixed_group_add: Option<DensePolynomial<F>>,
+    q_variable_group_add: Option<DensePolynomial<F>>,
     left_sigma: DensePolynomial<F>,
     right_sigma: DensePolynomial<F>,
     out_sigma: DensePolynomial<F>,
@@ -86,7 +90,7 @@ where
 
     /// Checks that all of the wires of the composer have the same
     /// length.
-    fn check_poly_same_len(&self) -> Result<(), Error> {
+    pub(crate) fn check_poly_same_len(&self) -> Result<(), Error> {
         let k = self.q_m.len();
 
         if self.q_o.len() == k
@@ -128,15 +132,26 @@ where
     where
         PC: HomomorphicCommitment<F>,
     {
-        let (_, selectors, domain) =
+        let (prover_key, _) =
+            self.preprocess_prover_and_verifier(commit_key, transcript, _pc)?;
+        Ok(prover_key)
+    }
+
+    /// Preprocesses the circuit like [`StandardComposer::preprocess_prover`],
+    /// also returning the verifier key computed along the way.
+    pub(crate) fn preprocess_prover_and_verifier<PC>(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        transcript: &mut Transcript,
+        _pc: PhantomData<PC>,
+    ) -> Result<(ProverKey<F>, widget::VerifierKey<F, PC>), Error>
+    where
+        PC: HomomorphicCommitment<F>,
+    {
+        let (verifier_key, selectors, domain) =
             self.preprocess_shared(commit_key, transcript, _pc)?;
 
-        let domain_4n =
-            GeneralEvaluationDomain::new(4 * domain.size()).ok_or(Error::InvalidEvalDomainSize {
-                log_size_of_group: (4 * domain.size()).trailing_zeros(),
-                adicity:
-                    <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-            })?;
+        let domain_4n = radix2_domain::<F>(4 * domain.size())?;
         let q_m_eval_4n = Evaluations::from_vec_and_domain(
             domain_4n.coset_fft(&selectors.q_m),
             domain_4n,
@@ -165,22 +180,19 @@ where
             domain_4n.coset_fft(&selectors.q_arith),
             domain_4n,
         );
-        let q_range_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_range),
-            domain_4n,
-        );
-        let q_logic_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_logic),
-            domain_4n,
-        );
-        let q_fixed_group_add_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_fixed_group_add),
-            domain_4n,
-        );
-        let q_variable_group_add_eval_4n = Evaluations::from_vec_and_domain(
-            domain_4n.coset_fft(&selectors.q_variable_group_add),
-            domain_4n,
-        );
+        // Selectors of unused gates are skipped altogether
+        let with_eval_4n = |poly: DensePolynomial<F>| {
+            let eval_4n = Evaluations::from_vec_and_domain(
+                domain_4n.coset_fft(&poly),
+                domain_4n,
+            );
+            (poly, eval_4n)
+        };
+        let q_range = selectors.q_range.map(with_eval_4n);
+        let q_logic = selectors.q_logic.map(with_eval_4n);
+        let q_fixed_group_add = selectors.q_fixed_group_add.map(with_eval_4n);
+        let q_variable_group_add =
+            selectors.q_variable_group_add.map(with_eval_4n);
 
         let left_sigma_eval_4n = Evaluations::from_vec_and_domain(
             domain_4n.coset_fft(&selectors.left_sigma),
@@ -208,7 +220,14 @@ where
         let v_h_coset_4n =
             compute_vanishing_poly_over_coset(domain_4n, domain.size() as u64);
 
-        Ok(ProverKey::from_polynomials_and_evals(
+        // Compute 4n evaluations for the first Lagrange polynomial
+        let l1_poly = compute_first_lagrange_poly_scaled(&domain, F::one());
+        let l1_coset_4n = Evaluations::from_vec_and_domain(
+            domain_4n.coset_fft(&l1_poly),
+            domain_4n,
+        );
+
+        let prover_key = ProverKey::from_polynomials_and_evals(
             domain.size(),
             (selectors.q_m, q_m_eval_4n),
             (selectors.q_l, q_l_eval_4n),
@@ -217,17 +236,19 @@ where
             (selectors.q_4, q_4_eval_4n),
             (selectors.q_c, q_c_eval_4n),
             (selectors.q_arith, q_arith_eval_4n),
-            (selectors.q_range, q_range_eval_4n),
-            (selectors.q_logic, q_logic_eval_4n),
-            (selectors.q_fixed_group_add, q_fixed_group_add_eval_4n),
-            (selectors.q_variable_group_add, q_variable_group_add_eval_4n),
+            q_range,
+            q_logic,
+            q_fixed_group_add,
+            q_variable_group_add,
             (selectors.left_sigma, left_sigma_eval_4n),
             (selectors.right_sigma, right_sigma_eval_4n),
             (selectors.out_sigma, out_sigma_eval_4n),
             (selectors.fourth_sigma, fourth_sigma_eval_4n),
             linear_eval_4n,
             v_h_coset_4n,
-        ))
+            l1_coset_4n,
+        )?;
+        Ok((prover_key, verifier_key))
     }
 
     /// The verifier only requires the commitments in order to verify a
@@ -268,11 +289,9 @@ where
     where
         PC: HomomorphicCommitment<F>,
     {
-        let domain = GeneralEvaluationDomain::new(self.circuit_size()).ok_or(Error::InvalidEvalDomainSize {
-            log_size_of_group: (self.circuit_size()).trailing_zeros(),
-            adicity:
-                <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-        })?;
+        self.check_public_inputs()?;
+
+        let domain = radix2_domain::<F>(self.circuit_size())?;
         // Check that the length of the wires is consistent.
         self.check_poly_same_len()?;
 
@@ -300,21 +319,15 @@ where
         let q_arith_poly: DensePolynomial<F> =
             DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_arith));
 
-        let q_range_poly: DensePolynomial<F> =
-            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_range));
+        let q_range_poly = sparse_selector_poly(&domain, &self.q_range);
 
-        let q_logic_poly: DensePolynomial<F> =
-            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_logic));
+        let q_logic_poly = sparse_selector_poly(&domain, &self.q_logic);
 
-        let q_fixed_group_add_poly: DensePolynomial<F> =
-            DensePolynomial::from_coefficients_vec(
-                domain.ifft(&self.q_fixed_group_add),
-            );
+        let q_fixed_group_add_poly =
+            sparse_selector_poly(&domain, &self.q_fixed_group_add);
 
-        let q_variable_group_add_poly: DensePolynomial<F> =
-            DensePolynomial::from_coefficients_vec(
-                domain.ifft(&self.q_variable_group_add),
-            );
+        let q_variable_group_add_poly =
+            sparse_selector_poly(&domain, &self.q_variable_group_add);
 
         // 2. Compute the sigma polynomials
         let (
@@ -334,10 +347,6 @@ where
                 label_polynomial!(q_4_poly),
                 label_polynomial!(q_c_poly),
                 label_polynomial!(q_arith_poly),
-                label_polynomial!(q_range_poly),
-                label_polynomial!(q_logic_poly),
-                label_polynomial!(q_fixed_group_add_poly),
-                label_polynomial!(q_variable_group_add_poly),
                 label_polynomial!(left_sigma_poly),
                 label_polynomial!(right_sigma_poly),
                 label_polynomial!(out_sigma_poly),
@@ -348,6 +357,39 @@ where
         )
         .map_err(to_pc_error::<F, PC>)?;
 
+        // Only the selectors of the gates used by the circuit are committed to
+        let sparse_selectors = [
+            ("q_range_poly", &q_range_poly),
+            ("q_logic_poly", &q_logic_poly),
+            ("q_fixed_group_add_poly", &q_fixed_group_add_poly),
+            ("q_variable_group_add_poly", &q_variable_group_add_poly),
+        ];
+        let (sparse_commitments, _) = PC::commit(
+            commit_key,
+            sparse_selectors
+                .iter()
+                .filter_map(|(label, poly)| {
+                    poly.as_ref().map(|poly| {
+                        LabeledPolynomial::new(
+                            label.to_string(),
+                            poly.clone(),
+                            None,
+                            None,
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+                .iter(),
+            None,
+        )
+        .map_err(to_pc_error::<F, PC>)?;
+        let mut sparse_commitments = sparse_commitments
+            .into_iter()
+            .map(|commitment| commitment.commitment().clone());
+        let mut sparse_commitment = |poly: &Option<DensePolynomial<F>>| {
+            poly.as_ref().and_then(|_| sparse_commitments.next())
+        };
+
         let verifier_key = widget::VerifierKey::from_polynomial_commitments(
             self.circuit_size(),
             commitments[0].commitment().clone(), // q_m_poly_commit.0,
@@ -357,15 +399,15 @@ where
             commitments[4].commitment().clone(), // q_4_poly_commit.0,
             commitments[5].commitment().clone(), // q_c_poly_commit.0,
             commitments[6].commitment().clone(), // q_arith_poly_commit.0,
-            commitments[7].commitment().clone(), // q_range_poly_commit.0,
-            commitments[8].commitment().clone(), // q_logic_poly_commit.0,
-            commitments[9].commitment().clone(), /* q_fixed_group_add_poly_commit.0, */
-            commitments[10].commitment().clone(), /* q_variable_group_add_poly_commit.0, */
-            commitments[11].commitment().clone(), // left_sigma_poly_commit.0,
-            commitments[12].commitment().clone(), // right_sigma_poly_commit.0,
-            commitments[13].commitment().clone(), // out_sigma_poly_commit.0,
-            commitments[14].commitment().clone(), /* fourth_sigma_poly_commit.0, */
-        );
+            sparse_commitment(&q_range_poly),
+            sparse_commitment(&q_logic_poly),
+            sparse_commitment(&q_fixed_group_add_poly),
+            sparse_commitment(&q_variable_group_add_poly),
+            commitments[7].commitment().clone(), // left_sigma_poly_commit.0,
+            commitments[8].commitment().clone(), // right_sigma_poly_commit.0,
+            commitments[9].commitment().clone(), // out_sigma_poly_commit.0,
+            commitments[10].commitment().clone(), /* fourth_sigma_poly_commit.0, */
+        )?;
 
         let selectors = SelectorPolynomials {
             q_m: q_m_poly,
@@ -392,6 +434,23 @@ where
     }
 }
 
+/// Interpolates the selector `values` over `domain`, returning `None` when the
+/// selector vanishes on every row, i.e. when the circuit contains no gate of
+/// the corresponding type.
+fn sparse_selector_poly<F>(
+    domain: &GeneralEvaluationDomain<F>,
+    values: &[F],
+) -> Option<DensePolynomial<F>>
+where
+    F: FftField,
+{
+    if values.iter().all(Zero::is_zero) {
+        None
+    } else {
+        Some(DensePolynomial::from_coefficients_vec(domain.ifft(values)))
+    }
+}
+
 /// Given that the domain size is `D`
 /// This function computes the `D` evaluation points for
 /// the vanishing polynomial of degree `n` over a coset
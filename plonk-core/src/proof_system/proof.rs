@@ -11,27 +11,67 @@
 //! `Proof` structure and it's methods.
 
 use crate::{
-    commitment::HomomorphicCommitment,
-    error::Error,
-    label_commitment,
+    commitment::{HomomorphicCommitment, OpeningGroup},
+    error::{to_pc_error, Error},
     proof_system::{
+        aggregate::AggregateProof,
         ecc::{CurveAddition, FixedBaseScalarMul},
-        linearisation_poly::ProofEvaluations,
+        linearisation_poly::{
+            PermutationEvaluations, ProofEvaluations, WireEvaluations,
+        },
         logic::Logic,
         range::Range,
-        GateConstraint, VerifierKey as PlonkVerifierKey,
+        GateConstraint, HashEndianness, VerifierKey as PlonkVerifierKey,
     },
     transcript::TranscriptProtocol,
-    util::EvaluationDomainExt,
+    util::{group_ops_span, phase_span, shifted_point, EvaluationDomainExt},
 };
 use ark_ec::TEModelParameters;
 
-use ark_ff::{fields::batch_inversion, FftField, PrimeField};
+use ark_ff::{fields::batch_inversion, PrimeField};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write,
 };
+use blake2::digest::{self, Digest};
 use merlin::Transcript;
+use rand::RngCore;
+
+/// Transcript labels of the commitments to the quotient polynomial pieces.
+pub(crate) const QUOTIENT_LABELS: [&[u8]; 4] = [b"t_1", b"t_2", b"t_3", b"t_4"];
+
+/// Transcript labels of the quotient challenge `alpha` and of the separation
+/// challenges of the range, logic, fixed base and variable base gates, in the
+/// order they are drawn.
+pub(crate) const QUOTIENT_CHALLENGE_LABELS: [&[u8]; 5] = [
+    b"alpha",
+    b"range separation challenge",
+    b"logic separation challenge",
+    b"fixed base separation challenge",
+    b"variable base separation challenge",
+];
+
+/// Version of the [`Proof`]s created by this crate.
+///
+/// Each version selects the verification algorithm a [`Proof`] is checked
+/// with, so that the proofs of a version keep verifying once later versions
+/// are introduced. The versions are:
+/// - 1: proofs created before the version was introduced. They are serialized
+///   without the version and their transcript differs from the one of later
+///   versions in more than the version, so they are no longer supported.
+/// - 2: the version is appended to the transcript before the witness
+///   commitments, so that a proof cannot be replayed under another version.
+pub const PROOF_VERSION: u8 = 2;
+
+/// Returns the number of commitments to the pieces of the quotient
+/// polynomial held by the [`Proof`]s of `version`, or `None` for versions
+/// this crate does not know about.
+fn quotient_piece_count(version: u8) -> Option<usize> {
+    match version {
+        2 => Some(QUOTIENT_LABELS.len()),
+        _ => None,
+    }
+}
 
 /// A Proof is a composition of `Commitment`s to the Witness, Permutation,
 /// Quotient, Shifted and Opening polynomials as well as the
@@ -43,7 +83,7 @@ use merlin::Transcript;
 /// [`Verifier`](super::Verifier) have in common succintly and without any
 /// capabilities of adquiring any kind of knowledge about the witness used to
 /// construct the Proof.
-#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derive(derivative::Derivative)]
 #[derivative(
     Clone(bound = "PC::Commitment: Clone, PC::Proof: Clone"),
     Debug(
@@ -58,6 +98,11 @@ where
     F: PrimeField,
     PC: HomomorphicCommitment<F>,
 {
+    /// Version of the verification algorithm of the proof, see
+    /// [`PROOF_VERSION`].
+    #[derivative(Default(value = "PROOF_VERSION"))]
+    pub(crate) version: u8,
+
     /// Commitment to the witness polynomial for the left wires.
     pub(crate) a_comm: PC::Commitment,
 
@@ -73,112 +118,1130 @@ where
     /// Commitment to the permutation polynomial.
     pub(crate) z_comm: PC::Commitment,
 
-    /// Commitment to the quotient polynomial.
-    pub(crate) t_1_comm: PC::Commitment,
+    /// Commitments to the pieces of the quotient polynomial, lowest degree
+    /// first.
+    ///
+    /// Their number is fixed by the version of the proof, see
+    /// [`quotient_piece_count`], so that they are serialized without a
+    /// length prefix.
+    pub(crate) t_comms: Vec<PC::Commitment>,
+
+    /// Batch opening proof of the aggregated witnesses
+    pub aw_opening: PC::Proof,
+
+    /// Batch opening proof of the shifted aggregated witnesses
+    pub saw_opening: PC::Proof,
+
+    /// Subset of all of the evaluations added to the proof.
+    pub(crate) evaluations: ProofEvaluations<F>,
+}
 
-    /// Commitment to the quotient polynomial.
-    pub(crate) t_2_comm: PC::Commitment,
+/// Commitment to the public inputs of a circuit, together with their claimed
+/// evaluation at the evaluation challenge `z` of a [`Proof`] and its opening
+/// proof.
+///
+/// It allows verifying a [`Proof`] without receiving the public inputs
+/// themselves, see [`Verifier::verify_with_committed_public_inputs`].
+///
+/// [`Verifier::verify_with_committed_public_inputs`]:
+/// super::Verifier::verify_with_committed_public_inputs
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(
+    Clone(bound = "PC::Commitment: Clone, PC::Proof: Clone"),
+    Debug(
+        bound = "PC::Commitment: std::fmt::Debug, PC::Proof: std::fmt::Debug"
+    )
+)]
+pub struct PublicInputOpening<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Commitment to the public input polynomial.
+    pub commitment: PC::Commitment,
+
+    /// Claimed evaluation of the public input polynomial at `z`.
+    pub eval: F,
+
+    /// Opening proof of the public input polynomial at `z`.
+    pub opening: PC::Proof,
+}
+
+impl<F, PC> PublicInputOpening<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Checks that the committed polynomial evaluates to `eval` at `point`.
+    fn check(
+        &self,
+        verifier_key: &PC::VerifierKey,
+        point: F,
+    ) -> Result<(), Error> {
+        let mut aggregate = AggregateProof::<F, PC>::new();
+        aggregate.add(&self.commitment, self.eval);
+        aggregate.verify(verifier_key, &point, &self.opening, F::one())
+    }
+}
+
+/// Scheme binding a [`Proof`] to the public inputs of a circuit.
+///
+/// The verifier receives a commitment to the public inputs instead of the
+/// public inputs themselves, and only needs the evaluation at the evaluation
+/// challenge `z` of the public input polynomial, interpolating the dense
+/// vector of the public inputs over the evaluation domain of the circuit.
+/// [`BarycentricPublicInputs`] is the scheme used by [`Verifier::verify`],
+/// whose commitment is the public inputs themselves. Applications can swap
+/// in their own scheme with
+/// [`Verifier::verify_with_public_input_commitment`], e.g. to receive the
+/// public inputs along with a hash or a Merkle root they must match.
+///
+/// # Soundness
+///
+/// A [`Proof`] is only bound to the public inputs whose polynomial evaluates
+/// to the contribution returned by
+/// [`verify_contribution`](Self::verify_contribution), so an implementation
+/// must only return the evaluation of public inputs it has checked against
+/// `commitment`.
+///
+/// [`Verifier::verify`]: super::Verifier::verify
+/// [`Verifier::verify_with_public_input_commitment`]:
+/// super::Verifier::verify_with_public_input_commitment
+pub trait PublicInputCommitment<F>
+where
+    F: PrimeField,
+{
+    /// Commitment to the public inputs received by the verifier.
+    type Commitment;
+
+    /// Commits to the dense vector of the public inputs `inputs`.
+    fn commit(&self, inputs: &[F]) -> Self::Commitment;
+
+    /// Returns the evaluation at `z_challenge` of the public input polynomial
+    /// over `domain` of the public inputs committed to by `commitment`.
+    fn verify_contribution(
+        &self,
+        commitment: &Self::Commitment,
+        z_challenge: F,
+        domain: &GeneralEvaluationDomain<F>,
+    ) -> Result<F, Error>;
+}
+
+/// Default [`PublicInputCommitment`], committing to the public inputs by
+/// their values and evaluating their polynomial in barycentric form.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BarycentricPublicInputs;
+
+impl<F> PublicInputCommitment<F> for BarycentricPublicInputs
+where
+    F: PrimeField,
+{
+    type Commitment = Vec<F>;
+
+    #[inline]
+    fn commit(&self, inputs: &[F]) -> Self::Commitment {
+        inputs.to_vec()
+    }
+
+    fn verify_contribution(
+        &self,
+        commitment: &Self::Commitment,
+        z_challenge: F,
+        domain: &GeneralEvaluationDomain<F>,
+    ) -> Result<F, Error> {
+        check_public_inputs_count(commitment, domain)?;
+        Ok(compute_barycentric_eval(commitment, z_challenge, domain))
+    }
+}
+
+/// Commitments to the witness and permutation polynomials of a [`Proof`].
+///
+/// They are known to the prover before the quotient polynomial and the
+/// openings are computed, and can be published ahead of the [`Proof`], e.g.
+/// to a data availability layer, see [`Prover::prove_with_commitments`]. The
+/// [`Proof`] carries the same commitments, as returned by
+/// [`Proof::witness_commitments`].
+///
+/// [`Prover::prove_with_commitments`]: super::Prover::prove_with_commitments
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(
+    Clone(bound = "PC::Commitment: Clone"),
+    Debug(bound = "PC::Commitment: std::fmt::Debug"),
+    Eq(bound = "PC::Commitment: Eq"),
+    PartialEq(bound = "PC::Commitment: PartialEq")
+)]
+pub struct WitnessCommitments<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Commitment to the witness polynomial for the left wires.
+    pub a_comm: PC::Commitment,
+
+    /// Commitment to the witness polynomial for the right wires.
+    pub b_comm: PC::Commitment,
+
+    /// Commitment to the witness polynomial for the output wires.
+    pub c_comm: PC::Commitment,
+
+    /// Commitment to the witness polynomial for the fourth wires.
+    pub d_comm: PC::Commitment,
+
+    /// Commitment to the permutation polynomial.
+    pub z_comm: PC::Commitment,
+}
+
+/// Public inputs a [`Proof`] is verified against.
+pub(crate) enum PublicInputs<'a, F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Values of the public inputs.
+    Values(&'a [F]),
+
+    /// Commitment to the public inputs, opened at the evaluation challenge.
+    Committed(&'a PublicInputOpening<F, PC>),
+
+    /// Contribution of the public inputs at the evaluation challenge, as
+    /// returned by [`PublicInputCommitment::verify_contribution`].
+    Contribution(&'a PublicInputContribution<'a, F>),
+}
+
+/// Evaluation at the evaluation challenge of the public input polynomial
+/// over the evaluation domain.
+pub(crate) type PublicInputContribution<'a, F> =
+    dyn Fn(F, &GeneralEvaluationDomain<F>) -> Result<F, Error> + 'a;
+
+/// Batch opening one of the opening proofs of a [`Proof`] must satisfy, as
+/// returned by [`Verifier::opening_claims`](super::Verifier::opening_claims).
+///
+/// The opening proof is computed with [`PolynomialCommitment::open`] at
+/// `point`, combined with `challenge`, from the polynomials committed to in
+/// `commitments`, which evaluate to `evaluations` at `point`.
+///
+/// For `aw_opening`, the polynomials are the linearisation polynomial, the
+/// first three sigma polynomials and the four witness polynomials. For
+/// `saw_opening`, they are the permutation polynomial and the witness
+/// polynomials of the left, right and fourth wires.
+///
+/// [`PolynomialCommitment::open`]: ark_poly_commit::PolynomialCommitment::open
+#[derive(derivative::Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = "PC::Commitment: std::fmt::Debug")
+)]
+pub struct OpeningClaim<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Commitments to the opened polynomials.
+    pub commitments: Vec<PC::Commitment>,
+
+    /// Point at which the polynomials are opened.
+    pub point: F,
+
+    /// Evaluations of the polynomials at `point`, in the same order as their
+    /// commitments.
+    pub evaluations: Vec<F>,
+
+    /// Challenge the polynomials are combined with in the opening proof.
+    pub challenge: F,
+}
+
+/// Batch opening proof of a [`Proof`] together with the commitments and
+/// evaluations it must be checked against.
+struct BatchOpening<'a, F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    aggregate: AggregateProof<F, PC>,
+    point: F,
+    opening: &'a PC::Proof,
+    challenge: F,
+}
+
+impl<F, PC> BatchOpening<'_, F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Checks the opening proof against the aggregated evaluations.
+    fn verify(&self, verifier_key: &PC::VerifierKey) -> Result<(), Error> {
+        self.aggregate.verify(
+            verifier_key,
+            &self.point,
+            self.opening,
+            self.challenge,
+        )
+    }
+
+    /// Returns the [`OpeningGroup`] of the opening proof.
+    fn group(&self) -> OpeningGroup<'_, F, PC> {
+        self.aggregate
+            .opening_group(self.point, self.opening, self.challenge)
+    }
+
+    /// Returns the [`OpeningClaim`] the opening proof must satisfy.
+    fn claim(&self) -> OpeningClaim<F, PC> {
+        let group = self.group();
+        OpeningClaim {
+            commitments: group
+                .commitments
+                .iter()
+                .map(|commitment| commitment.commitment().clone())
+                .collect(),
+            point: group.point,
+            evaluations: group.evaluations.to_vec(),
+            challenge: group.challenge,
+        }
+    }
+}
+
+/// Challenges derived from the commitments of a [`Proof`].
+struct Challenges<F> {
+    beta: F,
+    gamma: F,
+    alpha: F,
+    range_sep_challenge: F,
+    logic_sep_challenge: F,
+    fixed_base_sep_challenge: F,
+    var_base_sep_challenge: F,
+    z_challenge: F,
+}
+
+/// Reader of the commitments and opening proofs of a [`Proof`], see
+/// [`Proof::read_fields`].
+trait PointReader<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Reads a commitment from `reader`.
+    fn commitment<R>(
+        &mut self,
+        reader: R,
+    ) -> Result<PC::Commitment, SerializationError>
+    where
+        R: Read;
+
+    /// Reads an opening proof from `reader`.
+    fn opening<R>(
+        &mut self,
+        reader: R,
+    ) -> Result<PC::Proof, SerializationError>
+    where
+        R: Read;
+}
+
+/// Encoding of the fields of a [`Proof`], one for each of the ways
+/// [`CanonicalSerialize`] and [`CanonicalDeserialize`] have of encoding them.
+#[derive(Clone, Copy)]
+enum Encoding {
+    /// Compressed encoding, checked when read back.
+    Compressed,
+
+    /// Uncompressed encoding, checked when read back.
+    Uncompressed,
+
+    /// Uncompressed encoding, read back without any check.
+    Unchecked,
+}
+
+impl Encoding {
+    /// Writes `item` to `writer` with this encoding.
+    fn write<T, W>(self, item: &T, writer: W) -> Result<(), SerializationError>
+    where
+        T: CanonicalSerialize,
+        W: Write,
+    {
+        match self {
+            Self::Compressed => item.serialize(writer),
+            Self::Uncompressed => item.serialize_uncompressed(writer),
+            Self::Unchecked => item.serialize_unchecked(writer),
+        }
+    }
+
+    /// Returns the size of `item` with this encoding.
+    fn size<T>(self, item: &T) -> usize
+    where
+        T: CanonicalSerialize,
+    {
+        match self {
+            Self::Compressed => item.serialized_size(),
+            Self::Uncompressed | Self::Unchecked => item.uncompressed_size(),
+        }
+    }
+
+    /// Reads an item from `reader` with this encoding.
+    fn read<T, R>(self, reader: R) -> Result<T, SerializationError>
+    where
+        T: CanonicalDeserialize,
+        R: Read,
+    {
+        match self {
+            Self::Compressed => T::deserialize(reader),
+            Self::Uncompressed => T::deserialize_uncompressed(reader),
+            Self::Unchecked => T::deserialize_unchecked(reader),
+        }
+    }
+}
+
+impl<F, PC> PointReader<F, PC> for Encoding
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    fn commitment<R>(
+        &mut self,
+        reader: R,
+    ) -> Result<PC::Commitment, SerializationError>
+    where
+        R: Read,
+    {
+        self.read(reader)
+    }
+
+    fn opening<R>(&mut self, reader: R) -> Result<PC::Proof, SerializationError>
+    where
+        R: Read,
+    {
+        self.read(reader)
+    }
+}
+
+/// [`PointReader`] collecting the points it reads, whose subgroup checks are
+/// deferred to [`HomomorphicCommitment::batch_subgroup_check`].
+struct DeferredPoints<G>(Vec<G>);
+
+impl<F, PC> PointReader<F, PC> for DeferredPoints<PC::Point>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    fn commitment<R>(
+        &mut self,
+        reader: R,
+    ) -> Result<PC::Commitment, SerializationError>
+    where
+        R: Read,
+    {
+        PC::deserialize_commitment_deferred(reader, &mut self.0)
+    }
+
+    fn opening<R>(&mut self, reader: R) -> Result<PC::Proof, SerializationError>
+    where
+        R: Read,
+    {
+        PC::deserialize_proof_deferred(reader, &mut self.0)
+    }
+}
+
+impl<F, PC> Proof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Returns the version of the verification algorithm of this [`Proof`].
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the evaluations at the challenge points contained in this
+    /// [`Proof`].
+    pub fn evaluations(&self) -> &ProofEvaluations<F> {
+        &self.evaluations
+    }
+
+    /// Returns the commitments to the witness and permutation polynomials of
+    /// this [`Proof`].
+    pub fn witness_commitments(&self) -> WitnessCommitments<F, PC> {
+        WitnessCommitments {
+            a_comm: self.a_comm.clone(),
+            b_comm: self.b_comm.clone(),
+            c_comm: self.c_comm.clone(),
+            d_comm: self.d_comm.clone(),
+            z_comm: self.z_comm.clone(),
+        }
+    }
+
+    /// Serializes the [`Proof`] into its canonical byte representation.
+    ///
+    /// # Canonical Form
+    ///
+    /// The canonical bytes of a [`Proof`] are its compressed serialization,
+    /// as written by [`CanonicalSerialize::serialize`]: every point is
+    /// written in compressed affine form, which only depends on the point
+    /// itself. A [`Proof`] read from any other encoding, like the
+    /// uncompressed one of [`CanonicalDeserialize::deserialize_uncompressed`],
+    /// holds the same points and so has the same canonical bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.serialized_size());
+        self.serialize(&mut bytes).expect(
+            "Proofs hold the number of quotient commitments of their version",
+        );
+        bytes
+    }
+
+    /// Hashes the canonical serialization of the [`Proof`] with the hash
+    /// function `D`, returning the digest in the byte order `endianness`.
+    ///
+    /// The digest only depends on the points and scalars of the proof, not
+    /// on the encoding it was read from.
+    pub fn hash<D>(&self, endianness: HashEndianness) -> digest::Output<D>
+    where
+        D: Digest,
+    {
+        let mut digest = D::digest(&self.to_bytes());
+        if endianness == HashEndianness::Little {
+            digest.reverse();
+        }
+        digest
+    }
+
+    /// Deserializes a [`Proof`] from its canonical byte representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::deserialize(bytes)?)
+    }
+
+    /// Writes the [`Proof`] to `writer` as its canonical bytes, prefixed
+    /// with their length as a big-endian `u32`.
+    ///
+    /// The length prefix tells a reader where the proof ends, so that proofs
+    /// sent one after the other over a stream can be read back with
+    /// [`Proof::read_framed`].
+    pub fn write_framed<W>(&self, mut writer: W) -> Result<(), Error>
+    where
+        W: Write,
+    {
+        let bytes = self.to_bytes();
+        let len = u32::try_from(bytes.len())
+            .map_err(|_| SerializationError::InvalidData)?;
+        writer
+            .write_all(&len.to_be_bytes())
+            .map_err(SerializationError::from)?;
+        writer.write_all(&bytes).map_err(SerializationError::from)?;
+        Ok(())
+    }
+
+    /// Reads a [`Proof`] written by [`Proof::write_framed`] from `reader`,
+    /// consuming exactly its length prefix and the bytes it announces.
+    ///
+    /// Returns [`Error::NotEnoughBytes`] if the stream ends before the
+    /// announced number of bytes, and an error if these bytes hold anything
+    /// but a single [`Proof`].
+    pub fn read_framed<R>(mut reader: R) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let mut len = [0u8; 4];
+        reader
+            .read_exact(&mut len)
+            .map_err(SerializationError::from)?;
+        let len = u32::from_be_bytes(len) as usize;
+
+        // Read in chunks, so that a bogus length prefix on a short stream
+        // does not allocate the whole announced length upfront.
+        let mut bytes = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while bytes.len() < len {
+            let chunk = &mut chunk[..(len - bytes.len()).min(4096)];
+            reader.read_exact(chunk).map_err(SerializationError::from)?;
+            bytes.extend_from_slice(chunk);
+        }
+
+        let mut bytes = bytes.as_slice();
+        let proof = Self::deserialize(&mut bytes)?;
+        if !bytes.is_empty() {
+            return Err(SerializationError::InvalidData.into());
+        }
+        Ok(proof)
+    }
+
+    /// Serializes the commitments and opening proofs of the [`Proof`] apart
+    /// from its [`ProofEvaluations`], returning both byte representations in
+    /// this order.
+    ///
+    /// The verifier appends every commitment to the transcript before the
+    /// evaluations, so both parts can be sent or stored independently and
+    /// later recombined with [`Proof::combine_deserialize`]. Their
+    /// concatenation is the output of [`Proof::to_bytes`].
+    pub fn split_serialize(&self) -> (Vec<u8>, Vec<u8>) {
+        let mut commitments = self.to_bytes();
+        let evaluations = commitments
+            .split_off(commitments.len() - self.evaluations.serialized_size());
+        (commitments, evaluations)
+    }
+
+    /// Deserializes a [`Proof`] from the two parts returned by
+    /// [`Proof::split_serialize`].
+    ///
+    /// Each part must be consumed entirely, otherwise an error is returned.
+    pub fn combine_deserialize(
+        mut commitments: &[u8],
+        mut evaluations: &[u8],
+    ) -> Result<Self, Error> {
+        let proof = Self::read_fields(
+            &mut commitments,
+            &mut Encoding::Compressed,
+            |_| CanonicalDeserialize::deserialize(&mut evaluations),
+        )?;
+        if !commitments.is_empty() || !evaluations.is_empty() {
+            return Err(SerializationError::InvalidData.into());
+        }
+        Ok(proof)
+    }
+
+    /// Deserializes a [`Proof`] from an untrusted `reader`, reading at most
+    /// `max_bytes` bytes from it and accepting at most `max_custom_evals`
+    /// custom evaluations.
+    ///
+    /// The custom evaluations are read one at a time, so a proof claiming an
+    /// absurd number of them is rejected once it runs past `max_bytes`,
+    /// without allocating room for them. Returns [`Error::ProofTooLarge`] if
+    /// either limit is exceeded.
+    pub fn deserialize_with_limit<R>(
+        reader: R,
+        max_bytes: usize,
+        max_custom_evals: usize,
+    ) -> Result<Self, Error>
+    where
+        R: Read,
+    {
+        let mut reader = reader.take(max_bytes as u64);
+        let proof: Self = CanonicalDeserialize::deserialize(&mut reader)
+            .map_err(|error| match Error::from(error) {
+                Error::NotEnoughBytes if reader.limit() == 0 => {
+                    Error::ProofTooLarge
+                }
+                error => error,
+            })?;
+        if proof.evaluations.custom_evals.vals.len() > max_custom_evals {
+            return Err(Error::ProofTooLarge);
+        }
+        Ok(proof)
+    }
+
+    /// Deserializes a [`Proof`] **without** performing the subgroup checks on
+    /// its commitments and openings, from the encoding written by
+    /// [`CanonicalSerialize::serialize_unchecked`].
+    ///
+    /// # Security
+    ///
+    /// This is only sound for proofs coming from a trusted source which have
+    /// already been validated, e.g. proofs previously obtained through
+    /// [`CanonicalDeserialize::deserialize`]. **NEVER** use it on untrusted
+    /// input, since verification relies on the subgroup checks performed when
+    /// deserializing.
+    pub fn unchecked_deserialize<R>(
+        reader: R,
+    ) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        Self::deserialize_unchecked(reader)
+    }
+
+    /// Deserializes a [`Proof`] like [`CanonicalDeserialize::deserialize`],
+    /// checking that all of its commitments and openings lie in the prime
+    /// order subgroup at once with
+    /// [`HomomorphicCommitment::batch_subgroup_check`], with randomness
+    /// sampled from `rng`, instead of one point at a time.
+    ///
+    /// A proof holds at least eleven points, whose individual subgroup checks
+    /// are the bulk of the cost of deserializing it. They are replaced here
+    /// by a single multi-scalar multiplication and a single subgroup check
+    /// on the schemes enabling [`HomomorphicCommitment::BATCH_SUBGROUP_CHECK`]
+    /// and performed one by one otherwise. Returns
+    /// [`SerializationError::InvalidData`] if the check fails.
+    pub fn deserialize_batch_checked<R, T>(
+        reader: R,
+        rng: &mut T,
+    ) -> Result<Self, Error>
+    where
+        R: Read,
+        T: RngCore,
+    {
+        let mut points = DeferredPoints(Vec::new());
+        let proof = Self::read_fields(reader, &mut points, |reader| {
+            CanonicalDeserialize::deserialize(reader)
+        })?;
+        if !PC::batch_subgroup_check(&points.0, rng) {
+            return Err(SerializationError::InvalidData.into());
+        }
+        Ok(proof)
+    }
+
+    /// Reads the fields of a [`Proof`] from `reader` in the order
+    /// [`Proof::write_fields`] writes them in, its commitments and opening
+    /// proofs with `points` and its evaluations with `evaluations`.
+    ///
+    /// Every way of deserializing a [`Proof`] goes through this function, so
+    /// that the order is only spelled out here and in
+    /// [`Proof::write_fields`]. Proofs of versions this crate does not know
+    /// about cannot be read, since their number of quotient commitments is
+    /// unknown.
+    fn read_fields<R, P, V, E>(
+        mut reader: R,
+        points: &mut P,
+        evaluations: V,
+    ) -> Result<Self, E>
+    where
+        R: Read,
+        P: PointReader<F, PC>,
+        V: FnOnce(&mut R) -> Result<ProofEvaluations<F>, E>,
+        E: From<SerializationError>,
+    {
+        let version = u8::deserialize(&mut reader)?;
+        let num_t_comms = quotient_piece_count(version)
+            .ok_or(SerializationError::InvalidData)?;
+        let a_comm = points.commitment(&mut reader)?;
+        let b_comm = points.commitment(&mut reader)?;
+        let c_comm = points.commitment(&mut reader)?;
+        let d_comm = points.commitment(&mut reader)?;
+        let z_comm = points.commitment(&mut reader)?;
+        let t_comms = (0..num_t_comms)
+            .map(|_| points.commitment(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aw_opening = points.opening(&mut reader)?;
+        let saw_opening = points.opening(&mut reader)?;
+        let evaluations = evaluations(&mut reader)?;
+        Ok(Self {
+            version,
+            a_comm,
+            b_comm,
+            c_comm,
+            d_comm,
+            z_comm,
+            t_comms,
+            aw_opening,
+            saw_opening,
+            evaluations,
+        })
+    }
+
+    /// Reads a [`Proof`] written with `encoding` from `reader`.
+    fn read_encoded<R>(
+        reader: R,
+        encoding: Encoding,
+    ) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        let mut points = encoding;
+        Self::read_fields(reader, &mut points, |reader| encoding.read(reader))
+    }
+
+    /// Writes the fields of the [`Proof`] to `writer` with `encoding`.
+    ///
+    /// Returns [`SerializationError::InvalidData`] if the proof does not
+    /// hold the number of quotient commitments of its version.
+    fn write_fields<W>(
+        &self,
+        mut writer: W,
+        encoding: Encoding,
+    ) -> Result<(), SerializationError>
+    where
+        W: Write,
+    {
+        if quotient_piece_count(self.version) != Some(self.t_comms.len()) {
+            return Err(SerializationError::InvalidData);
+        }
+        encoding.write(&self.version, &mut writer)?;
+        for commitment in self.commitments() {
+            encoding.write(commitment, &mut writer)?;
+        }
+        encoding.write(&self.aw_opening, &mut writer)?;
+        encoding.write(&self.saw_opening, &mut writer)?;
+        encoding.write(&self.evaluations, &mut writer)
+    }
+
+    /// Returns the size of the fields of the [`Proof`] with `encoding`.
+    fn fields_size(&self, encoding: Encoding) -> usize {
+        encoding.size(&self.version)
+            + self
+                .commitments()
+                .map(|commitment| encoding.size(commitment))
+                .sum::<usize>()
+            + encoding.size(&self.aw_opening)
+            + encoding.size(&self.saw_opening)
+            + encoding.size(&self.evaluations)
+    }
+
+    /// Returns the commitments of the [`Proof`] in their serialization order.
+    fn commitments(&self) -> impl Iterator<Item = &PC::Commitment> {
+        [
+            &self.a_comm,
+            &self.b_comm,
+            &self.c_comm,
+            &self.d_comm,
+            &self.z_comm,
+        ]
+        .into_iter()
+        .chain(&self.t_comms)
+    }
+
+    /// Performs the verification of a [`Proof`] returning a boolean result.
+    ///
+    /// `aux_data` is appended to `transcript` after the witness commitments,
+    /// as by the prover.
+    pub(crate) fn verify<P>(
+        &self,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<(), Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        let domain = plonk_verifier_key.domain()?;
+        self.verify_with_domain::<P>(
+            &domain,
+            plonk_verifier_key,
+            transcript,
+            verifier_key,
+            public_inputs,
+            aux_data,
+        )
+    }
+
+    /// Replays the commitments of the [`Proof`] and `aux_data` in
+    /// `transcript`, returning the evaluation challenge `z` at which its
+    /// polynomials are opened.
+    pub(crate) fn evaluation_challenge(
+        &self,
+        transcript: &mut Transcript,
+        aux_data: &[u8],
+    ) -> F {
+        self.compute_challenges(transcript, aux_data).z_challenge
+    }
+
+    /// Adds the commitments of the [`Proof`] to `transcript` in the same
+    /// order as the prover, deriving the same challenges.
+    ///
+    /// `aux_data` is appended right after the witness commitments, see
+    /// [`Prover::prove_with_aux_data`](super::Prover::prove_with_aux_data).
+    fn compute_challenges(
+        &self,
+        transcript: &mut Transcript,
+        aux_data: &[u8],
+    ) -> Challenges<F> {
+        // In order for the Verifier and Prover to have the same view in the
+        // non-interactive setting Both parties must commit the same
+        // elements into the transcript Below the verifier will simulate
+        // an interaction with the prover by adding the same elements
+        // that the prover added into the transcript, hence generating the
+        // same challenges
+        //
+        // Add commitment to witness polynomials to transcript
+        transcript.append_proof_version(self.version);
+        transcript.append(b"w_l", &self.a_comm);
+        transcript.append(b"w_r", &self.b_comm);
+        transcript.append(b"w_o", &self.c_comm);
+        transcript.append(b"w_4", &self.d_comm);
+        transcript.append_aux_data(aux_data);
+
+        // Compute beta and gamma challenges
+        let beta = transcript.challenge_scalar(b"beta");
+        transcript.append(b"beta", &beta);
+        let gamma = transcript.challenge_scalar(b"gamma");
+        transcript.append(b"gamma", &gamma);
+
+        // Equal challenges only happen with negligible probability and do not
+        // affect soundness, so this is only checked in debug builds.
+        debug_assert!(beta != gamma, "challenges must be different");
+
+        // Add commitment to permutation polynomial to transcript
+        transcript.append(b"z", &self.z_comm);
+
+        // Compute quotient challenge
+        let mut quotient_challenges = [F::zero(); 5];
+        transcript.challenge_scalars(
+            &QUOTIENT_CHALLENGE_LABELS,
+            &mut quotient_challenges,
+        );
+        let [alpha, range_sep_challenge, logic_sep_challenge, fixed_base_sep_challenge, var_base_sep_challenge] =
+            quotient_challenges;
+
+        // Add commitment to quotient polynomial to transcript
+        for (label, t_comm) in QUOTIENT_LABELS.iter().zip(&self.t_comms) {
+            transcript.append(*label, t_comm);
+        }
+
+        // Compute evaluation point challenge
+        let z_challenge = transcript.challenge_scalar(b"z");
+
+        Challenges {
+            beta,
+            gamma,
+            alpha,
+            range_sep_challenge,
+            logic_sep_challenge,
+            fixed_base_sep_challenge,
+            var_base_sep_challenge,
+            z_challenge,
+        }
+    }
+
+    /// Performs the verification of a [`Proof`] over an already constructed
+    /// evaluation `domain`, which must be the one of `plonk_verifier_key`.
+    pub(crate) fn verify_with_domain<P>(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<(), Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        let openings = self.batch_openings::<P>(
+            domain,
+            plonk_verifier_key,
+            transcript,
+            verifier_key,
+            public_inputs,
+            aux_data,
+        )?;
+        phase_span!("opening_checks", domain.size());
+        self.check_openings(&openings, transcript, verifier_key)
+    }
 
-    /// Commitment to the quotient polynomial.
-    pub(crate) t_3_comm: PC::Commitment,
+    /// Runs all of the checks of the verification of the [`Proof`] but the
+    /// opening checks, returning the [`OpeningClaim`]s of its opening proofs
+    /// at `z` and at `z * omega`.
+    pub(crate) fn opening_claims<P>(
+        &self,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<[OpeningClaim<F, PC>; 2], Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        let domain = plonk_verifier_key.domain()?;
+        let openings = self.batch_openings::<P>(
+            &domain,
+            plonk_verifier_key,
+            transcript,
+            verifier_key,
+            public_inputs,
+            aux_data,
+        )?;
+        Ok([openings[0].claim(), openings[1].claim()])
+    }
 
-    /// Commitment to the quotient polynomial.
-    pub(crate) t_4_comm: PC::Commitment,
+    /// Checks the batch `openings` of the [`Proof`] at once with
+    /// [`HomomorphicCommitment::check_multi_point`].
+    fn check_openings(
+        &self,
+        openings: &[BatchOpening<'_, F, PC>],
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+    ) -> Result<(), Error> {
+        let separator = self.opening_separator(transcript);
+        check_groups(openings, separator, verifier_key)
+    }
 
-    /// Batch opening proof of the aggregated witnesses
-    pub aw_opening: PC::Proof,
+    /// Appends the opening proofs of the [`Proof`] to `transcript` and draws
+    /// the separator of the combined check of its batch openings, so that
+    /// the prover cannot pick them to cancel out.
+    fn opening_separator(&self, transcript: &mut Transcript) -> F {
+        transcript.append(b"aw_opening", &self.aw_opening);
+        transcript.append(b"saw_opening", &self.saw_opening);
+        transcript.challenge_scalar(b"separator")
+    }
 
-    /// Batch opening proof of the shifted aggregated witnesses
-    pub saw_opening: PC::Proof,
+    /// Verifies each of `proofs` like [`Proof::verify_with_domain`], against
+    /// its own public inputs and auxiliary data and from its own copy of
+    /// `transcript`, checking
+    /// the batch openings of all of them at once with a single call to
+    /// [`HomomorphicCommitment::check_multi_point`].
+    ///
+    /// The separator of the combined check is drawn from a transcript
+    /// absorbing the separators of every proof, so that it is only fixed
+    /// once all of their opening proofs are.
+    pub(crate) fn verify_batch<'a, P, I>(
+        domain: &GeneralEvaluationDomain<F>,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &Transcript,
+        verifier_key: &PC::VerifierKey,
+        proofs: I,
+    ) -> Result<(), Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+        I: IntoIterator<Item = (&'a Self, PublicInputs<'a, F, PC>, &'a [u8])>,
+        Self: 'a,
+    {
+        let mut batch_transcript = Transcript::new(b"batch_verify");
+        let mut openings = Vec::new();
+        for (proof, public_inputs, aux_data) in proofs {
+            let mut transcript = transcript.clone();
+            openings.extend(proof.batch_openings::<P>(
+                domain,
+                plonk_verifier_key,
+                &mut transcript,
+                verifier_key,
+                public_inputs,
+                aux_data,
+            )?);
+            let separator = proof.opening_separator(&mut transcript);
+            batch_transcript.append(b"separator", &separator);
+        }
+        let separator = batch_transcript.challenge_scalar(b"separator");
 
-    /// Subset of all of the evaluations added to the proof.
-    pub(crate) evaluations: ProofEvaluations<F>,
-}
+        phase_span!("opening_checks", domain.size());
+        check_groups(&openings, separator, verifier_key)
+    }
 
-impl<F, PC> Proof<F, PC>
-where
-    F: PrimeField,
-    PC: HomomorphicCommitment<F>,
-{
-    /// Performs the verification of a [`Proof`] returning a boolean result.
-    pub(crate) fn verify<P>(
+    /// Checks only one of the two batch openings of the [`Proof`], picked at
+    /// random with `rng`.
+    pub(crate) fn quick_check<P, R>(
         &self,
         plonk_verifier_key: &PlonkVerifierKey<F, PC>,
         transcript: &mut Transcript,
         verifier_key: &PC::VerifierKey,
-        pub_inputs: &[F],
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+        rng: &mut R,
     ) -> Result<(), Error>
     where
         P: TEModelParameters<BaseField = F>,
+        R: RngCore,
     {
-        let domain =
-            GeneralEvaluationDomain::<F>::new(plonk_verifier_key.n).ok_or(Error::InvalidEvalDomainSize {
-                log_size_of_group: plonk_verifier_key.n.trailing_zeros(),
-                adicity: <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-            })?;
-
-        // Subgroup checks are done when the proof is deserialised.
-
-        // In order for the Verifier and Prover to have the same view in the
-        // non-interactive setting Both parties must commit the same
-        // elements into the transcript Below the verifier will simulate
-        // an interaction with the prover by adding the same elements
-        // that the prover added into the transcript, hence generating the
-        // same challenges
-        //
-        // Add commitment to witness polynomials to transcript
-        transcript.append(b"w_l", &self.a_comm);
-        transcript.append(b"w_r", &self.b_comm);
-        transcript.append(b"w_o", &self.c_comm);
-        transcript.append(b"w_4", &self.d_comm);
+        let domain = plonk_verifier_key.domain()?;
+        let openings = self.batch_openings::<P>(
+            &domain,
+            plonk_verifier_key,
+            transcript,
+            verifier_key,
+            public_inputs,
+            aux_data,
+        )?;
+        let index = rng.next_u32() as usize % openings.len();
+        phase_span!("opening_checks", domain.size());
+        openings[index].verify(verifier_key)
+    }
 
-        // Compute beta and gamma challenges
-        let beta = transcript.challenge_scalar(b"beta");
-        transcript.append(b"beta", &beta);
-        let gamma = transcript.challenge_scalar(b"gamma");
-        transcript.append(b"gamma", &gamma);
+    /// Runs all of the checks of the verification of the [`Proof`] but the
+    /// opening checks, returning the batch openings at `z` and at `z * omega`
+    /// to be checked.
+    ///
+    /// The checks are the ones of the verification algorithm selected by the
+    /// version of the [`Proof`], and [`Error::UnsupportedProofVersion`] is
+    /// returned for versions this crate does not know about.
+    fn batch_openings<P>(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<[BatchOpening<'_, F, PC>; 2], Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        phase_span!("batch_openings", domain.size());
+        match self.version {
+            2 => self.batch_openings_v2::<P>(
+                domain,
+                plonk_verifier_key,
+                transcript,
+                verifier_key,
+                public_inputs,
+                aux_data,
+            ),
+            version => Err(Error::UnsupportedProofVersion { version }),
+        }
+    }
 
-        assert!(beta != gamma, "challenges must be different");
+    /// Version 2 of [`Proof::batch_openings`].
+    fn batch_openings_v2<P>(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        plonk_verifier_key: &PlonkVerifierKey<F, PC>,
+        transcript: &mut Transcript,
+        verifier_key: &PC::VerifierKey,
+        public_inputs: PublicInputs<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<[BatchOpening<'_, F, PC>; 2], Error>
+    where
+        P: TEModelParameters<BaseField = F>,
+    {
+        if let PublicInputs::Values(values) = public_inputs {
+            check_public_inputs_count(values, domain)?;
+        }
 
-        // Add commitment to permutation polynomial to transcript
-        transcript.append(b"z", &self.z_comm);
+        // The proof must carry one commitment per piece of the quotient
+        // polynomial.
+        let expected = plonk_verifier_key.quotient_piece_count();
+        debug_assert!(expected <= QUOTIENT_LABELS.len());
+        if self.t_comms.len() != expected {
+            return Err(Error::QuotientPieceCountMismatch {
+                expected,
+                found: self.t_comms.len(),
+            });
+        }
 
-        // Compute quotient challenge
-        let alpha = transcript.challenge_scalar(b"alpha");
-        let range_sep_challenge =
-            transcript.challenge_scalar(b"range separation challenge");
-        let logic_sep_challenge =
-            transcript.challenge_scalar(b"logic separation challenge");
-        let fixed_base_sep_challenge =
-            transcript.challenge_scalar(b"fixed base separation challenge");
-        let var_base_sep_challenge =
-            transcript.challenge_scalar(b"variable base separation challenge");
+        // The evaluations read below must all be present.
+        self.evaluations.check_custom_evaluations(
+            &plonk_verifier_key.evaluations_schema(),
+        )?;
 
-        // Add commitment to quotient polynomial to transcript
-        transcript.append(b"t_1", &self.t_1_comm);
-        transcript.append(b"t_2", &self.t_2_comm);
-        transcript.append(b"t_3", &self.t_3_comm);
-        transcript.append(b"t_4", &self.t_4_comm);
+        // Subgroup checks are done when the proof is deserialised.
 
-        // Compute evaluation point challenge
-        let z_challenge = transcript.challenge_scalar(b"z");
+        let Challenges {
+            beta,
+            gamma,
+            alpha,
+            range_sep_challenge,
+            logic_sep_challenge,
+            fixed_base_sep_challenge,
+            var_base_sep_challenge,
+            z_challenge,
+        } = self.compute_challenges(transcript, aux_data);
 
-        // Compute zero polynomial evaluated at `z_challenge`
-        let z_h_eval = domain.evaluate_vanishing_polynomial(z_challenge);
+        // Compute zero polynomial evaluated at `z_challenge`, rejecting the
+        // degenerate case where the challenge lands on the domain.
+        let z_h_eval = compute_vanishing_poly_eval(domain, z_challenge)?;
 
         // Compute first lagrange polynomial evaluated at `z_challenge`
         let l1_eval =
-            compute_first_lagrange_evaluation(&domain, &z_h_eval, &z_challenge);
+            compute_first_lagrange_evaluation(domain, &z_h_eval, &z_challenge);
+
+        // Compute the public input polynomial evaluated at `z_challenge`,
+        // checking its opening when only a commitment to the public inputs
+        // is given.
+        let pi_eval = match public_inputs {
+            PublicInputs::Values(values) => {
+                compute_barycentric_eval(values, z_challenge, domain)
+            }
+            PublicInputs::Committed(pi_opening) => {
+                pi_opening.check(verifier_key, z_challenge)?;
+                pi_opening.eval
+            }
+            PublicInputs::Contribution(contribution) => {
+                contribution(z_challenge, domain)?
+            }
+        };
 
-        let r0 = self.compute_r0(
-            &domain,
-            pub_inputs,
+        let r0 = compute_r0(
+            &self.evaluations.wire_evals,
+            &self.evaluations.perm_evals,
+            pi_eval,
             alpha,
             beta,
             gamma,
-            z_challenge,
             l1_eval,
-            self.evaluations.perm_evals.permutation_eval,
         );
 
         // Add evaluations to transcript
@@ -206,27 +1269,25 @@ where
 
         self.evaluations
             .custom_evals
-            .vals
-            .iter()
-            .for_each(|(label, eval)| {
-                let static_label = Box::leak(label.to_owned().into_boxed_str());
-                transcript.append(static_label.as_bytes(), eval);
-            });
+            .append_to_transcript(transcript);
 
         // Compute linearisation commitment
-        let lin_comm = self.compute_linearisation_commitment::<P>(
-            &domain,
-            alpha,
-            beta,
-            gamma,
-            range_sep_challenge,
-            logic_sep_challenge,
-            fixed_base_sep_challenge,
-            var_base_sep_challenge,
-            z_challenge,
-            l1_eval,
-            plonk_verifier_key,
-        );
+        let lin_comm = {
+            phase_span!("linearisation", domain.size());
+            self.compute_linearisation_commitment::<P>(
+                alpha,
+                beta,
+                gamma,
+                range_sep_challenge,
+                logic_sep_challenge,
+                fixed_base_sep_challenge,
+                var_base_sep_challenge,
+                z_challenge,
+                z_h_eval,
+                l1_eval,
+                plonk_verifier_key,
+            )
+        };
 
         // Commitment Scheme
         // Now we delegate computation to the commitment scheme by batch
@@ -248,120 +1309,63 @@ where
         // challenge `z`
         let aw_challenge: F = transcript.challenge_scalar(b"aggregate_witness");
 
-        let aw_commits = [
-            label_commitment!(lin_comm),
-            label_commitment!(plonk_verifier_key.permutation.left_sigma),
-            label_commitment!(plonk_verifier_key.permutation.right_sigma),
-            label_commitment!(plonk_verifier_key.permutation.out_sigma),
-            label_commitment!(self.a_comm),
-            label_commitment!(self.b_comm),
-            label_commitment!(self.c_comm),
-            label_commitment!(self.d_comm),
-        ];
-
-        let aw_evals = [
-            -r0,
+        let mut aw = AggregateProof::<F, PC>::new();
+        aw.add(&lin_comm, -r0);
+        aw.add(
+            &plonk_verifier_key.permutation.left_sigma,
             self.evaluations.perm_evals.left_sigma_eval,
+        );
+        aw.add(
+            &plonk_verifier_key.permutation.right_sigma,
             self.evaluations.perm_evals.right_sigma_eval,
+        );
+        aw.add(
+            &plonk_verifier_key.permutation.out_sigma,
             self.evaluations.perm_evals.out_sigma_eval,
-            self.evaluations.wire_evals.a_eval,
-            self.evaluations.wire_evals.b_eval,
-            self.evaluations.wire_evals.c_eval,
-            self.evaluations.wire_evals.d_eval,
-        ];
+        );
+        aw.add(&self.a_comm, self.evaluations.wire_evals.a_eval);
+        aw.add(&self.b_comm, self.evaluations.wire_evals.b_eval);
+        aw.add(&self.c_comm, self.evaluations.wire_evals.c_eval);
+        aw.add(&self.d_comm, self.evaluations.wire_evals.d_eval);
 
         let saw_challenge: F =
             transcript.challenge_scalar(b"aggregate_witness");
 
-        let saw_commits = [
-            label_commitment!(self.z_comm),
-            label_commitment!(self.a_comm),
-            label_commitment!(self.b_comm),
-            label_commitment!(self.d_comm),
-        ];
-
-        let saw_evals = [
-            self.evaluations.perm_evals.permutation_eval,
+        let mut saw = AggregateProof::<F, PC>::new();
+        saw.add(&self.z_comm, self.evaluations.perm_evals.permutation_eval);
+        saw.add(
+            &self.a_comm,
             self.evaluations.custom_evals.get("a_next_eval"),
+        );
+        saw.add(
+            &self.b_comm,
             self.evaluations.custom_evals.get("b_next_eval"),
+        );
+        saw.add(
+            &self.d_comm,
             self.evaluations.custom_evals.get("d_next_eval"),
-        ];
-
-        match PC::check(
-            verifier_key,
-            &aw_commits,
-            &z_challenge,
-            aw_evals,
-            &self.aw_opening,
-            aw_challenge,
-            None,
-        ) {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(Error::ProofVerificationError),
-            Err(e) => panic!("{:?}", e),
-        }
-        .and_then(|_| {
-            match PC::check(
-                verifier_key,
-                &saw_commits,
-                &(z_challenge * domain.element(1)),
-                saw_evals,
-                &self.saw_opening,
-                saw_challenge,
-                None,
-            ) {
-                Ok(true) => Ok(()),
-                Ok(false) => Err(Error::ProofVerificationError),
-                Err(e) => panic!("{:?}", e),
-            }
-        })
-    }
-
-    fn compute_r0(
-        &self,
-        domain: &GeneralEvaluationDomain<F>,
-        pub_inputs: &[F],
-        alpha: F,
-        beta: F,
-        gamma: F,
-        z_challenge: F,
-        l1_eval: F,
-        z_hat_eval: F,
-    ) -> F {
-        // Compute the public input polynomial evaluated at `z_challenge`
-        let pi_eval = compute_barycentric_eval(pub_inputs, z_challenge, domain);
-
-        let alpha_sq = alpha.square();
-
-        // a + beta * sigma_1 + gamma
-        let beta_sig1 = beta * self.evaluations.perm_evals.left_sigma_eval;
-        let b_0 = self.evaluations.wire_evals.a_eval + beta_sig1 + gamma;
-
-        // b+ beta * sigma_2 + gamma
-        let beta_sig2 = beta * self.evaluations.perm_evals.right_sigma_eval;
-        let b_1 = self.evaluations.wire_evals.b_eval + beta_sig2 + gamma;
-
-        // c+ beta * sigma_3 + gamma
-        let beta_sig3 = beta * self.evaluations.perm_evals.out_sigma_eval;
-        let b_2 = self.evaluations.wire_evals.c_eval + beta_sig3 + gamma;
-
-        // ((d + gamma) * z_hat) * alpha
-        let b_3 =
-            (self.evaluations.wire_evals.d_eval + gamma) * z_hat_eval * alpha;
-
-        let b = b_0 * b_1 * b_2 * b_3;
-
-        // l_1(z) * alpha^2
-        let c = l1_eval * alpha_sq;
+        );
 
-        // Return r_0
-        pi_eval - b - c
+        Ok([
+            BatchOpening {
+                aggregate: aw,
+                point: z_challenge,
+                opening: &self.aw_opening,
+                challenge: aw_challenge,
+            },
+            BatchOpening {
+                aggregate: saw,
+                point: shifted_point(domain, z_challenge),
+                opening: &self.saw_opening,
+                challenge: saw_challenge,
+            },
+        ])
     }
 
-    /// Computes the commitment to `[r]_1`.
+    /// Computes the commitment to `[r]_1`, given the evaluation `z_h_eval`
+    /// of the vanishing polynomial at `z_challenge`.
     fn compute_linearisation_commitment<P>(
         &self,
-        domain: &GeneralEvaluationDomain<F>,
         alpha: F,
         beta: F,
         gamma: F,
@@ -370,6 +1374,7 @@ where
         fixed_base_sep_challenge: F,
         var_base_sep_challenge: F,
         z_challenge: F,
+        z_h_eval: F,
         l1_eval: F,
         plonk_verifier_key: &PlonkVerifierKey<F, PC>,
     ) -> PC::Commitment
@@ -388,36 +1393,50 @@ where
                 &mut points,
                 &self.evaluations,
             );
-        Range::extend_linearisation_commitment::<PC>(
-            &plonk_verifier_key.range_selector_commitment,
-            range_sep_challenge,
-            &self.evaluations,
-            &mut scalars,
-            &mut points,
-        );
+        if let Some(commitment) = &plonk_verifier_key.range_selector_commitment
+        {
+            Range::extend_linearisation_commitment::<PC>(
+                commitment,
+                range_sep_challenge,
+                &self.evaluations,
+                &mut scalars,
+                &mut points,
+            );
+        }
 
-        Logic::extend_linearisation_commitment::<PC>(
-            &plonk_verifier_key.logic_selector_commitment,
-            logic_sep_challenge,
-            &self.evaluations,
-            &mut scalars,
-            &mut points,
-        );
+        if let Some(commitment) = &plonk_verifier_key.logic_selector_commitment
+        {
+            Logic::extend_linearisation_commitment::<PC>(
+                commitment,
+                logic_sep_challenge,
+                &self.evaluations,
+                &mut scalars,
+                &mut points,
+            );
+        }
 
-        FixedBaseScalarMul::<_, P>::extend_linearisation_commitment::<PC>(
-            &plonk_verifier_key.fixed_group_add_selector_commitment,
-            fixed_base_sep_challenge,
-            &self.evaluations,
-            &mut scalars,
-            &mut points,
-        );
-        CurveAddition::<_, P>::extend_linearisation_commitment::<PC>(
-            &plonk_verifier_key.variable_group_add_selector_commitment,
-            var_base_sep_challenge,
-            &self.evaluations,
-            &mut scalars,
-            &mut points,
-        );
+        if let Some(commitment) =
+            &plonk_verifier_key.fixed_group_add_selector_commitment
+        {
+            FixedBaseScalarMul::<_, P>::extend_linearisation_commitment::<PC>(
+                commitment,
+                fixed_base_sep_challenge,
+                &self.evaluations,
+                &mut scalars,
+                &mut points,
+            );
+        }
+        if let Some(commitment) =
+            &plonk_verifier_key.variable_group_add_selector_commitment
+        {
+            CurveAddition::<_, P>::extend_linearisation_commitment::<PC>(
+                commitment,
+                var_base_sep_challenge,
+                &self.evaluations,
+                &mut scalars,
+                &mut points,
+            );
+        }
         plonk_verifier_key
             .permutation
             .compute_linearisation_commitment(
@@ -430,28 +1449,235 @@ where
                 self.z_comm.clone(),
             );
 
-        // Second part
-        let vanishing_poly_eval =
-            domain.evaluate_vanishing_polynomial(z_challenge);
-        // z_challenge ^ n
-        let z_challenge_to_n = vanishing_poly_eval + F::one();
+        // Second part
+        //
+        // The quotient chain only depends on the domain size `n`. Any extra
+        // degree the quotient polynomial may have, e.g. due to blinding, is
+        // carried by `t_4`, so the verifier does not need to know how the
+        // prover blinded its polynomials.
+        //
+        // Z_H(z) = z^n - 1, so that z^n is derived from it.
+        let z_challenge_to_n = z_h_eval + F::one();
+
+        let mut t_scalar = -z_h_eval;
+        for t_comm in &self.t_comms {
+            scalars.push(t_scalar);
+            points.push(t_comm.clone());
+            t_scalar *= z_challenge_to_n;
+        }
+
+        group_ops_span!(points.len(), 0);
+        PC::multi_scalar_mul(&points, &scalars)
+    }
+}
+
+/// Computes the constant term `r_0` of the linearisation polynomial, which
+/// the verifier checks the opening of the proof against, from the
+/// evaluations of a [`Proof`] at the evaluation challenge `z`.
+///
+/// Given the evaluation `pi_eval` of the public input polynomial, the
+/// evaluation `l1_eval` of the first Lagrange polynomial and the challenges
+/// `alpha`, `beta` and `gamma`, `r_0` is `pi_eval - b - c` where:
+/// - `b` is the permutation product `b_0 * b_1 * b_2 * b_3` with `b_0 = a +
+///   beta * sigma_1 + gamma`, `b_1 = b + beta * sigma_2 + gamma`, `b_2 = c +
+///   beta * sigma_3 + gamma` and `b_3 = (d + gamma) * z_hat * alpha`, over the
+///   wire evaluations `a` to `d`, the sigma evaluations `sigma_1` to `sigma_3`
+///   and the evaluation `z_hat` of the permutation polynomial at `z * omega`,
+/// - `c` is the first Lagrange term `l1_eval * alpha^2`.
+///
+/// This is exposed so that test vectors can be checked against other
+/// implementations.
+pub fn compute_r0<F>(
+    wire_evals: &WireEvaluations<F>,
+    perm_evals: &PermutationEvaluations<F>,
+    pi_eval: F,
+    alpha: F,
+    beta: F,
+    gamma: F,
+    l1_eval: F,
+) -> F
+where
+    F: PrimeField,
+{
+    let alpha_sq = alpha.square();
+
+    // a + beta * sigma_1 + gamma
+    let beta_sig1 = beta * perm_evals.left_sigma_eval;
+    let b_0 = wire_evals.a_eval + beta_sig1 + gamma;
+
+    // b+ beta * sigma_2 + gamma
+    let beta_sig2 = beta * perm_evals.right_sigma_eval;
+    let b_1 = wire_evals.b_eval + beta_sig2 + gamma;
+
+    // c+ beta * sigma_3 + gamma
+    let beta_sig3 = beta * perm_evals.out_sigma_eval;
+    let b_2 = wire_evals.c_eval + beta_sig3 + gamma;
+
+    // ((d + gamma) * z_hat) * alpha
+    let b_3 = (wire_evals.d_eval + gamma) * perm_evals.permutation_eval * alpha;
+
+    let b = b_0 * b_1 * b_2 * b_3;
+
+    // l_1(z) * alpha^2
+    let c = l1_eval * alpha_sq;
+
+    // Return r_0
+    pi_eval - b - c
+}
+
+impl<F, PC> CanonicalSerialize for Proof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    fn serialize<W>(&self, writer: W) -> Result<(), SerializationError>
+    where
+        W: Write,
+    {
+        self.write_fields(writer, Encoding::Compressed)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.fields_size(Encoding::Compressed)
+    }
+
+    fn serialize_uncompressed<W>(
+        &self,
+        writer: W,
+    ) -> Result<(), SerializationError>
+    where
+        W: Write,
+    {
+        self.write_fields(writer, Encoding::Uncompressed)
+    }
+
+    fn serialize_unchecked<W>(
+        &self,
+        writer: W,
+    ) -> Result<(), SerializationError>
+    where
+        W: Write,
+    {
+        self.write_fields(writer, Encoding::Unchecked)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.fields_size(Encoding::Uncompressed)
+    }
+}
+
+impl<F, PC> CanonicalDeserialize for Proof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    fn deserialize<R>(reader: R) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        Self::read_encoded(reader, Encoding::Compressed)
+    }
+
+    fn deserialize_uncompressed<R>(
+        reader: R,
+    ) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        Self::read_encoded(reader, Encoding::Uncompressed)
+    }
+
+    fn deserialize_unchecked<R>(reader: R) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        Self::read_encoded(reader, Encoding::Unchecked)
+    }
+}
+
+impl<F, PC> TryFrom<&[u8]> for Proof<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
 
-        let t_1_scalar = -vanishing_poly_eval;
-        let t_2_scalar = t_1_scalar * z_challenge_to_n;
-        let t_3_scalar = t_2_scalar * z_challenge_to_n;
-        let t_4_scalar = t_3_scalar * z_challenge_to_n;
-        scalars.extend_from_slice(&[
-            t_1_scalar, t_2_scalar, t_3_scalar, t_4_scalar,
-        ]);
-        points.extend_from_slice(&[
-            self.t_1_comm.clone(),
-            self.t_2_comm.clone(),
-            self.t_3_comm.clone(),
-            self.t_4_comm.clone(),
-        ]);
+/// Reconstructs the commitment to the full quotient polynomial `t(X)`
+/// evaluated at `z_challenge` from the commitments to its pieces:
+///
+/// ```text
+/// [t] = [t_1] + z^n * [t_2] + z^2n * [t_3] + z^3n * [t_4] + ...
+/// ```
+///
+/// where `n` is the size of the evaluation domain. This is the combination of
+/// the `t_*_comm` of a [`Proof`] that the verifier folds into the
+/// linearisation commitment, scaled there by `-Z_H(z)`.
+pub fn reconstruct_quotient_commitment<F, PC>(
+    t_comms: &[PC::Commitment],
+    z_challenge: F,
+    n: usize,
+) -> PC::Commitment
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    let z_challenge_to_n = z_challenge.pow(&[n as u64]);
+    let mut scalar = F::one();
+    let scalars = t_comms
+        .iter()
+        .map(|_| {
+            let current = scalar;
+            scalar *= z_challenge_to_n;
+            current
+        })
+        .collect::<Vec<_>>();
+    PC::multi_scalar_mul(t_comms, &scalars)
+}
 
-        PC::multi_scalar_mul(&points, &scalars)
+/// Checks all of the batch `openings` at once with
+/// [`HomomorphicCommitment::check_multi_point`], combined with the powers of
+/// `separator`.
+fn check_groups<F, PC>(
+    openings: &[BatchOpening<'_, F, PC>],
+    separator: F,
+    verifier_key: &PC::VerifierKey,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    let groups = openings.iter().map(BatchOpening::group).collect::<Vec<_>>();
+    let valid = PC::check_multi_point(verifier_key, &groups, separator)
+        .map_err(to_pc_error::<F, PC>)?;
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::ProofVerificationError)
+    }
+}
+
+/// Evaluates the vanishing polynomial of `domain` at `z_challenge`.
+///
+/// Returns [`Error::ChallengeInDomain`] if `z_challenge` is an element of
+/// `domain`, since both the first lagrange evaluation and the barycentric
+/// evaluation of the public inputs would then divide by zero.
+fn compute_vanishing_poly_eval<F>(
+    domain: &GeneralEvaluationDomain<F>,
+    z_challenge: F,
+) -> Result<F, Error>
+where
+    F: PrimeField,
+{
+    let z_h_eval = domain.evaluate_vanishing_polynomial(z_challenge);
+    if z_h_eval.is_zero() {
+        return Err(Error::ChallengeInDomain);
     }
+    Ok(z_h_eval)
 }
 
 /// The first lagrange polynomial has the expression:
@@ -480,9 +1706,38 @@ where
 {
     let n_fr = F::from(domain.size() as u64);
     let denom = n_fr * (*z_challenge - F::one());
-    *z_h_eval * denom.inverse().unwrap()
+    *z_h_eval
+        * denom
+            .inverse()
+            .expect("the challenge is checked not to lie in the domain")
+}
+
+/// Checks that the dense vector of the public inputs `values` fits in
+/// `domain`.
+fn check_public_inputs_count<F>(
+    values: &[F],
+    domain: &GeneralEvaluationDomain<F>,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+{
+    // The public inputs are the evaluations of a polynomial over the
+    // domain, so there cannot be more of them than domain elements.
+    if values.len() > domain.size() {
+        return Err(Error::TooManyPublicInputs {
+            count: values.len(),
+            limit: domain.size(),
+        });
+    }
+    Ok(())
 }
 
+/// Evaluates at `point` the polynomial taking the values `evaluations` over
+/// `domain`.
+///
+/// For the Public Input polynomial, `evaluations` is the dense vector of the
+/// Public Inputs, public outputs included: public outputs are Public Inputs
+/// whose values are sent by the prover, and are evaluated like any other.
 fn compute_barycentric_eval<F>(
     evaluations: &[F],
     point: F,
@@ -491,8 +1746,6 @@ fn compute_barycentric_eval<F>(
 where
     F: PrimeField,
 {
-    let numerator =
-        domain.evaluate_vanishing_polynomial(point) * domain.size_inv();
     let range = 0..evaluations.len();
 
     let non_zero_evaluations = range
@@ -502,16 +1755,35 @@ where
         })
         .collect::<Vec<_>>();
 
+    // Circuits without public inputs have an all-zero public input
+    // polynomial, whose evaluation is zero everywhere. Skip evaluating the
+    // vanishing polynomial in that case.
+    if non_zero_evaluations.is_empty() {
+        return F::zero();
+    }
+
+    let numerator =
+        domain.evaluate_vanishing_polynomial(point) * domain.size_inv();
+
     // Only compute the denominators with non-zero evaluations
     let range = 0..non_zero_evaluations.len();
 
+    // The indices of the non-zero evaluations are increasing, so the power
+    // of the inverse generator at each index is obtained from the one at the
+    // previous index, raising the generator to the gap between them. Only
+    // the non-zero evaluations are visited, however far apart they are.
     let group_gen_inv = domain.group_gen_inv();
+    let mut power = F::one();
+    let mut power_index = 0;
     let mut denominators = range
         .clone()
         .map(|i| {
             // index of non-zero evaluation
             let index = non_zero_evaluations[i];
-            (group_gen_inv.pow(&[index as u64, 0, 0, 0]) * point) - F::one()
+            debug_assert!(power_index <= index);
+            power *= group_gen_inv.pow([(index - power_index) as u64]);
+            power_index = index;
+            (power * point) - F::one()
         })
         .collect::<Vec<_>>();
     batch_inversion(&mut denominators);
@@ -530,9 +1802,18 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::batch_test_kzg;
+    use crate::{
+        batch_test_field, batch_test_kzg,
+        constraint_system::StandardComposer,
+        proof_system::{Prover, Verifier},
+    };
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::Zero;
+    use ark_poly::Polynomial;
+    use rand::rngs::OsRng;
+    use sha3::Keccak256;
 
     fn test_serde_proof<F, P, PC>()
     where
@@ -557,16 +1838,771 @@ mod test {
         assert_eq!(proof, obtained_proof);
     }
 
+    fn test_proof_bytes<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let proof =
+            crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+                |_: &mut StandardComposer<F, P>| {},
+                200,
+            )
+            .expect("Empty circuit failed");
+
+        let bytes = proof.to_bytes();
+        assert_eq!(Proof::<F, PC>::from_bytes(&bytes).unwrap(), proof);
+        assert_eq!(Proof::<F, PC>::try_from(bytes.as_slice()).unwrap(), proof);
+
+        // Truncated bytes are reported as an error.
+        assert!(matches!(
+            Proof::<F, PC>::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::NotEnoughBytes)
+        ));
+        assert!(Proof::<F, PC>::from_bytes(&[]).is_err());
+    }
+
+    fn test_framed_proofs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let empty =
+            crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+                |_: &mut StandardComposer<F, P>| {},
+                200,
+            )
+            .expect("Empty circuit failed");
+        let add = crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+            |composer: &mut StandardComposer<F, P>| {
+                let one = composer.add_input(F::one());
+                composer.arithmetic_gate(|gate| {
+                    gate.witness(one, one, None).add(F::one(), F::one())
+                });
+            },
+            200,
+        )
+        .expect("Addition circuit failed");
+
+        let mut stream = Vec::new();
+        empty.write_framed(&mut stream).unwrap();
+        add.write_framed(&mut stream).unwrap();
+        let framed_len = 4 + empty.serialized_size();
+        assert_eq!(stream[..4], (empty.serialized_size() as u32).to_be_bytes());
+
+        let mut reader = stream.as_slice();
+        assert_eq!(Proof::<F, PC>::read_framed(&mut reader).unwrap(), empty);
+        assert_eq!(Proof::<F, PC>::read_framed(&mut reader).unwrap(), add);
+        assert!(reader.is_empty());
+
+        // A truncated frame is reported as an error.
+        assert!(matches!(
+            Proof::<F, PC>::read_framed(&stream[..framed_len - 1]),
+            Err(Error::NotEnoughBytes)
+        ));
+
+        // And so is a frame holding more than a proof.
+        let mut padded = stream[..framed_len].to_vec();
+        padded[..4].copy_from_slice(&(framed_len as u32 - 3).to_be_bytes());
+        padded.push(0);
+        assert!(Proof::<F, PC>::read_framed(padded.as_slice()).is_err());
+    }
+
+    fn test_deserialize_with_limit<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let proof =
+            crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+                |composer: &mut StandardComposer<F, P>| {
+                    let one = composer.add_input(F::one());
+                    composer.arithmetic_gate(|gate| {
+                        gate.witness(one, one, None).add(F::one(), F::one())
+                    });
+                },
+                200,
+            )
+            .expect("Addition circuit failed");
+        let bytes = proof.to_bytes();
+        let num_custom_evals = proof.evaluations.custom_evals.vals.len();
+
+        assert_eq!(
+            Proof::<F, PC>::deserialize_with_limit(
+                bytes.as_slice(),
+                bytes.len(),
+                num_custom_evals
+            )
+            .unwrap(),
+            proof
+        );
+
+        // Either limit being too tight rejects the proof.
+        assert!(matches!(
+            Proof::<F, PC>::deserialize_with_limit(
+                bytes.as_slice(),
+                bytes.len() - 1,
+                num_custom_evals
+            ),
+            Err(Error::ProofTooLarge)
+        ));
+        assert!(matches!(
+            Proof::<F, PC>::deserialize_with_limit(
+                bytes.as_slice(),
+                bytes.len(),
+                num_custom_evals - 1
+            ),
+            Err(Error::ProofTooLarge)
+        ));
+
+        // The custom evaluations come last, prefixed with their number. A
+        // proof claiming an absurd number of them is rejected once it runs
+        // past the byte limit.
+        let mut absurd = bytes.clone();
+        let offset =
+            bytes.len() - proof.evaluations.custom_evals.serialized_size();
+        absurd[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        absurd.resize(2 * bytes.len(), 0);
+        assert!(matches!(
+            Proof::<F, PC>::deserialize_with_limit(
+                absurd.as_slice(),
+                absurd.len() - 1,
+                num_custom_evals
+            ),
+            Err(Error::ProofTooLarge)
+        ));
+    }
+
+    fn test_split_serialize<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(3u64))
+            });
+        };
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"split");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let (commitments, evaluations) = proof.split_serialize();
+        assert_eq!(
+            [commitments.as_slice(), evaluations.as_slice()].concat(),
+            proof.to_bytes()
+        );
+
+        let combined =
+            Proof::<F, PC>::combine_deserialize(&commitments, &evaluations)
+                .unwrap();
+        assert_eq!(combined, proof);
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"split");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&combined, &vk, &public_inputs).is_ok());
+
+        // Parts which are truncated or too long are rejected.
+        assert!(Proof::<F, PC>::combine_deserialize(
+            &commitments[..commitments.len() - 1],
+            &evaluations
+        )
+        .is_err());
+        assert!(Proof::<F, PC>::combine_deserialize(
+            &commitments,
+            &[evaluations.as_slice(), &[0]].concat()
+        )
+        .is_err());
+    }
+
+    fn test_canonical_encoding<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(3u64))
+            });
+        };
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"canonical");
+        gadget(prover.mut_cs());
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"canonical");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        // A proof read from its uncompressed encoding has the same canonical
+        // bytes and hash, and verifies alike.
+        let mut uncompressed = Vec::new();
+        proof.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_ne!(uncompressed, proof.to_bytes());
+        let decoded =
+            Proof::<F, PC>::deserialize_uncompressed(&uncompressed[..])
+                .unwrap();
+        assert_eq!(decoded, proof);
+        assert_eq!(decoded.to_bytes(), proof.to_bytes());
+        assert_eq!(
+            decoded.hash::<Keccak256>(HashEndianness::Big),
+            proof.hash::<Keccak256>(HashEndianness::Big)
+        );
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+        assert!(verifier.verify(&decoded, &vk, &public_inputs).is_ok());
+
+        // Both proofs are rejected alike.
+        let wrong_inputs = vec![F::one(); public_inputs.len()];
+        assert!(verifier.verify(&proof, &vk, &wrong_inputs).is_err());
+        assert!(verifier.verify(&decoded, &vk, &wrong_inputs).is_err());
+    }
+
+    fn test_batch_subgroup_check<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"subgroup");
+        prover.cs.add_dummy_constraints();
+        let proof = prover.prove(&ck).unwrap();
+        let bytes = proof.to_bytes();
+        let batch_checked = Proof::<F, PC>::deserialize_batch_checked(
+            bytes.as_slice(),
+            &mut OsRng,
+        )
+        .unwrap();
+        assert_eq!(batch_checked, proof);
+
+        // A point on the curve outside of the prime order subgroup.
+        let size = PC::Point::zero().serialized_size();
+        let point = loop {
+            let mut random = vec![0u8; size];
+            OsRng.fill_bytes(&mut random);
+            if let Some(point) = PC::Point::from_random_bytes(&random) {
+                let order = point.into_projective().mul(F::characteristic());
+                if !order.is_zero() {
+                    break point;
+                }
+            }
+        };
+
+        // Replace the first commitment, right after the version, with it.
+        let mut tampered = bytes;
+        let mut encoding = Vec::new();
+        point.serialize(&mut encoding).unwrap();
+        tampered[1..1 + size].copy_from_slice(&encoding);
+        assert!(Proof::<F, PC>::deserialize_batch_checked(
+            tampered.as_slice(),
+            &mut OsRng
+        )
+        .is_err());
+        assert!(Proof::<F, PC>::from_bytes(&tampered).is_err());
+    }
+
+    fn test_proof_version<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(3u64))
+            });
+        };
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"version");
+        gadget(prover.mut_cs());
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+        assert_eq!(proof.version(), PROOF_VERSION);
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"version");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        // The version is serialized in front of the proof.
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes[0], PROOF_VERSION);
+        let read = Proof::<F, PC>::from_bytes(&bytes).unwrap();
+        assert_eq!(read.version(), PROOF_VERSION);
+        assert!(verifier.verify(&read, &vk, &public_inputs).is_ok());
+
+        // Version 1 and unknown versions are rejected, and cannot even be
+        // deserialized since their number of quotient commitments is unknown.
+        let mut v1_bytes = bytes.clone();
+        v1_bytes[0] = 1;
+        assert!(Proof::<F, PC>::from_bytes(&v1_bytes).is_err());
+        let mut v1_proof = read.clone();
+        v1_proof.version = 1;
+        assert!(matches!(
+            verifier.verify(&v1_proof, &vk, &public_inputs),
+            Err(Error::UnsupportedProofVersion { version: 1 })
+        ));
+        let mut unknown_bytes = bytes;
+        unknown_bytes[0] = PROOF_VERSION + 1;
+        assert!(Proof::<F, PC>::from_bytes(&unknown_bytes).is_err());
+        let mut unknown = read;
+        unknown.version = PROOF_VERSION + 1;
+        assert!(matches!(
+            verifier.verify(&unknown, &vk, &public_inputs),
+            Err(Error::UnsupportedProofVersion { version })
+                if version == PROOF_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_unversioned_proof() {
+        type F = <Bls12_381 as ark_ec::PairingEngine>::Fr;
+        type PC = crate::commitment::KZG10<Bls12_381>;
+
+        // Proof of an empty circuit of 200 gates serialized before the
+        // version was introduced, which is no longer supported.
+        let bytes = include_bytes!("../../fixtures/v1_proof.bin");
+        assert!(Proof::<F, PC>::from_bytes(bytes).is_err());
+        let versioned = [1].iter().chain(bytes).copied().collect::<Vec<_>>();
+        assert!(Proof::<F, PC>::from_bytes(&versioned).is_err());
+    }
+
+    fn test_evaluation_getters<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let proof =
+            crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+                |composer: &mut StandardComposer<F, P>| {
+                    composer.add_dummy_constraints()
+                },
+                200,
+            )
+            .expect("Dummy circuit failed");
+        let evaluations = proof.evaluations();
+
+        let wire_evals = &evaluations.wire_evals;
+        assert_eq!(evaluations.a_eval(), wire_evals.a_eval);
+        assert_eq!(evaluations.b_eval(), wire_evals.b_eval);
+        assert_eq!(evaluations.c_eval(), wire_evals.c_eval);
+        assert_eq!(evaluations.d_eval(), wire_evals.d_eval);
+
+        let perm_evals = &evaluations.perm_evals;
+        assert_eq!(evaluations.left_sigma_eval(), perm_evals.left_sigma_eval);
+        assert_eq!(evaluations.right_sigma_eval(), perm_evals.right_sigma_eval);
+        assert_eq!(evaluations.out_sigma_eval(), perm_evals.out_sigma_eval);
+        assert_eq!(evaluations.permutation_eval(), perm_evals.permutation_eval);
+
+        for (label, eval) in &evaluations.custom_evals.vals {
+            assert_eq!(evaluations.custom(label), Some(*eval));
+        }
+        assert!(evaluations.custom("q_arith_eval").is_some());
+        assert_eq!(evaluations.custom("not_a_label"), None);
+    }
+
+    fn test_unchecked_deserialize<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        Proof<F, PC>: std::fmt::Debug + PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"unchecked");
+        prover.mut_cs().add_dummy_constraints();
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut proof_bytes = vec![];
+        proof.serialize_unchecked(&mut proof_bytes).unwrap();
+        let obtained_proof =
+            Proof::<F, PC>::unchecked_deserialize(proof_bytes.as_slice())
+                .unwrap();
+        assert_eq!(proof, obtained_proof);
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"unchecked");
+        verifier.mut_cs().add_dummy_constraints();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier
+            .verify(&obtained_proof, &vk, &public_inputs)
+            .is_ok());
+    }
+
+    fn test_reconstruct_quotient_commitment<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: std::fmt::Debug + PartialEq,
+    {
+        let crate::constraint_system::helper::PiCircuit {
+            proof, verifier, ..
+        } = crate::constraint_system::helper::pi_circuit::<F, P, PC>(
+            b"reconstruct",
+        );
+        let t_comms = proof.t_comms.clone();
+
+        // The pieces are combined over the domain of the circuit they come
+        // from.
+        let domain = verifier.verifier_key.as_ref().unwrap().domain().unwrap();
+        let z_challenge = F::from(12345u64);
+
+        // Combination used in `compute_linearisation_commitment`.
+        let vanishing_poly_eval =
+            domain.evaluate_vanishing_polynomial(z_challenge);
+        let z_challenge_to_n = vanishing_poly_eval + F::one();
+        let t_1_scalar = -vanishing_poly_eval;
+        let t_2_scalar = t_1_scalar * z_challenge_to_n;
+        let t_3_scalar = t_2_scalar * z_challenge_to_n;
+        let t_4_scalar = t_3_scalar * z_challenge_to_n;
+        let expected = PC::multi_scalar_mul(
+            &t_comms,
+            &[t_1_scalar, t_2_scalar, t_3_scalar, t_4_scalar],
+        );
+
+        let t_comm = reconstruct_quotient_commitment::<F, PC>(
+            &t_comms,
+            z_challenge,
+            domain.size(),
+        );
+        assert_eq!(
+            PC::multi_scalar_mul(&[t_comm], &[-vanishing_poly_eval]),
+            expected
+        );
+    }
+
+    fn test_quotient_piece_count_mismatch<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"quotient_pieces");
+        prover.mut_cs().add_dummy_constraints();
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"quotient_pieces");
+        verifier.mut_cs().add_dummy_constraints();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // The quotient commitments are encoded without a length prefix.
+        let commitment_size = proof.a_comm.serialized_size();
+        assert_eq!(
+            proof.serialized_size(),
+            1 + 9 * commitment_size
+                + proof.aw_opening.serialized_size()
+                + proof.saw_opening.serialized_size()
+                + proof.evaluations.serialized_size()
+        );
+
+        // One extra quotient commitment, which cannot be serialized either.
+        let mut extended = proof.clone();
+        extended.t_comms.push(proof.t_comms[0].clone());
+        assert!(extended.serialize(&mut Vec::new()).is_err());
+        assert!(matches!(
+            verifier.verify(&extended, &vk, &public_inputs),
+            Err(Error::QuotientPieceCountMismatch {
+                expected: 4,
+                found: 5
+            })
+        ));
+
+        // One missing quotient commitment
+        let mut truncated = proof;
+        truncated.t_comms.pop();
+        assert!(matches!(
+            verifier.verify(&truncated, &vk, &public_inputs),
+            Err(Error::QuotientPieceCountMismatch {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+
+    fn test_quotient_chain<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"quotient_chain");
+        prover.mut_cs().add_dummy_constraints();
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"quotient_chain");
+        verifier.mut_cs().add_dummy_constraints();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Each quotient piece is scaled by its own power of `z^n`.
+        let mut swapped = proof;
+        swapped.t_comms.swap(0, 1);
+        assert!(verifier.verify(&swapped, &vk, &public_inputs).is_err());
+    }
+
+    fn test_no_public_inputs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let res = crate::constraint_system::helper::gadget_tester::<F, P, PC>(
+            |composer: &mut crate::constraint_system::StandardComposer<
+                F,
+                P,
+            >| {
+                let one = composer.add_input(F::one());
+                composer.arithmetic_gate(|gate| {
+                    gate.witness(one, one, None).add(F::one(), F::one())
+                });
+            },
+            200,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_barycentric_eval_all_zero<F>()
+    where
+        F: PrimeField,
+    {
+        let domain = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let point = F::from(12345u64);
+
+        assert_eq!(compute_barycentric_eval(&[], point, &domain), F::zero());
+        assert_eq!(
+            compute_barycentric_eval(&[F::zero(); 8], point, &domain),
+            F::zero()
+        );
+    }
+
+    fn test_barycentric_eval_matches_pi_poly<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let mut prover = Prover::<F, P, PC>::new(b"pi_poly");
+        let composer = prover.mut_cs();
+        for value in [3u64, 5, 7] {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(value))
+            });
+        }
+        composer.add_dummy_constraints();
+
+        let pi_poly = prover.public_inputs_poly().unwrap();
+        let pub_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let domain =
+            GeneralEvaluationDomain::<F>::new(prover.circuit_size()).unwrap();
+
+        for _ in 0..10 {
+            let point = F::rand(&mut OsRng);
+            assert_eq!(
+                compute_barycentric_eval(&pub_inputs, point, &domain),
+                pi_poly.evaluate(&point)
+            );
+        }
+    }
+
+    fn test_compute_r0<F>()
+    where
+        F: PrimeField,
+    {
+        let wire_evals = WireEvaluations {
+            a_eval: F::from(1u64),
+            b_eval: F::from(2u64),
+            c_eval: F::from(3u64),
+            d_eval: F::from(4u64),
+        };
+        let perm_evals = PermutationEvaluations {
+            left_sigma_eval: F::from(5u64),
+            right_sigma_eval: F::from(6u64),
+            out_sigma_eval: F::from(7u64),
+            permutation_eval: F::from(8u64),
+        };
+        let r0 = compute_r0(
+            &wire_evals,
+            &perm_evals,
+            F::from(100u64),
+            F::from(2u64),
+            F::from(1u64),
+            F::from(1u64),
+            F::from(3u64),
+        );
+
+        // b = (1 + 5 + 1) * (2 + 6 + 1) * (3 + 7 + 1) * (4 + 1) * 8 * 2
+        //   = 55440
+        // c = 3 * 2^2 = 12
+        // r0 = 100 - 55440 - 12
+        assert_eq!(r0, -F::from(55352u64));
+    }
+
+    fn test_barycentric_eval_incremental_powers<F>()
+    where
+        F: PrimeField,
+    {
+        let domain = GeneralEvaluationDomain::<F>::new(64).unwrap();
+        let point = F::rand(&mut OsRng);
+
+        // Sparse evaluations with gaps of various lengths, including the
+        // first and last elements of the domain.
+        let mut evaluations = vec![F::zero(); 64];
+        for index in [0, 1, 2, 5, 17, 18, 40, 63] {
+            evaluations[index] = F::rand(&mut OsRng);
+        }
+
+        // Reference evaluation recomputing every power from scratch.
+        let group_gen_inv = domain.group_gen_inv();
+        let expected = evaluations
+            .iter()
+            .enumerate()
+            .filter(|(_, evaluation)| !evaluation.is_zero())
+            .map(|(index, evaluation)| {
+                let denominator = group_gen_inv.pow(&[index as u64, 0, 0, 0])
+                    * point
+                    - F::one();
+                *evaluation * denominator.inverse().unwrap()
+            })
+            .sum::<F>()
+            * domain.evaluate_vanishing_polynomial(point)
+            * domain.size_inv();
+
+        assert_eq!(
+            compute_barycentric_eval(&evaluations, point, &domain),
+            expected
+        );
+    }
+
+    fn test_challenge_in_domain<F>()
+    where
+        F: PrimeField,
+    {
+        let domain = GeneralEvaluationDomain::<F>::new(8).unwrap();
+
+        // A challenge sitting on a root of unity must be rejected.
+        let z_challenge = domain.element(3);
+        assert!(matches!(
+            compute_vanishing_poly_eval(&domain, z_challenge),
+            Err(Error::ChallengeInDomain)
+        ));
+
+        // Any point of the coset is outside of the domain.
+        let z_challenge = F::multiplicative_generator() * domain.element(3);
+        assert!(compute_vanishing_poly_eval(&domain, z_challenge).is_ok());
+    }
+
+    batch_test_field!(
+        [
+            test_challenge_in_domain,
+            test_barycentric_eval_all_zero,
+            test_barycentric_eval_incremental_powers,
+            test_compute_r0
+        ],
+        [] => (Bls12_381)
+    );
+    batch_test_field!(
+        [
+            test_challenge_in_domain,
+            test_barycentric_eval_all_zero,
+            test_barycentric_eval_incremental_powers,
+            test_compute_r0
+        ],
+        [] => (Bls12_377)
+    );
+
     // Bls12-381 tests
     batch_test_kzg!(
-        [test_serde_proof],
+        [
+            test_serde_proof,
+            test_proof_bytes,
+            test_framed_proofs,
+            test_deserialize_with_limit,
+            test_split_serialize,
+            test_evaluation_getters,
+            test_unchecked_deserialize,
+            test_reconstruct_quotient_commitment,
+            test_no_public_inputs,
+            test_barycentric_eval_matches_pi_poly,
+            test_quotient_piece_count_mismatch,
+            test_quotient_chain,
+            test_proof_version,
+            test_canonical_encoding,
+            test_batch_subgroup_check
+        ],
         [] => (
             Bls12_381, ark_ed_on_bls12_381::EdwardsParameters
         )
     );
     // Bls12-377 tests
     batch_test_kzg!(
-        [test_serde_proof],
+        [
+            test_serde_proof,
+            test_proof_bytes,
+            test_framed_proofs,
+            test_deserialize_with_limit,
+            test_split_serialize,
+            test_evaluation_getters,
+            test_unchecked_deserialize,
+            test_reconstruct_quotient_commitment,
+            test_no_public_inputs,
+            test_barycentric_eval_matches_pi_poly,
+            test_quotient_piece_count_mismatch,
+            test_quotient_chain,
+            test_proof_version,
+            test_canonical_encoding,
+            test_batch_subgroup_check
+        ],
         [] => (
             Bls12_377, ark_ed_on_bls12_377::EdwardsParameters
         )
@@ -6,24 +6,356 @@
 
 //! Prover-side of the PLONK Proving System
 
+#[cfg(any(test, feature = "debug-polys"))]
+use crate::proof_system::linearisation_poly::ProofEvaluations;
 use crate::{
     commitment::HomomorphicCommitment,
-    constraint_system::{StandardComposer, Variable},
+    constraint_system::{StandardComposer, Variable, NUM_BLINDING_FACTORS},
     error::{to_pc_error, Error},
     label_polynomial,
     proof_system::{
-        linearisation_poly, proof::Proof, quotient_poly, ProverKey,
+        aggregate::AggregateProof,
+        linearisation_poly,
+        proof::{
+            Proof, PublicInputOpening, PublicInputs, WitnessCommitments,
+            PROOF_VERSION, QUOTIENT_CHALLENGE_LABELS, QUOTIENT_LABELS,
+        },
+        quotient_poly::{self, ArkworksFft, CosetFft},
+        widget::{GateType, VerifierKey as PlonkVerifierKey},
+        ProverKey,
     },
     transcript::TranscriptProtocol,
+    util::{key_domain, phase_span, radix2_domain, shifted_point},
 };
+use alloc::sync::Arc;
 use ark_ec::{ModelParameters, TEModelParameters};
 use ark_ff::PrimeField;
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
-    UVPolynomial,
+    Polynomial, UVPolynomial,
 };
-use core::marker::PhantomData;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PCRandomness};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write,
+};
+use core::{marker::PhantomData, time::Duration};
 use merlin::Transcript;
+use rand::{CryptoRng, RngCore};
+
+/// Hiding bound of the witness polynomial commitments made by
+/// [`Prover::prove_hiding`].
+///
+/// The witness polynomials are opened at two points, `z` and `z * omega`, so
+/// their blinding polynomials must have degree two. The commit key must be
+/// trimmed with a `supported_hiding_bound` of at least this value.
+pub const WITNESS_HIDING_BOUND: usize = 2;
+
+/// Reborrows an optional random number generator so that it can be used more
+/// than once.
+fn reborrow<'a>(
+    rng: &'a mut Option<&mut dyn RngCore>,
+) -> Option<&'a mut dyn RngCore> {
+    match rng {
+        Some(rng) => Some(&mut **rng),
+        None => None,
+    }
+}
+
+/// Labels a witness polynomial, to be committed to with `hiding_bound`.
+fn label_witness<F>(
+    label: &str,
+    poly: &DensePolynomial<F>,
+    hiding_bound: Option<usize>,
+) -> LabeledPolynomial<F, DensePolynomial<F>>
+where
+    F: PrimeField,
+{
+    LabeledPolynomial::new(label.to_owned(), poly.clone(), None, hiding_bound)
+}
+
+/// Milestones reached by the [`Prover`] while computing a [`Proof`].
+///
+/// Each stage is reported once it has been completed, in the order in which
+/// the variants are declared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProvingStage {
+    /// Witness polynomials have been computed and committed to.
+    WitnessCommitment,
+
+    /// Permutation polynomial has been computed and committed to.
+    PermutationPolynomial,
+
+    /// Quotient polynomial has been computed and committed to.
+    QuotientPolynomial,
+
+    /// Linearisation polynomial and evaluations have been computed.
+    Linearisation,
+
+    /// Opening proofs have been computed.
+    Opening,
+}
+
+impl ProvingStage {
+    /// Returns the name of the stage.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::WitnessCommitment => "witness commitment",
+            Self::PermutationPolynomial => "permutation polynomial",
+            Self::QuotientPolynomial => "quotient polynomial",
+            Self::Linearisation => "linearisation",
+            Self::Opening => "opening",
+        }
+    }
+
+    /// Returns the approximate fraction of the proving work that has been
+    /// completed once this stage has been reached.
+    ///
+    /// The quotient polynomial dominates proving time, since it is computed
+    /// over a domain four times larger than the circuit.
+    pub fn fraction(&self) -> f64 {
+        match self {
+            Self::WitnessCommitment => 0.15,
+            Self::PermutationPolynomial => 0.3,
+            Self::QuotientPolynomial => 0.75,
+            Self::Linearisation => 0.85,
+            Self::Opening => 1.0,
+        }
+    }
+}
+
+/// Checks that `commit_key` supports the degree of the polynomials committed
+/// to when proving a circuit over `domain`, returning
+/// [`Error::InsufficientSRS`] otherwise.
+///
+/// The quotient polynomial, of degree about `3n` for a domain of size `n`,
+/// is committed to in pieces of `n` coefficients, so that every polynomial
+/// committed to has degree at most `n - 1`.
+fn check_commit_key<F, PC>(
+    commit_key: &PC::CommitterKey,
+    domain: &GeneralEvaluationDomain<F>,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    let required = domain.size() - 1;
+    let available = PC::max_supported_degree(commit_key);
+    if required > available {
+        return Err(Error::InsufficientSRS {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Metadata about a [`Proof`] created by [`Prover::prove_with_metadata`].
+///
+/// The metadata is not part of the [`Proof`], it describes the circuit it
+/// was created for, so that callers can e.g. feed dashboards or cache the
+/// parameters of the circuit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofMetadata {
+    /// Size of the evaluation domain of the circuit.
+    pub domain_size: usize,
+
+    /// Number of Public Inputs of the circuit.
+    pub num_public_inputs: usize,
+
+    /// Types of gates used by the circuit, in the order of
+    /// [`VerifierKey::gate_types`](super::VerifierKey::gate_types).
+    pub gate_types: Vec<GateType>,
+
+    /// Time taken to create the [`Proof`], preprocessing included if the
+    /// circuit was not preprocessed yet.
+    pub proving_time: Duration,
+}
+
+/// Options of a [`Prover`], set with [`Prover::with_config`].
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""), Default(bound = ""))]
+pub struct ProverConfig<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Commitment scheme verifier key each [`Proof`] is checked against
+    /// before being returned, if any.
+    self_check_key: Option<PC::VerifierKey>,
+
+    /// FFT backend the quotient polynomial is computed with, if not the
+    /// default [`ArkworksFft`].
+    fft: Option<Arc<dyn CosetFft<F>>>,
+}
+
+impl<F, PC> ProverConfig<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Creates the default configuration, without self-check.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the [`Prover`] verify each [`Proof`] it creates against
+    /// `pc_verifier_key` and the public inputs of the circuit before
+    /// returning it, failing with [`Error::SelfCheckFailed`] if the proof
+    /// does not verify.
+    ///
+    /// This catches a divergence between the prover and the verifier, such
+    /// as an unsatisfied gate, at the source. It roughly adds the cost of a
+    /// verification to each proof.
+    pub fn self_check(mut self, pc_verifier_key: PC::VerifierKey) -> Self {
+        self.self_check_key = Some(pc_verifier_key);
+        self
+    }
+
+    /// Makes the [`Prover`] compute the quotient polynomial with the FFTs of
+    /// `fft` instead of the ones of [`ArkworksFft`].
+    ///
+    /// This allows plugging in a specialized FFT implementation, e.g.
+    /// running on a GPU or using SIMD instructions, without forking the
+    /// crate.
+    pub fn fft<T>(mut self, fft: T) -> Self
+    where
+        T: CosetFft<F> + 'static,
+    {
+        self.fft = Some(Arc::new(fft));
+        self
+    }
+}
+
+/// State of the [`Prover`] once the quotient polynomial of a [`Proof`] is
+/// committed to, returned by [`Prover::prove_to_checkpoint`].
+///
+/// Computing the quotient polynomial is the most expensive part of proving.
+/// A checkpoint can be serialized and the [`Proof`] completed later, or on
+/// another machine, with [`Prover::resume_from_checkpoint`], which replays
+/// the challenges from the commitments it holds.
+///
+/// # Security
+///
+/// The commitments of a checkpoint are the ones of the [`Proof`], so they
+/// reveal nothing more than the proof does. The polynomials however are the
+/// witness polynomials and the quotient polynomial in the clear, from which
+/// the witness is readily recovered: a serialized checkpoint must be stored
+/// and transferred as confidentially as the witness itself.
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = "PC::Commitment: std::fmt::Debug")
+)]
+pub struct ProvingCheckpoint<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Witness polynomial of the left wires.
+    w_l_poly: DensePolynomial<F>,
+
+    /// Witness polynomial of the right wires.
+    w_r_poly: DensePolynomial<F>,
+
+    /// Witness polynomial of the output wires.
+    w_o_poly: DensePolynomial<F>,
+
+    /// Witness polynomial of the fourth wires.
+    w_4_poly: DensePolynomial<F>,
+
+    /// Permutation polynomial.
+    z_poly: DensePolynomial<F>,
+
+    /// Pieces of the quotient polynomial, lowest degree first.
+    t_polys: Vec<DensePolynomial<F>>,
+
+    /// Commitments to the witness and permutation polynomials.
+    witness_commitments: WitnessCommitments<F, PC>,
+
+    /// Auxiliary data the proof is bound to, appended to the transcript
+    /// after the witness commitments.
+    aux_data: Vec<u8>,
+
+    /// Version of the proof, appended to the transcript before the witness
+    /// commitments.
+    version: u8,
+
+    /// Commitments to the pieces of the quotient polynomial.
+    t_comms: Vec<PC::Commitment>,
+}
+
+impl<F, PC> ProvingCheckpoint<F, PC>
+where
+    F: PrimeField,
+    PC: HomomorphicCommitment<F>,
+{
+    /// Appends the commitments of the checkpoint to `transcript` in the same
+    /// order as the [`Prover`], drawing the same challenges.
+    fn replay(&self, transcript: &mut Transcript) -> QuotientChallenges<F> {
+        let commitments = &self.witness_commitments;
+        transcript.append_proof_version(self.version);
+        transcript.append(b"w_l", &commitments.a_comm);
+        transcript.append(b"w_r", &commitments.b_comm);
+        transcript.append(b"w_o", &commitments.c_comm);
+        transcript.append(b"w_4", &commitments.d_comm);
+        transcript.append_aux_data(&self.aux_data);
+
+        let beta = transcript.challenge_scalar(b"beta");
+        transcript.append(b"beta", &beta);
+        let gamma = transcript.challenge_scalar(b"gamma");
+        transcript.append(b"gamma", &gamma);
+        transcript.append(b"z", &commitments.z_comm);
+
+        let mut quotient = [F::zero(); 5];
+        transcript.challenge_scalars(&QUOTIENT_CHALLENGE_LABELS, &mut quotient);
+        for (label, t_comm) in QUOTIENT_LABELS.iter().zip(&self.t_comms) {
+            transcript.append(*label, t_comm);
+        }
+        QuotientChallenges {
+            beta,
+            gamma,
+            quotient,
+        }
+    }
+}
+
+/// Quotient polynomial of the witness of a [`Prover`], returned by
+/// [`Prover::quotient_poly`] together with the challenges it was computed
+/// with.
+#[cfg(any(test, feature = "quotient-poly"))]
+#[derive(Clone, Debug)]
+pub struct QuotientPoly<F>
+where
+    F: PrimeField,
+{
+    /// Quotient polynomial, before it is split into pieces.
+    pub quotient: DensePolynomial<F>,
+
+    /// Permutation polynomial.
+    pub z_poly: DensePolynomial<F>,
+
+    /// Quotient challenge.
+    pub alpha: F,
+
+    /// First permutation challenge.
+    pub beta: F,
+
+    /// Second permutation challenge.
+    pub gamma: F,
+
+    /// Separation challenges of the range, logic, fixed base and variable
+    /// base gates.
+    pub separation_challenges: [F; 4],
+}
+
+/// Challenges drawn by the [`Prover`] up to the quotient polynomial.
+struct QuotientChallenges<F> {
+    beta: F,
+    gamma: F,
+    /// Quotient challenge and separation challenges, in the order of
+    /// [`QUOTIENT_CHALLENGE_LABELS`].
+    quotient: [F; 5],
+}
 
 /// Abstraction structure designed to construct a circuit and generate
 /// [`Proof`]s for it.
@@ -45,6 +377,13 @@ where
     /// This is copied each time, we make a proof.
     pub preprocessed_transcript: Transcript,
 
+    /// Options of the prover.
+    config: ProverConfig<F, PC>,
+
+    /// Verifier key of the circuit, computed along with the
+    /// [`Prover::prover_key`] and used to self-check the proofs.
+    verifier_key: Option<PlonkVerifierKey<F, PC>>,
+
     _phantom: PhantomData<PC>,
 }
 impl<F, P, PC> Prover<F, P, PC>
@@ -59,20 +398,186 @@ where
             prover_key: None,
             cs: StandardComposer::new(),
             preprocessed_transcript: Transcript::new(label),
+            config: ProverConfig::new(),
+            verifier_key: None,
             _phantom: PhantomData::<PC>,
         }
     }
 
+    /// Creates a new `Prover` instance whose transcript is bound to the
+    /// circuit identified by `circuit_id`.
+    ///
+    /// Proofs only verify when the prover and the verifier use the same
+    /// `label` and `circuit_id`, so that proofs for circuits sharing the same
+    /// structure, and therefore the same keys, cannot be used for one
+    /// another. The identifier can be, for instance, a hash of the verifier
+    /// key together with a protocol version.
+    pub fn with_circuit_id(label: &'static [u8], circuit_id: &[u8]) -> Self {
+        let mut prover = Self::new(label);
+        prover.preprocessed_transcript.append_circuit_id(circuit_id);
+        prover
+    }
+
+    /// Creates a new `Prover` instance with the options of `config`.
+    pub fn with_config(
+        label: &'static [u8],
+        config: ProverConfig<F, PC>,
+    ) -> Self {
+        let mut prover = Self::new(label);
+        prover.config = config;
+        prover
+    }
+
     /// Creates a new `Prover` object with some expected size.
     pub fn with_expected_size(label: &'static [u8], size: usize) -> Self {
         Self {
             prover_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            config: ProverConfig::new(),
+            verifier_key: None,
             _phantom: PhantomData::<PC>,
         }
     }
 
+    /// Returns the FFT backend the quotient polynomial is computed with.
+    fn fft(&self) -> &dyn CosetFft<F> {
+        self.config.fft.as_deref().unwrap_or(&ArkworksFft)
+    }
+
+    /// Interpolates the public inputs of the circuit over `domain`.
+    fn compute_pi_poly(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+    ) -> DensePolynomial<F> {
+        DensePolynomial::from_coefficients_vec(
+            domain.ifft(&self.cs.construct_dense_pi_vec()),
+        )
+    }
+
+    /// Returns the public input polynomial exactly as it is built when
+    /// proving the current circuit.
+    ///
+    /// This is a debugging aid meant to cross-check independent evaluations
+    /// of the public inputs, and must be called before [`Prover::prove`]
+    /// clears the witness. It is only built with the `debug-polys` feature.
+    #[cfg(any(test, feature = "debug-polys"))]
+    pub fn public_inputs_poly(&self) -> Result<DensePolynomial<F>, Error> {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        Ok(self.compute_pi_poly(&domain))
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], also committing
+    /// to its public inputs and opening the commitment at the evaluation
+    /// challenge of the [`Proof`], so that it can be verified with
+    /// [`Verifier::verify_with_committed_public_inputs`] without receiving
+    /// the public inputs themselves.
+    ///
+    /// The commitment is absorbed into the transcript before any challenge is
+    /// drawn, so the proof only verifies along with it. The proof is also
+    /// bound to `aux_data`, see [`Prover::prove_with_aux_data`].
+    ///
+    /// [`Verifier::verify_with_committed_public_inputs`]:
+    /// crate::proof_system::Verifier::verify_with_committed_public_inputs
+    #[allow(clippy::type_complexity)]
+    pub fn prove_with_committed_public_inputs(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        aux_data: &[u8],
+    ) -> Result<(Proof<F, PC>, PublicInputOpening<F, PC>), Error> {
+        if self.prover_key.is_none() {
+            self.preprocess(commit_key)?;
+        }
+        let prover_key = self.prover_key.as_ref().unwrap();
+        let domain = key_domain::<F>(prover_key.n)?;
+        // The witness is cleared once proven, so the public inputs are
+        // committed to beforehand.
+        let pi_poly = self.compute_pi_poly(&domain);
+        let pi_polys = [label_polynomial!(pi_poly)];
+        let (pi_commits, pi_rands) = PC::commit(commit_key, &pi_polys, None)
+            .map_err(to_pc_error::<F, PC>)?;
+        let commitment = pi_commits[0].commitment().clone();
+
+        // Bind the proof to the commitment for the duration of the proof
+        // only.
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append(b"pi_comm", &commitment);
+        let preprocessed_transcript =
+            core::mem::replace(&mut self.preprocessed_transcript, transcript);
+        let proof = self.prove_with_aux_data(commit_key, aux_data);
+        let mut transcript = core::mem::replace(
+            &mut self.preprocessed_transcript,
+            preprocessed_transcript,
+        );
+        let proof = proof?;
+
+        let z_challenge = proof.evaluation_challenge(&mut transcript, aux_data);
+        let opening = PC::open(
+            commit_key,
+            &pi_polys,
+            &pi_commits,
+            &z_challenge,
+            F::one(),
+            &pi_rands,
+            None,
+        )
+        .map_err(to_pc_error::<F, PC>)?;
+        Ok((
+            proof,
+            PublicInputOpening {
+                commitment,
+                eval: pi_poly.evaluate(&z_challenge),
+                opening,
+            },
+        ))
+    }
+
+    /// Evaluates the polynomials of the stored witness and of the
+    /// [`ProverKey`] at `point`, computing the same [`ProofEvaluations`] a
+    /// [`Proof`] carries for its evaluation challenge `z`.
+    ///
+    /// The permutation polynomial depends on the permutation challenges
+    /// `beta` and `gamma` of the proof, which must be given as well. Called
+    /// with the challenges of a [`Proof`], this reproduces its evaluations and
+    /// can be used to cross-check them. It is a debugging aid which must be
+    /// called after [`Prover::preprocess`] and before [`Prover::prove`] clears
+    /// the witness, and is only built with the `debug-polys` feature.
+    #[cfg(any(test, feature = "debug-polys"))]
+    pub fn evaluate_wires_at(
+        &self,
+        point: F,
+        beta: F,
+        gamma: F,
+    ) -> Result<ProofEvaluations<F>, Error> {
+        let prover_key = self
+            .prover_key
+            .as_ref()
+            .ok_or(Error::CircuitNotPreprocessed)?;
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let w_scalars = self.padded_witness_scalars(&domain);
+        let [w_l_poly, w_r_poly, w_o_poly, w_4_poly] = w_scalars
+            .clone()
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+
+        let z_poly = self.cs.perm.compute_permutation_poly(
+            &domain,
+            (&w_scalars[0], &w_scalars[1], &w_scalars[2], &w_scalars[3]),
+            beta,
+            gamma,
+            (
+                &prover_key.permutation.left_sigma.0,
+                &prover_key.permutation.right_sigma.0,
+                &prover_key.permutation.out_sigma.0,
+                &prover_key.permutation.fourth_sigma.0,
+            ),
+        );
+
+        Ok(linearisation_poly::compute_evaluations(
+            &domain, prover_key, &point, &w_l_poly, &w_r_poly, &w_o_poly,
+            &w_4_poly, &z_poly,
+        ))
+    }
+
     /// Returns a mutable copy of the underlying [`StandardComposer`].
     pub fn mut_cs(&mut self) -> &mut StandardComposer<F, P> {
         &mut self.cs
@@ -92,12 +597,15 @@ where
         if self.prover_key.is_some() {
             return Err(Error::CircuitAlreadyPreprocessed);
         }
-        let pk = self.cs.preprocess_prover(
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        check_commit_key::<F, PC>(commit_key, &domain)?;
+        let (pk, vk) = self.cs.preprocess_prover_and_verifier(
             commit_key,
             &mut self.preprocessed_transcript,
             PhantomData::<PC>,
         )?;
         self.prover_key = Some(pk);
+        self.verifier_key = Some(vk);
         Ok(())
     }
 
@@ -141,6 +649,7 @@ where
     pub fn clear(&mut self) {
         self.clear_witness();
         self.prover_key = None;
+        self.verifier_key = None;
         self.preprocessed_transcript = Transcript::new(b"plonk");
     }
 
@@ -165,57 +674,285 @@ where
         prover_key: &ProverKey<F>,
         _data: PhantomData<PC>,
     ) -> Result<Proof<F, PC>, Error> {
-        let domain =
-            GeneralEvaluationDomain::new(self.cs.circuit_size()).ok_or(Error::InvalidEvalDomainSize {
-                log_size_of_group: self.cs.circuit_size().trailing_zeros(),
-                adicity: <<F as ark_ff::FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-            })?;
-        let n = domain.size();
+        self.prove_with_preprocessed_and_progress(
+            commit_key,
+            prover_key,
+            None,
+            None,
+            None,
+            &[],
+            &[],
+        )
+    }
+
+    /// Creates a [`Proof`] like [`Prover::prove_with_preprocessed`], calling
+    /// `progress`, if any, each time a [`ProvingStage`] is completed, and
+    /// `commitments`, if any, with the witness and permutation commitments
+    /// as soon as they are computed.
+    ///
+    /// When `hiding_rng` is given, the witness polynomials are committed to
+    /// with hiding commitments sampled from it, see [`Prover::prove_hiding`].
+    /// The proof is bound to `context`, see [`Prover::prove_with_context`],
+    /// and to `aux_data`, see [`Prover::prove_with_aux_data`].
+    #[allow(clippy::type_complexity)]
+    fn prove_with_preprocessed_and_progress(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+        progress: Option<&dyn Fn(ProvingStage)>,
+        commitments: Option<&dyn Fn(&WitnessCommitments<F, PC>)>,
+        hiding_rng: Option<&mut dyn RngCore>,
+        context: &[u8],
+        aux_data: &[u8],
+    ) -> Result<Proof<F, PC>, Error> {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
 
         // Since the caller is passing a pre-processed circuit
         // We assume that the Transcript has been seeded with the preprocessed
         // Commitments
         let mut transcript = self.preprocessed_transcript.clone();
 
+        // Bind the proof to its application context before drawing any
+        // challenge.
+        transcript.append_context(context);
+
+        let (w_polys, compute_z_poly) = self.witness_polys(&domain, prover_key);
+        self.prove_polys(
+            commit_key,
+            prover_key,
+            w_polys,
+            compute_z_poly,
+            progress,
+            commitments,
+            hiding_rng,
+            transcript,
+            aux_data,
+        )
+    }
+
+    /// Interpolates the witness polynomials of the stored witness over
+    /// `domain`, in the order `[w_l, w_r, w_o, w_4]`, returning them together
+    /// with the computation of their permutation polynomial from the
+    /// permutation challenges `beta` and `gamma`.
+    fn witness_polys<'a>(
+        &'a self,
+        domain: &'a GeneralEvaluationDomain<F>,
+        prover_key: &'a ProverKey<F>,
+    ) -> (
+        [DensePolynomial<F>; 4],
+        impl FnOnce(F, F) -> DensePolynomial<F> + 'a,
+    ) {
         // 1. Compute witness Polynomials
         //
         // Convert Variables to scalars padding them to the
         // correct domain size.
-        let pad = vec![F::zero(); n - self.cs.w_l.len()];
-        let w_l_scalar = &[&self.to_scalars(&self.cs.w_l)[..], &pad].concat();
-        let w_r_scalar = &[&self.to_scalars(&self.cs.w_r)[..], &pad].concat();
-        let w_o_scalar = &[&self.to_scalars(&self.cs.w_o)[..], &pad].concat();
-        let w_4_scalar = &[&self.to_scalars(&self.cs.w_4)[..], &pad].concat();
+        let [w_l_scalar, w_r_scalar, w_o_scalar, w_4_scalar] =
+            self.padded_witness_scalars(domain);
 
         // Witnesses are now in evaluation form, convert them to coefficients
         // so that we may commit to them.
-        let w_l_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_l_scalar));
-        let w_r_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_r_scalar));
-        let w_o_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_o_scalar));
-        let w_4_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_4_scalar));
+        let w_polys = [&w_l_scalar, &w_r_scalar, &w_o_scalar, &w_4_scalar]
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(w)));
+
+        let compute_z_poly = self.permutation_poly(
+            domain,
+            prover_key,
+            [w_l_scalar, w_r_scalar, w_o_scalar, w_4_scalar],
+        );
+        (w_polys, compute_z_poly)
+    }
+
+    /// Returns the computation of the permutation polynomial of the wire
+    /// values `w_scalars`, in the order `[w_l, w_r, w_o, w_4]` and padded to
+    /// the size of `domain`, from the permutation challenges `beta` and
+    /// `gamma`.
+    fn permutation_poly<'a>(
+        &'a self,
+        domain: &'a GeneralEvaluationDomain<F>,
+        prover_key: &'a ProverKey<F>,
+        w_scalars: [Vec<F>; 4],
+    ) -> impl FnOnce(F, F) -> DensePolynomial<F> + 'a {
+        move |beta, gamma| {
+            self.cs.perm.compute_permutation_poly(
+                domain,
+                (&w_scalars[0], &w_scalars[1], &w_scalars[2], &w_scalars[3]),
+                beta,
+                gamma,
+                (
+                    &prover_key.permutation.left_sigma.0,
+                    &prover_key.permutation.right_sigma.0,
+                    &prover_key.permutation.out_sigma.0,
+                    &prover_key.permutation.fourth_sigma.0,
+                ),
+            )
+        }
+    }
+
+    /// Creates a [`Proof`] for the witness of the stored circuit, starting
+    /// from its already interpolated witness polynomials `w_polys`, in the
+    /// order `[w_l, w_r, w_o, w_4]`.
+    ///
+    /// This skips the interpolation of the witness polynomials from the
+    /// witness variables, so that it can happen in a separate stage. The
+    /// permutation polynomial, the commitments and every challenge are still
+    /// computed here, from the preprocessed transcript of the circuit, so
+    /// that given the polynomials of a [`Prover::prove_with_preprocessed`]
+    /// run, this returns the same [`Proof`].
+    ///
+    /// # Invariants
+    ///
+    /// The caller must uphold the following, otherwise the [`Proof`] does
+    /// not verify:
+    /// - `w_polys` are the interpolations, over the evaluation domain of the
+    ///   circuit, of the wire values padded with zeros to the domain size.
+    /// - The stored circuit holds the same public inputs as the witness, and
+    ///   `prover_key` is the [`ProverKey`] of the circuit.
+    pub fn prove_from_polys(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+        w_polys: [DensePolynomial<F>; 4],
+    ) -> Result<Proof<F, PC>, Error> {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let w_scalars = [&w_polys[0], &w_polys[1], &w_polys[2], &w_polys[3]]
+            .map(|w| domain.fft(&w.coeffs));
+        let compute_z_poly =
+            self.permutation_poly(&domain, prover_key, w_scalars);
+        self.prove_polys(
+            commit_key,
+            prover_key,
+            w_polys,
+            compute_z_poly,
+            None,
+            None,
+            None,
+            self.preprocessed_transcript.clone(),
+            &[],
+        )
+    }
+
+    /// Creates a [`Proof`] from the witness polynomials `w_polys`, computing
+    /// the permutation polynomial with `compute_z_poly` once the permutation
+    /// challenges `beta` and `gamma` are drawn from `transcript`.
+    ///
+    /// `commitments`, if any, is called with the witness and permutation
+    /// commitments once they are computed, before the quotient polynomial.
+    #[allow(clippy::type_complexity)]
+    fn prove_polys<Z>(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+        w_polys: [DensePolynomial<F>; 4],
+        compute_z_poly: Z,
+        progress: Option<&dyn Fn(ProvingStage)>,
+        commitments: Option<&dyn Fn(&WitnessCommitments<F, PC>)>,
+        mut hiding_rng: Option<&mut dyn RngCore>,
+        mut transcript: Transcript,
+        aux_data: &[u8],
+    ) -> Result<Proof<F, PC>, Error>
+    where
+        Z: FnOnce(F, F) -> DensePolynomial<F>,
+    {
+        let report = |stage| {
+            if let Some(progress) = progress {
+                progress(stage)
+            }
+        };
+
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let (checkpoint, challenges, w_rands, z_rands) = self
+            .commit_to_quotient(
+                commit_key,
+                prover_key,
+                &domain,
+                w_polys,
+                compute_z_poly,
+                &report,
+                commitments,
+                reborrow(&mut hiding_rng),
+                &mut transcript,
+                aux_data,
+            )?;
+        self.open_checkpoint(
+            commit_key,
+            prover_key,
+            &domain,
+            &checkpoint,
+            challenges,
+            &w_rands,
+            &z_rands,
+            &report,
+            hiding_rng,
+            transcript,
+        )
+    }
+
+    /// Runs the first half of [`Prover::prove_polys`], up to the commitments
+    /// to the pieces of the quotient polynomial, returning the
+    /// [`ProvingCheckpoint`] reached together with the challenges drawn from
+    /// `transcript` and the randomness of the witness and permutation
+    /// commitments.
+    #[allow(clippy::type_complexity)]
+    fn commit_to_quotient<Z>(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+        domain: &GeneralEvaluationDomain<F>,
+        w_polys: [DensePolynomial<F>; 4],
+        compute_z_poly: Z,
+        report: &dyn Fn(ProvingStage),
+        commitments: Option<&dyn Fn(&WitnessCommitments<F, PC>)>,
+        hiding_rng: Option<&mut dyn RngCore>,
+        transcript: &mut Transcript,
+        aux_data: &[u8],
+    ) -> Result<
+        (
+            ProvingCheckpoint<F, PC>,
+            QuotientChallenges<F>,
+            Vec<PC::Randomness>,
+            Vec<PC::Randomness>,
+        ),
+        Error,
+    >
+    where
+        Z: FnOnce(F, F) -> DensePolynomial<F>,
+    {
+        // Zero blinding factors would leave the witness unhidden.
+        if !self.cs.blinded {
+            return Err(Error::MissingBlindingFactors);
+        }
+        self.cs.check_public_inputs()?;
+        check_commit_key::<F, PC>(commit_key, domain)?;
+
+        let n = domain.size();
+        let [w_l_poly, w_r_poly, w_o_poly, w_4_poly] = w_polys;
 
         // Add blinders
+        let hiding_bound = hiding_rng.as_ref().map(|_| WITNESS_HIDING_BOUND);
         let w_polys = [
-            label_polynomial!(w_l_poly),
-            label_polynomial!(w_r_poly),
-            label_polynomial!(w_o_poly),
-            label_polynomial!(w_4_poly),
+            label_witness("w_l_poly", &w_l_poly, hiding_bound),
+            label_witness("w_r_poly", &w_r_poly, hiding_bound),
+            label_witness("w_o_poly", &w_o_poly, hiding_bound),
+            label_witness("w_4_poly", &w_4_poly, hiding_bound),
         ];
 
         // Commit to witness polynomials.
-        let (w_commits, w_rands) = PC::commit(commit_key, w_polys.iter(), None)
-            .map_err(to_pc_error::<F, PC>)?;
+        let (w_commits, w_rands) = {
+            phase_span!("commitment", n);
+            PC::commit(commit_key, w_polys.iter(), hiding_rng)
+                .map_err(to_pc_error::<F, PC>)?
+        };
 
         // Add witness polynomial commitments to transcript.
         //transcript.append_commitments(&*w_commits, PhantomData::<PC>);
+        transcript.append_proof_version(PROOF_VERSION);
         transcript.append(b"w_l", w_commits[0].commitment());
         transcript.append(b"w_r", w_commits[1].commitment());
         transcript.append(b"w_o", w_commits[2].commitment());
         transcript.append(b"w_4", w_commits[3].commitment());
+        // Bind the proof to its auxiliary data, if any.
+        transcript.append_aux_data(aux_data);
+        report(ProvingStage::WitnessCommitment);
 
         // 2. Compute permutation polynomial
         //
@@ -226,85 +963,175 @@ where
         transcript.append(b"gamma", &gamma);
         assert!(beta != gamma, "challenges must be different");
 
-        let z_poly = self.cs.perm.compute_permutation_poly(
-            &domain,
-            (w_l_scalar, w_r_scalar, w_o_scalar, w_4_scalar),
-            beta,
-            gamma,
-            (
-                &prover_key.permutation.left_sigma.0,
-                &prover_key.permutation.right_sigma.0,
-                &prover_key.permutation.out_sigma.0,
-                &prover_key.permutation.fourth_sigma.0,
-            ),
-        );
+        let z_poly = compute_z_poly(beta, gamma);
 
         // Commit to permutation polynomial.
-        let (z_poly_commit, _) =
-            PC::commit(commit_key, &[label_polynomial!(z_poly)], None)
-                .map_err(to_pc_error::<F, PC>)?;
+        let z_polys = [label_polynomial!(z_poly)];
+        let (z_commits, z_rands) = {
+            phase_span!("commitment", n);
+            PC::commit(commit_key, &z_polys, None)
+                .map_err(to_pc_error::<F, PC>)?
+        };
 
         // Add permutation polynomial commitment to transcript.
-        transcript.append(b"z", z_poly_commit[0].commitment());
+        transcript.append(b"z", z_commits[0].commitment());
+        report(ProvingStage::PermutationPolynomial);
+
+        let witness_commitments = WitnessCommitments {
+            a_comm: w_commits[0].commitment().clone(),
+            b_comm: w_commits[1].commitment().clone(),
+            c_comm: w_commits[2].commitment().clone(),
+            d_comm: w_commits[3].commitment().clone(),
+            z_comm: z_commits[0].commitment().clone(),
+        };
+        if let Some(commitments) = commitments {
+            commitments(&witness_commitments);
+        }
 
         // 3. Compute public inputs polynomial.
-        let pi_poly = DensePolynomial::from_coefficients_vec(
-            domain.ifft(&self.cs.construct_dense_pi_vec()),
-        );
+        let pi_poly = self.compute_pi_poly(domain);
 
         // 4. Compute quotient polynomial
         //
         // Compute quotient challenge; `alpha`, and gate-specific separation
         // challenges.
-        let alpha = transcript.challenge_scalar(b"alpha");
-        let range_sep_challenge =
-            transcript.challenge_scalar(b"range separation challenge");
-        let logic_sep_challenge =
-            transcript.challenge_scalar(b"logic separation challenge");
-        let fixed_base_sep_challenge =
-            transcript.challenge_scalar(b"fixed base separation challenge");
-        let var_base_sep_challenge =
-            transcript.challenge_scalar(b"variable base separation challenge");
-
-        let t_poly = quotient_poly::compute::<F, P>(
-            &domain,
-            prover_key,
-            &z_poly,
-            &w_l_poly,
-            &w_r_poly,
-            &w_o_poly,
-            &w_4_poly,
-            &pi_poly,
-            &alpha,
-            &beta,
-            &gamma,
-            &range_sep_challenge,
-            &logic_sep_challenge,
-            &fixed_base_sep_challenge,
-            &var_base_sep_challenge,
-        )?;
+        let mut quotient_challenges = [F::zero(); 5];
+        transcript.challenge_scalars(
+            &QUOTIENT_CHALLENGE_LABELS,
+            &mut quotient_challenges,
+        );
+        let [alpha, range_sep_challenge, logic_sep_challenge, fixed_base_sep_challenge, var_base_sep_challenge] =
+            quotient_challenges;
+
+        let t_poly = {
+            phase_span!("quotient_poly::compute", n);
+            quotient_poly::compute::<F, P>(
+                domain,
+                prover_key,
+                self.fft(),
+                &z_poly,
+                &w_l_poly,
+                &w_r_poly,
+                &w_o_poly,
+                &w_4_poly,
+                &pi_poly,
+                &alpha,
+                &beta,
+                &gamma,
+                &range_sep_challenge,
+                &logic_sep_challenge,
+                &fixed_base_sep_challenge,
+                &var_base_sep_challenge,
+            )?
+        };
 
         let (t_1_poly, t_2_poly, t_3_poly, t_4_poly) =
             self.split_tx_poly(n, &t_poly);
+        let t_polys = vec![t_1_poly, t_2_poly, t_3_poly, t_4_poly];
 
         // Commit to splitted quotient polynomial
-        let (t_commits, _) = PC::commit(
-            commit_key,
-            &[
-                label_polynomial!(t_1_poly),
-                label_polynomial!(t_2_poly),
-                label_polynomial!(t_3_poly),
-                label_polynomial!(t_4_poly),
-            ],
-            None,
-        )
-        .map_err(to_pc_error::<F, PC>)?;
+        let t_commits = {
+            phase_span!("commitment", n);
+            PC::batch_commit(commit_key, &t_polys)
+                .map_err(to_pc_error::<F, PC>)?
+        };
 
         // Add quotient polynomial commitments to transcript
-        transcript.append(b"t_1", t_commits[0].commitment());
-        transcript.append(b"t_2", t_commits[1].commitment());
-        transcript.append(b"t_3", t_commits[2].commitment());
-        transcript.append(b"t_4", t_commits[3].commitment());
+        transcript.append(b"t_1", &t_commits[0]);
+        transcript.append(b"t_2", &t_commits[1]);
+        transcript.append(b"t_3", &t_commits[2]);
+        transcript.append(b"t_4", &t_commits[3]);
+        report(ProvingStage::QuotientPolynomial);
+
+        let checkpoint = ProvingCheckpoint {
+            w_l_poly,
+            w_r_poly,
+            w_o_poly,
+            w_4_poly,
+            z_poly,
+            t_polys,
+            witness_commitments,
+            aux_data: aux_data.to_vec(),
+            version: PROOF_VERSION,
+            t_comms: t_commits,
+        };
+        let challenges = QuotientChallenges {
+            beta,
+            gamma,
+            quotient: quotient_challenges,
+        };
+        Ok((checkpoint, challenges, w_rands, z_rands))
+    }
+
+    /// Runs the second half of [`Prover::prove_polys`] from `checkpoint`,
+    /// computing the evaluations and the opening proofs of the [`Proof`].
+    ///
+    /// `transcript` must hold the commitments of `checkpoint`, from which
+    /// `challenges` were drawn, and `w_rands` and `z_rands` must be the
+    /// randomness of the witness and permutation commitments.
+    fn open_checkpoint(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+        domain: &GeneralEvaluationDomain<F>,
+        checkpoint: &ProvingCheckpoint<F, PC>,
+        challenges: QuotientChallenges<F>,
+        w_rands: &[PC::Randomness],
+        z_rands: &[PC::Randomness],
+        report: &dyn Fn(ProvingStage),
+        mut hiding_rng: Option<&mut dyn RngCore>,
+        mut transcript: Transcript,
+    ) -> Result<Proof<F, PC>, Error> {
+        let ProvingCheckpoint {
+            w_l_poly,
+            w_r_poly,
+            w_o_poly,
+            w_4_poly,
+            z_poly,
+            t_polys,
+            witness_commitments,
+            t_comms,
+            version,
+            ..
+        } = checkpoint;
+        check_commit_key::<F, PC>(commit_key, domain)?;
+        for found in [t_polys.len(), t_comms.len()] {
+            if found != QUOTIENT_LABELS.len() {
+                return Err(Error::QuotientPieceCountMismatch {
+                    expected: QUOTIENT_LABELS.len(),
+                    found,
+                });
+            }
+        }
+        let QuotientChallenges {
+            beta,
+            gamma,
+            quotient:
+                [alpha, range_sep_challenge, logic_sep_challenge, fixed_base_sep_challenge, var_base_sep_challenge],
+        } = challenges;
+
+        let hiding_bound = hiding_rng.as_ref().map(|_| WITNESS_HIDING_BOUND);
+        let w_polys = [
+            label_witness("w_l_poly", w_l_poly, hiding_bound),
+            label_witness("w_r_poly", w_r_poly, hiding_bound),
+            label_witness("w_o_poly", w_o_poly, hiding_bound),
+            label_witness("w_4_poly", w_4_poly, hiding_bound),
+        ];
+        let w_commits = [
+            ("w_l_poly", &witness_commitments.a_comm),
+            ("w_r_poly", &witness_commitments.b_comm),
+            ("w_o_poly", &witness_commitments.c_comm),
+            ("w_4_poly", &witness_commitments.d_comm),
+        ]
+        .map(|(label, comm)| {
+            LabeledCommitment::new(label.to_owned(), comm.clone(), None)
+        });
+        let z_polys = [label_polynomial!(z_poly)];
+        let z_commits = [LabeledCommitment::new(
+            z_polys[0].label().clone(),
+            witness_commitments.z_comm.clone(),
+            None,
+        )];
 
         // 4. Compute linearisation polynomial
         //
@@ -312,7 +1139,7 @@ where
         let z_challenge = transcript.challenge_scalar(b"z");
 
         let (lin_poly, evaluations) = linearisation_poly::compute::<F, P>(
-            &domain,
+            domain,
             prover_key,
             &alpha,
             &beta,
@@ -322,15 +1149,15 @@ where
             &fixed_base_sep_challenge,
             &var_base_sep_challenge,
             &z_challenge,
-            &w_l_poly,
-            &w_r_poly,
-            &w_o_poly,
-            &w_4_poly,
-            &t_1_poly,
-            &t_2_poly,
-            &t_3_poly,
-            &t_4_poly,
-            &z_poly,
+            w_l_poly,
+            w_r_poly,
+            w_o_poly,
+            w_4_poly,
+            &t_polys[0],
+            &t_polys[1],
+            &t_polys[2],
+            &t_polys[3],
+            z_poly,
         )?;
 
         // Add evaluations to transcript.
@@ -355,12 +1182,8 @@ where
         // Third, all evals needed for custom gates
         evaluations
             .custom_evals
-            .vals
-            .iter()
-            .for_each(|(label, eval)| {
-                let static_label = Box::leak(label.to_owned().into_boxed_str());
-                transcript.append(static_label.as_bytes(), eval);
-            });
+            .append_to_transcript(&mut transcript);
+        report(ProvingStage::Linearisation);
 
         // 5. Compute Openings using KZG10
         //
@@ -369,6 +1192,7 @@ where
 
         // Compute aggregate witness to polynomials evaluated at the evaluation
         // challenge `z`
+        phase_span!("opening", domain.size());
         let aw_challenge: F = transcript.challenge_scalar(b"aggregate_witness");
 
         let aw_polys = [
@@ -381,82 +1205,598 @@ where
         let (aw_commits, aw_rands) = PC::commit(commit_key, &aw_polys, None)
             .map_err(to_pc_error::<F, PC>)?;
 
-        let aw_opening = PC::open(
+        // Both aggregates are built in the same order as in the verifier.
+        let wire_evals = &evaluations.wire_evals;
+        let perm_evals = &evaluations.perm_evals;
+        let mut aw = AggregateProof::<F, PC>::new();
+        aw.add(
+            aw_commits[0].commitment(),
+            aw_polys[0].evaluate(&z_challenge),
+        );
+        aw.add(aw_commits[1].commitment(), perm_evals.left_sigma_eval);
+        aw.add(aw_commits[2].commitment(), perm_evals.right_sigma_eval);
+        aw.add(aw_commits[3].commitment(), perm_evals.out_sigma_eval);
+        aw.add(w_commits[0].commitment(), wire_evals.a_eval);
+        aw.add(w_commits[1].commitment(), wire_evals.b_eval);
+        aw.add(w_commits[2].commitment(), wire_evals.c_eval);
+        aw.add(w_commits[3].commitment(), wire_evals.d_eval);
+
+        let aw_opening = aw.open(
             commit_key,
-            aw_polys.iter().chain(w_polys.iter()),
-            aw_commits.iter().chain(w_commits.iter()),
+            &aw_polys.iter().chain(&w_polys).collect::<Vec<_>>(),
+            &aw_rands.iter().chain(w_rands).collect::<Vec<_>>(),
             &z_challenge,
             aw_challenge,
-            aw_rands.iter().chain(w_rands.iter()),
-            None,
-        )
-        .map_err(to_pc_error::<F, PC>)?;
+            reborrow(&mut hiding_rng),
+        )?;
 
         let saw_challenge: F =
             transcript.challenge_scalar(b"aggregate_witness");
 
-        let saw_polys = [
-            label_polynomial!(z_poly),
-            label_polynomial!(w_l_poly),
-            label_polynomial!(w_r_poly),
-            label_polynomial!(w_4_poly),
-        ];
-
-        let (saw_commits, saw_rands) = PC::commit(commit_key, &saw_polys, None)
-            .map_err(to_pc_error::<F, PC>)?;
+        // The shifted openings reuse the witness commitments, so that their
+        // blinding, if any, is carried into the opening proof.
+        let custom_evals = &evaluations.custom_evals;
+        let mut saw = AggregateProof::<F, PC>::new();
+        saw.add(z_commits[0].commitment(), perm_evals.permutation_eval);
+        saw.add(w_commits[0].commitment(), custom_evals.get("a_next_eval"));
+        saw.add(w_commits[1].commitment(), custom_evals.get("b_next_eval"));
+        saw.add(w_commits[3].commitment(), custom_evals.get("d_next_eval"));
 
-        let saw_opening = PC::open(
+        let shifted_z_challenge = shifted_point(domain, z_challenge);
+        let saw_opening = saw.open(
             commit_key,
-            &saw_polys,
-            &saw_commits,
-            &(z_challenge * domain.element(1)),
+            &[&z_polys[0], &w_polys[0], &w_polys[1], &w_polys[3]],
+            &[&z_rands[0], &w_rands[0], &w_rands[1], &w_rands[3]],
+            &shifted_z_challenge,
             saw_challenge,
-            &saw_rands,
-            None,
-        )
-        .map_err(to_pc_error::<F, PC>)?;
+            reborrow(&mut hiding_rng),
+        )?;
+        report(ProvingStage::Opening);
 
         Ok(Proof {
-            a_comm: w_commits[0].commitment().clone(),
-            b_comm: w_commits[1].commitment().clone(),
-            c_comm: w_commits[2].commitment().clone(),
-            d_comm: w_commits[3].commitment().clone(),
-            z_comm: saw_commits[0].commitment().clone(),
-            t_1_comm: t_commits[0].commitment().clone(),
-            t_2_comm: t_commits[1].commitment().clone(),
-            t_3_comm: t_commits[2].commitment().clone(),
-            t_4_comm: t_commits[3].commitment().clone(),
+            version: *version,
+            a_comm: witness_commitments.a_comm.clone(),
+            b_comm: witness_commitments.b_comm.clone(),
+            c_comm: witness_commitments.c_comm.clone(),
+            d_comm: witness_commitments.d_comm.clone(),
+            z_comm: witness_commitments.z_comm.clone(),
+            t_comms: t_comms.clone(),
             aw_opening,
             saw_opening,
             evaluations,
         })
     }
 
+    /// Returns the values of the four wires of the stored witness, padded
+    /// with zeros to the size of `domain`.
+    pub(crate) fn padded_witness_scalars(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+    ) -> [Vec<F>; 4] {
+        let pad = vec![F::zero(); domain.size() - self.cs.w_l.len()];
+        [&self.cs.w_l, &self.cs.w_r, &self.cs.w_o, &self.cs.w_4]
+            .map(|w| [&self.to_scalars(w)[..], &pad].concat())
+    }
+
+    /// Computes the contribution of each type of gate to the gate
+    /// constraints part of the quotient polynomial of the stored witness, at
+    /// every point of the coset of the `4n` domain.
+    ///
+    /// The contributions of the range, logic, fixed base and curve addition
+    /// gates are computed with fixed non-zero separation challenges. For a
+    /// satisfied circuit, the [`sum`](super::GateContributions::sum) of the
+    /// contributions is divisible by the vanishing polynomial, so a gate
+    /// whose contribution breaks this is the one to look at when the
+    /// quotient polynomial is wrong.
+    #[cfg(feature = "trace")]
+    pub fn gate_contributions(
+        &self,
+        prover_key: &ProverKey<F>,
+    ) -> Result<Vec<quotient_poly::GateContributions<F>>, Error> {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let [w_l_poly, w_r_poly, w_o_poly, w_4_poly] = self
+            .padded_witness_scalars(&domain)
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+        let challenges = [5u64, 6, 7, 8].map(F::from);
+        quotient_poly::compute_gate_contributions::<F, P>(
+            &domain,
+            prover_key,
+            &w_l_poly,
+            &w_r_poly,
+            &w_o_poly,
+            &w_4_poly,
+            &self.compute_pi_poly(&domain),
+            challenges[0],
+            challenges[1],
+            challenges[2],
+            challenges[3],
+        )
+    }
+
+    /// Computes the quotient polynomial of the stored witness, before it is
+    /// split into the pieces `t_1, t_2, ...` committed to in a [`Proof`].
+    ///
+    /// The challenges are drawn from the transcript as in
+    /// [`Prover::prove_with_preprocessed`], so the quotient polynomial is the
+    /// one of the proof created for the same witness and blinding factors.
+    /// For a satisfied circuit, it is the polynomial `t` of degree lower than
+    /// `4n` such that `t * Z_H` is the sum of the gate constraints and of the
+    /// permutation checks, `Z_H` being the vanishing polynomial of the domain
+    /// of size `n`. This is meant for validating the split of the quotient
+    /// polynomial and for computing test vectors.
+    #[cfg(any(test, feature = "quotient-poly"))]
+    pub fn quotient_poly(
+        &self,
+        commit_key: &PC::CommitterKey,
+        prover_key: &ProverKey<F>,
+    ) -> Result<QuotientPoly<F>, Error> {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let (w_polys, compute_z_poly) = self.witness_polys(&domain, prover_key);
+        let (checkpoint, challenges, ..) = self.commit_to_quotient(
+            commit_key,
+            prover_key,
+            &domain,
+            w_polys,
+            compute_z_poly,
+            &|_| {},
+            None,
+            None,
+            &mut self.preprocessed_transcript.clone(),
+            &[],
+        )?;
+        let QuotientChallenges {
+            beta,
+            gamma,
+            quotient: [alpha, separation_challenges @ ..],
+        } = challenges;
+        let quotient = quotient_poly::compute::<F, P>(
+            &domain,
+            prover_key,
+            self.fft(),
+            &checkpoint.z_poly,
+            &checkpoint.w_l_poly,
+            &checkpoint.w_r_poly,
+            &checkpoint.w_o_poly,
+            &checkpoint.w_4_poly,
+            &self.compute_pi_poly(&domain),
+            &alpha,
+            &beta,
+            &gamma,
+            &separation_challenges[0],
+            &separation_challenges[1],
+            &separation_challenges[2],
+            &separation_challenges[3],
+        )?;
+        Ok(QuotientPoly {
+            quotient,
+            z_poly: checkpoint.z_poly,
+            alpha,
+            beta,
+            gamma,
+            separation_challenges,
+        })
+    }
+
+    /// Builds the inputs of the quotient polynomial computation for the stored
+    /// witness, using fixed challenges, and returns a closure which only
+    /// computes the quotient polynomial.
+    ///
+    /// This is a hook for benchmarking the quotient computation in isolation
+    /// and is not part of the proving API.
+    #[doc(hidden)]
+    pub fn quotient_poly_bench<'a>(
+        &'a self,
+        prover_key: &'a ProverKey<F>,
+    ) -> Result<impl Fn() -> Result<DensePolynomial<F>, Error> + 'a, Error>
+    {
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let w_scalars = self.padded_witness_scalars(&domain);
+        let [w_l_poly, w_r_poly, w_o_poly, w_4_poly] = w_scalars
+            .clone()
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+
+        let beta = F::from(2u64);
+        let gamma = F::from(3u64);
+        let z_poly = self.cs.perm.compute_permutation_poly(
+            &domain,
+            (&w_scalars[0], &w_scalars[1], &w_scalars[2], &w_scalars[3]),
+            beta,
+            gamma,
+            (
+                &prover_key.permutation.left_sigma.0,
+                &prover_key.permutation.right_sigma.0,
+                &prover_key.permutation.out_sigma.0,
+                &prover_key.permutation.fourth_sigma.0,
+            ),
+        );
+        let pi_poly = self.compute_pi_poly(&domain);
+        let challenges = [4u64, 5, 6, 7, 8].map(F::from);
+
+        Ok(move || {
+            quotient_poly::compute::<F, P>(
+                &domain,
+                prover_key,
+                self.fft(),
+                &z_poly,
+                &w_l_poly,
+                &w_r_poly,
+                &w_o_poly,
+                &w_4_poly,
+                &pi_poly,
+                &challenges[0],
+                &beta,
+                &gamma,
+                &challenges[1],
+                &challenges[2],
+                &challenges[3],
+                &challenges[4],
+            )
+        })
+    }
+
     /// Proves a circuit is satisfied, then clears the witness variables
     /// If the circuit is not pre-processed, then the preprocessed circuit will
     /// also be computed.
     pub fn prove(
         &mut self,
         commit_key: &PC::CommitterKey,
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_with_progress(commit_key, None)
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], calling
+    /// `progress`, if any, each time a [`ProvingStage`] is completed.
+    pub fn prove_with_progress(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        progress: Option<&dyn Fn(ProvingStage)>,
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_and_clear(commit_key, progress, None, None, &[], &[])
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], also returning
+    /// the [`ProofMetadata`] of the circuit.
+    pub fn prove_with_metadata(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+    ) -> Result<(Proof<F, PC>, ProofMetadata), Error> {
+        let start = std::time::Instant::now();
+        if self.prover_key.is_none() {
+            self.preprocess(commit_key)?;
+        }
+        // The witness is cleared once proven, so the metadata is read
+        // beforehand.
+        let verifier_key = self
+            .verifier_key
+            .as_ref()
+            .ok_or(Error::CircuitNotPreprocessed)?;
+        let domain_size = verifier_key.domain()?.size();
+        let gate_types = verifier_key.gate_types();
+        let num_public_inputs = self.cs.public_inputs_sparse_store.len();
+        let proof = self.prove(commit_key)?;
+        Ok((
+            proof,
+            ProofMetadata {
+                domain_size,
+                num_public_inputs,
+                gate_types,
+                proving_time: start.elapsed(),
+            },
+        ))
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], calling
+    /// `commitments` with the witness and permutation commitments of the
+    /// [`Proof`] as soon as they are computed.
+    ///
+    /// This lets the commitments be published, e.g. to a data availability
+    /// layer, before the quotient polynomial and the openings, which make
+    /// up most of the proving time, are done. The returned [`Proof`] holds
+    /// the same commitments, see [`Proof::witness_commitments`].
+    pub fn prove_with_commitments(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        commitments: &dyn Fn(&WitnessCommitments<F, PC>),
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_and_clear(
+            commit_key,
+            None,
+            Some(commitments),
+            None,
+            &[],
+            &[],
+        )
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], binding the
+    /// [`Proof`] to the application `context`, e.g. a block height or a nonce.
+    ///
+    /// The `context` is absorbed into the transcript before any challenge is
+    /// drawn, so the proof only verifies with
+    /// [`Verifier::verify_with_context`](super::Verifier::verify_with_context)
+    /// given the same `context`. Unlike a Public Input, it is not part of the
+    /// circuit. An empty `context` is the same as no context at all.
+    pub fn prove_with_context(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        context: &[u8],
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_and_clear(commit_key, None, None, None, context, &[])
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], binding the
+    /// [`Proof`] to `aux_data`, e.g. a timestamp or a request id checked
+    /// out-of-band by the verifier.
+    ///
+    /// The `aux_data` is absorbed into the transcript right after the
+    /// witness commitments, so the proof only verifies with
+    /// [`Verifier::verify_with_aux_data`] given the same `aux_data` and cannot
+    /// be reused for other data. Unlike a Public Input, it is not part of the
+    /// circuit, and unlike the transcript label, it changes from proof to
+    /// proof. Empty `aux_data` is the same as none at all.
+    ///
+    /// [`Verifier::verify_with_aux_data`]:
+    /// super::Verifier::verify_with_aux_data
+    pub fn prove_with_aux_data(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        aux_data: &[u8],
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_and_clear(commit_key, None, None, None, &[], aux_data)
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], binding the
+    /// [`Proof`] to both the application `context`, see
+    /// [`Prover::prove_with_context`], and `aux_data`, see
+    /// [`Prover::prove_with_aux_data`].
+    ///
+    /// The proof only verifies with
+    /// [`Verifier::verify_bound`](super::Verifier::verify_bound) given the
+    /// same `context` and `aux_data`.
+    pub fn prove_bound(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        context: &[u8],
+        aux_data: &[u8],
+    ) -> Result<Proof<F, PC>, Error> {
+        self.prove_and_clear(commit_key, None, None, None, context, aux_data)
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], as the next
+    /// [`Proof`] of a chain of proofs whose state so far is `incoming`.
+    ///
+    /// The proof is bound to a link drawn from `incoming`, see
+    /// [`Prover::prove_with_context`], and then absorbed into `incoming`,
+    /// which is returned as the state to prove the next proof of the chain
+    /// from. The chain is verified proof by proof with
+    /// [`Verifier::verify_chained`](super::Verifier::verify_chained),
+    /// starting from the same initial transcript, so that the proofs only
+    /// verify in the order they were made in.
+    pub fn prove_chained(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        mut incoming: Transcript,
+    ) -> Result<(Proof<F, PC>, Transcript), Error> {
+        let link = incoming.chain_link();
+        let proof = self.prove_with_context(commit_key, &link)?;
+        incoming.append(b"chained_proof", &proof);
+        Ok((proof, incoming))
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove`], committing to
+    /// the witness polynomials with hiding commitments whose randomness is
+    /// sampled from `rng`.
+    ///
+    /// # Zero-Knowledge
+    ///
+    /// The blinding of the witness commitments is carried through into the
+    /// opening proofs `aw_opening` and `saw_opening`, so that neither the
+    /// commitments nor the openings reveal anything about the witness beyond
+    /// the evaluations contained in the [`Proof`]. Without hiding, the
+    /// commitments are deterministic in the witness polynomials and
+    /// zero-knowledge relies only on the blinding rows added by the
+    /// [`StandardComposer`].
+    ///
+    /// The commit key must be trimmed with a `supported_hiding_bound` of at
+    /// least [`WITNESS_HIDING_BOUND`]. The resulting proofs are verified as
+    /// any other proof.
+    pub fn prove_hiding<R>(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        rng: &mut R,
+    ) -> Result<Proof<F, PC>, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        self.prove_and_clear(
+            commit_key,
+            None,
+            None,
+            Some(rng as &mut dyn RngCore),
+            &[],
+            &[],
+        )
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove_hiding`], drawing
+    /// all of the blinding randomness of the [`Proof`] from `blinding`
+    /// instead of sampling any of it internally.
+    ///
+    /// The first [`NUM_BLINDING_FACTORS`] scalars drawn from `blinding` are
+    /// set as the blinding factors of the circuit, see
+    /// [`StandardComposer::set_blinding_factors`], and the rest of the
+    /// stream is the randomness of the hiding commitments. The same witness
+    /// and the same `blinding` stream give the same [`Proof`], so that the
+    /// randomness can be supplied by a multi-party computation. The resulting
+    /// proofs are verified as any other proof.
+    ///
+    /// # Security
+    ///
+    /// `blinding` must output uniformly random values which are kept secret
+    /// and never reused across proofs: zero-knowledge rests entirely on
+    /// them.
+    pub fn prove_with_blinding<R>(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        blinding: &mut R,
+    ) -> Result<Proof<F, PC>, Error>
+    where
+        R: RngCore,
+    {
+        let factors = [(); NUM_BLINDING_FACTORS].map(|_| F::rand(blinding));
+        self.cs.set_blinding_factors(factors);
+        self.prove_and_clear(
+            commit_key,
+            None,
+            None,
+            Some(blinding as &mut dyn RngCore),
+            &[],
+            &[],
+        )
+    }
+
+    /// Proves a circuit is satisfied like [`Prover::prove_with_aux_data`] up
+    /// to the commitments to the quotient polynomial, returning the
+    /// [`ProvingCheckpoint`] reached, then clears the witness variables.
+    ///
+    /// The [`Proof`] is completed with [`Prover::resume_from_checkpoint`],
+    /// which gives the same [`Proof`] as [`Prover::prove_with_aux_data`] with
+    /// the same `aux_data`, carried by the checkpoint. If the circuit is not
+    /// pre-processed, then the preprocessed circuit will also be computed.
+    pub fn prove_to_checkpoint(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        aux_data: &[u8],
+    ) -> Result<ProvingCheckpoint<F, PC>, Error> {
+        if self.prover_key.is_none() {
+            self.preprocess(commit_key)?;
+        }
+        let prover_key = self.prover_key.as_ref().unwrap();
+
+        let domain = radix2_domain::<F>(self.cs.circuit_size())?;
+        let (w_polys, compute_z_poly) = self.witness_polys(&domain, prover_key);
+        let (checkpoint, ..) = self.commit_to_quotient(
+            commit_key,
+            prover_key,
+            &domain,
+            w_polys,
+            compute_z_poly,
+            &|_| {},
+            None,
+            None,
+            &mut self.preprocessed_transcript.clone(),
+            aux_data,
+        )?;
+
+        self.clear_witness();
+        Ok(checkpoint)
+    }
+
+    /// Completes the [`Proof`] of `checkpoint`, as returned by
+    /// [`Prover::prove_to_checkpoint`].
+    ///
+    /// The witness is not needed, only the preprocessed circuit, so that the
+    /// `Prover` may be another one than the one which created the
+    /// checkpoint, as long as it uses the same transcript label and
+    /// preprocessed circuit. Otherwise, the [`Proof`] does not verify.
+    pub fn resume_from_checkpoint(
+        &self,
+        commit_key: &PC::CommitterKey,
+        checkpoint: &ProvingCheckpoint<F, PC>,
+    ) -> Result<Proof<F, PC>, Error> {
+        let prover_key = self
+            .prover_key
+            .as_ref()
+            .ok_or(Error::CircuitNotPreprocessed)?;
+        let domain = key_domain::<F>(prover_key.n)?;
+
+        let mut transcript = self.preprocessed_transcript.clone();
+        let challenges = checkpoint.replay(&mut transcript);
+
+        // Checkpoints are only made without hiding, so the commitments carry
+        // no randomness.
+        let w_rands =
+            (0..4).map(|_| PC::Randomness::empty()).collect::<Vec<_>>();
+        let z_rands = [PC::Randomness::empty()];
+        self.open_checkpoint(
+            commit_key,
+            prover_key,
+            &domain,
+            checkpoint,
+            challenges,
+            &w_rands,
+            &z_rands,
+            &|_| {},
+            None,
+            transcript,
+        )
+    }
+
+    /// Verifies `proof` against the stored witness as
+    /// [`Verifier::verify_with_context`](super::Verifier::verify_with_context)
+    /// would, returning [`Error::SelfCheckFailed`] if it does not verify.
+    ///
+    /// The verifier key of the circuit is only known if it was preprocessed
+    /// by this `Prover`, otherwise [`Error::CircuitNotPreprocessed`] is
+    /// returned.
+    fn self_check(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        context: &[u8],
+        aux_data: &[u8],
+    ) -> Result<(), Error> {
+        let verifier_key = self
+            .verifier_key
+            .as_ref()
+            .ok_or(Error::CircuitNotPreprocessed)?;
+        let public_inputs = self.cs.construct_dense_pi_vec();
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append_context(context);
+        proof
+            .verify::<P>(
+                verifier_key,
+                &mut transcript,
+                pc_verifier_key,
+                PublicInputs::Values(&public_inputs),
+                aux_data,
+            )
+            .map_err(|_| Error::SelfCheckFailed)
+    }
+
+    /// Preprocesses the circuit if needed, proves it and clears the witness.
+    #[allow(clippy::type_complexity)]
+    fn prove_and_clear(
+        &mut self,
+        commit_key: &PC::CommitterKey,
+        progress: Option<&dyn Fn(ProvingStage)>,
+        commitments: Option<&dyn Fn(&WitnessCommitments<F, PC>)>,
+        hiding_rng: Option<&mut dyn RngCore>,
+        context: &[u8],
+        aux_data: &[u8],
     ) -> Result<Proof<F, PC>, Error> {
         if self.prover_key.is_none() {
             // Preprocess circuit and store preprocessed circuit and transcript
             // in the Prover.
-            self.prover_key = Some(self.cs.preprocess_prover(
-                commit_key,
-                &mut self.preprocessed_transcript,
-                PhantomData::<PC>,
-            )?);
+            self.preprocess(commit_key)?;
         }
 
         let prover_key = self.prover_key.as_ref().unwrap();
 
-        let proof = self.prove_with_preprocessed(
+        let proof = self.prove_with_preprocessed_and_progress(
             commit_key,
             prover_key,
-            PhantomData::<PC>,
+            progress,
+            commitments,
+            hiding_rng,
+            context,
+            aux_data,
         )?;
 
+        if let Some(pc_verifier_key) = &self.config.self_check_key {
+            self.self_check(&proof, pc_verifier_key, context, aux_data)?;
+        }
+
         // Clear witness and reset composer variables
         self.clear_witness();
 
@@ -475,3 +1815,897 @@ where
         Prover::new(b"plonk")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test,
+        permutation::constants::{K1, K2, K3},
+        proof_system::{OpeningClaim, Verifier},
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ff::FftField;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use core::cell::RefCell;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use rand::rngs::OsRng;
+
+    fn test_progress_stages<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"progress");
+        let composer = prover.mut_cs();
+        let one = composer.add_input(F::one());
+        composer.arithmetic_gate(|gate| {
+            gate.witness(one, one, None).add(F::one(), F::one())
+        });
+
+        let stages = RefCell::new(Vec::new());
+        let record = |stage: ProvingStage| stages.borrow_mut().push(stage);
+        prover.prove_with_progress(&ck, Some(&record)).unwrap();
+
+        let stages = stages.into_inner();
+        assert_eq!(
+            stages,
+            [
+                ProvingStage::WitnessCommitment,
+                ProvingStage::PermutationPolynomial,
+                ProvingStage::QuotientPolynomial,
+                ProvingStage::Linearisation,
+                ProvingStage::Opening,
+            ]
+        );
+        assert!(stages
+            .windows(2)
+            .all(|pair| pair[0].fraction() < pair[1].fraction()));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        use crate::test::SpanRecorder;
+        use ark_ff::One;
+        use ark_poly_commit::PolynomialCommitment;
+
+        type F = <Bls12_381 as ark_ec::PairingEngine>::Fr;
+        type P = ark_ed_on_bls12_381::EdwardsParameters;
+        type PC = crate::commitment::KZG10<Bls12_381>;
+
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None).add(F::one(), F::one())
+            });
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"tracing");
+        gadget(prover.mut_cs());
+        let n = radix2_domain::<F>(prover.cs.circuit_size()).unwrap().size();
+        let mut verifier = Verifier::<F, P, PC>::new(b"tracing");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        let (spans, _) = SpanRecorder::record(|| {
+            let proof = prover.prove(&ck).unwrap();
+            verifier.verify(&proof, &vk, &[]).unwrap();
+        });
+
+        let n = n as u64;
+        let phases = spans
+            .iter()
+            .filter(|span| !["group_ops", "PC::check"].contains(&span.name))
+            .map(|span| (span.name, span.field("domain_size")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            [
+                ("commitment", n),
+                ("commitment", n),
+                ("quotient_poly::compute", n),
+                ("commitment", n),
+                ("opening", n),
+                ("batch_openings", n),
+                ("linearisation", n),
+                ("opening_checks", n),
+            ]
+        );
+
+        // The KZG10 verifier checks both batch openings at once.
+        let checks = spans
+            .iter()
+            .filter(|span| span.name == "PC::check")
+            .collect::<Vec<_>>();
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].field("polynomials") > 0);
+    }
+
+    fn test_witness_commitments<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        WitnessCommitments<F, PC>: PartialEq,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"commitments");
+        let composer = prover.mut_cs();
+        let a = composer.add_input(F::from(2u64));
+        let b = composer.add_input(F::from(3u64));
+        let c = composer
+            .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+        composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+
+        let emitted = RefCell::new(Vec::new());
+        let publish = |commitments: &WitnessCommitments<F, PC>| {
+            emitted.borrow_mut().push(commitments.clone())
+        };
+        let proof = prover.prove_with_commitments(&ck, &publish).unwrap();
+
+        let emitted = emitted.into_inner();
+        assert_eq!(emitted.len(), 1);
+        assert!(emitted[0] == proof.witness_commitments());
+    }
+
+    fn test_proof_metadata<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"metadata");
+        let composer = prover.mut_cs();
+        let (x, _) = composer.add_public_input_variable(F::from(200u64));
+        composer.range_gate(x, 8);
+        let domain_size = prover.circuit_size().next_power_of_two();
+
+        let (_, metadata) = prover.prove_with_metadata(&ck).unwrap();
+        assert_eq!(
+            metadata,
+            ProofMetadata {
+                domain_size,
+                num_public_inputs: 1,
+                gate_types: vec![GateType::Arithmetic, GateType::Range],
+                proving_time: metadata.proving_time,
+            }
+        );
+        assert!(metadata.proving_time > Duration::ZERO);
+    }
+
+    fn to_bytes<T>(item: &T) -> Vec<u8>
+    where
+        T: CanonicalSerialize,
+    {
+        let mut bytes = Vec::new();
+        item.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn test_hiding_proofs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) =
+            PC::trim(&u_params, 2 * 16, WITNESS_HIDING_BOUND, None).unwrap();
+
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(3u64))
+            });
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"hiding");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        prover.clear_witness();
+
+        // Prove the same witness under the same blinding rows, so that only
+        // the hiding commitments can tell the proofs apart.
+        let factors = [F::from(7u64); NUM_BLINDING_FACTORS];
+        let mut proofs = Vec::new();
+        for hiding in [false, false, true, true] {
+            gadget(prover.mut_cs());
+            prover.mut_cs().set_blinding_factors(factors);
+            let proof = if hiding {
+                prover.prove_hiding(&ck, &mut OsRng)
+            } else {
+                prover.prove(&ck)
+            };
+            proofs.push(proof.unwrap());
+        }
+        let [plain, same_plain, first, second] =
+            <[Proof<F, PC>; 4]>::try_from(proofs).ok().unwrap();
+
+        // Without hiding, the commitments are deterministic in the witness.
+        assert_eq!(to_bytes(&plain), to_bytes(&same_plain));
+
+        // With hiding, they are randomized, and so are the openings.
+        for (a, b) in [(&first, &second), (&first, &plain), (&second, &plain)] {
+            assert_ne!(to_bytes(&a.a_comm), to_bytes(&b.a_comm));
+            assert_ne!(to_bytes(&a.aw_opening), to_bytes(&b.aw_opening));
+            assert_ne!(to_bytes(&a.saw_opening), to_bytes(&b.saw_opening));
+        }
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"hiding");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        for proof in [&plain, &first, &second] {
+            assert!(verifier.verify(proof, &vk, &public_inputs).is_ok());
+        }
+    }
+
+    fn test_prove_with_blinding<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) =
+            PC::trim(&u_params, 2 * 16, WITNESS_HIDING_BOUND, None).unwrap();
+
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let one = composer.add_input(F::one());
+            composer.arithmetic_gate(|gate| {
+                gate.witness(one, one, None)
+                    .add(F::one(), F::one())
+                    .pi(F::from(3u64))
+            });
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"blinding");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let first = prover
+            .prove_with_blinding(&ck, &mut ark_std::test_rng())
+            .unwrap();
+        gadget(prover.mut_cs());
+        let second = prover
+            .prove_with_blinding(&ck, &mut ark_std::test_rng())
+            .unwrap();
+        gadget(prover.mut_cs());
+        let hidden = prover.prove_hiding(&ck, &mut OsRng).unwrap();
+
+        // The same blinding randomness gives the same proof.
+        assert_eq!(to_bytes(&first), to_bytes(&second));
+        assert_ne!(to_bytes(&first), to_bytes(&hidden));
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"blinding");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&first, &vk, &public_inputs).is_ok());
+    }
+
+    fn test_missing_blinding_factors<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // A circuit built without the `prover` feature has zero blinding
+        // factors until they are set.
+        let mut prover = Prover::<F, P, PC>::new(b"unblinded");
+        prover.cs.add_dummy_constraints();
+        prover.cs.blinded = false;
+        assert!(matches!(
+            prover.prove(&ck),
+            Err(Error::MissingBlindingFactors)
+        ));
+
+        let mut rng = ark_std::test_rng();
+        prover.cs.set_blinding_factors(
+            [(); NUM_BLINDING_FACTORS].map(|_| F::rand(&mut rng)),
+        );
+        assert!(prover.prove(&ck).is_ok());
+    }
+
+    fn test_evaluate_wires_at<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"evaluations");
+        let composer = prover.mut_cs();
+        let two = composer.add_input(F::from(2u64));
+        let three = composer.add_input(F::from(3u64));
+        let six = composer.arithmetic_gate(|gate| {
+            gate.witness(two, three, None).mul(F::one())
+        });
+        composer.constrain_to_constant(six, F::from(6u64), None);
+
+        assert!(matches!(
+            prover.evaluate_wires_at(F::one(), F::one(), F::one()),
+            Err(Error::CircuitNotPreprocessed)
+        ));
+        prover.preprocess(&ck).unwrap();
+        let proof = prover
+            .prove_with_preprocessed(
+                &ck,
+                prover.prover_key.as_ref().unwrap(),
+                PhantomData,
+            )
+            .unwrap();
+
+        // Replay the transcript to recover the challenges of the proof.
+        let mut transcript = prover.preprocessed_transcript.clone();
+        transcript.append_proof_version(proof.version);
+        transcript.append(b"w_l", &proof.a_comm);
+        transcript.append(b"w_r", &proof.b_comm);
+        transcript.append(b"w_o", &proof.c_comm);
+        transcript.append(b"w_4", &proof.d_comm);
+        let beta = transcript.challenge_scalar(b"beta");
+        transcript.append(b"beta", &beta);
+        let gamma = transcript.challenge_scalar(b"gamma");
+        transcript.append(b"gamma", &gamma);
+        transcript.append(b"z", &proof.z_comm);
+        for label in [
+            &b"alpha"[..],
+            b"range separation challenge",
+            b"logic separation challenge",
+            b"fixed base separation challenge",
+            b"variable base separation challenge",
+        ] {
+            let _: F = transcript.challenge_scalar(label);
+        }
+        for (label, t_comm) in [&b"t_1"[..], b"t_2", b"t_3", b"t_4"]
+            .into_iter()
+            .zip(&proof.t_comms)
+        {
+            transcript.append(label, t_comm);
+        }
+        let z_challenge = transcript.challenge_scalar(b"z");
+
+        let evaluations =
+            prover.evaluate_wires_at(z_challenge, beta, gamma).unwrap();
+        assert_eq!(evaluations.wire_evals, proof.evaluations.wire_evals);
+        assert_eq!(evaluations.perm_evals, proof.evaluations.perm_evals);
+        assert_eq!(evaluations.custom_evals, proof.evaluations.custom_evals);
+    }
+
+    fn test_prove_from_polys<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // 2 * 3 = c, with c matching the public input 6
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(2u64));
+            let b = composer.add_input(F::from(3u64));
+            let c = composer
+                .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+            composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"polys");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+        let proof = prover
+            .prove_with_preprocessed(&ck, prover_key, PhantomData)
+            .unwrap();
+
+        // Interpolate the witness polynomials as a separate stage would.
+        let domain = radix2_domain::<F>(prover.circuit_size()).unwrap();
+        let w_polys = prover
+            .padded_witness_scalars(&domain)
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+
+        let rebuilt =
+            prover.prove_from_polys(&ck, prover_key, w_polys).unwrap();
+        assert_eq!(to_bytes(&rebuilt), to_bytes(&proof));
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"polys");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        assert!(verifier.verify(&rebuilt, &vk, &public_inputs).is_ok());
+    }
+
+    fn test_checkpoint<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // 2 * 3 = c, with c matching the public input 6
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(2u64));
+            let b = composer.add_input(F::from(3u64));
+            let c = composer
+                .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+            composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"checkpoint");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover
+            .prove_with_preprocessed(
+                &ck,
+                prover.prover_key.as_ref().unwrap(),
+                PhantomData,
+            )
+            .unwrap();
+        let checkpoint = prover.prove_to_checkpoint(&ck, &[]).unwrap();
+
+        // Resume from the serialized checkpoint on another prover, which only
+        // holds the preprocessed circuit.
+        let bytes = to_bytes(&checkpoint);
+        let checkpoint =
+            ProvingCheckpoint::<F, PC>::deserialize(bytes.as_slice()).unwrap();
+        let mut resumer = Prover::<F, P, PC>::new(b"checkpoint");
+        gadget(resumer.mut_cs());
+        resumer.preprocess(&ck).unwrap();
+        resumer.clear_witness();
+        let resumed = resumer.resume_from_checkpoint(&ck, &checkpoint).unwrap();
+        assert_eq!(to_bytes(&resumed), to_bytes(&proof));
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"checkpoint");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&resumed, &vk, &public_inputs).is_ok());
+    }
+
+    fn test_external_openings<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        // 2 * 3 = c, with c matching the public input 6
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(2u64));
+            let b = composer.add_input(F::from(3u64));
+            let c = composer
+                .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+            composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"external_openings");
+        gadget(prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let aux_data = b"request=7";
+        let checkpoint = prover.prove_to_checkpoint(&ck, aux_data).unwrap();
+        let mut proof =
+            prover.resume_from_checkpoint(&ck, &checkpoint).unwrap();
+
+        // Discard the opening proofs of the prover.
+        core::mem::swap(&mut proof.aw_opening, &mut proof.saw_opening);
+        let mut verifier = Verifier::<F, P, PC>::new(b"external_openings");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        let verify = |proof: &Proof<F, PC>| {
+            verifier.verify_with_aux_data(proof, &vk, &public_inputs, aux_data)
+        };
+        assert!(verify(&proof).is_err());
+        let [aw_claim, saw_claim] = verifier
+            .opening_claims(&proof, &vk, &public_inputs, aux_data)
+            .unwrap();
+
+        // Recompute the polynomials opened by the prover.
+        let prover_key = prover.prover_key.as_ref().unwrap();
+        let domain = key_domain::<F>(prover_key.n).unwrap();
+        let mut transcript = prover.preprocessed_transcript.clone();
+        let QuotientChallenges {
+            beta,
+            gamma,
+            quotient: [alpha, range, logic, fixed_base, var_base],
+        } = checkpoint.replay(&mut transcript);
+        let z_challenge = transcript.challenge_scalar(b"z");
+        let ProvingCheckpoint {
+            w_l_poly,
+            w_r_poly,
+            w_o_poly,
+            w_4_poly,
+            z_poly,
+            t_polys,
+            ..
+        } = &checkpoint;
+        let (lin_poly, _) = linearisation_poly::compute::<F, P>(
+            &domain,
+            prover_key,
+            &alpha,
+            &beta,
+            &gamma,
+            &range,
+            &logic,
+            &fixed_base,
+            &var_base,
+            &z_challenge,
+            w_l_poly,
+            w_r_poly,
+            w_o_poly,
+            w_4_poly,
+            &t_polys[0],
+            &t_polys[1],
+            &t_polys[2],
+            &t_polys[3],
+            z_poly,
+        )
+        .unwrap();
+        let sigmas = &prover_key.permutation;
+
+        // Open them as an external prover would, from the claims only.
+        let open = |claim: &OpeningClaim<F, PC>,
+                    polys: Vec<DensePolynomial<F>>| {
+            let polys = polys
+                .into_iter()
+                .enumerate()
+                .map(|(i, poly)| {
+                    LabeledPolynomial::new(i.to_string(), poly, None, None)
+                })
+                .collect::<Vec<_>>();
+            let commitments = claim
+                .commitments
+                .iter()
+                .enumerate()
+                .map(|(i, commitment)| {
+                    let label = i.to_string();
+                    LabeledCommitment::new(label, commitment.clone(), None)
+                })
+                .collect::<Vec<_>>();
+            for (poly, evaluation) in polys.iter().zip(&claim.evaluations) {
+                assert_eq!(poly.evaluate(&claim.point), *evaluation);
+            }
+            let rands = polys
+                .iter()
+                .map(|_| PC::Randomness::empty())
+                .collect::<Vec<_>>();
+            PC::open(
+                &ck,
+                &polys,
+                &commitments,
+                &claim.point,
+                claim.challenge,
+                &rands,
+                None,
+            )
+            .unwrap()
+        };
+        assert_eq!(aw_claim.point, z_challenge);
+        proof.aw_opening = open(
+            &aw_claim,
+            vec![
+                lin_poly,
+                sigmas.left_sigma.0.clone(),
+                sigmas.right_sigma.0.clone(),
+                sigmas.out_sigma.0.clone(),
+                w_l_poly.clone(),
+                w_r_poly.clone(),
+                w_o_poly.clone(),
+                w_4_poly.clone(),
+            ],
+        );
+        proof.saw_opening = open(
+            &saw_claim,
+            vec![
+                z_poly.clone(),
+                w_l_poly.clone(),
+                w_r_poly.clone(),
+                w_4_poly.clone(),
+            ],
+        );
+        assert!(verify(&proof).is_ok());
+    }
+
+    fn test_insufficient_srs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let x = composer.add_input(F::from(200u64));
+            composer.range_gate(x, 8);
+        };
+        let mut prover = Prover::<F, P, PC>::new(b"srs");
+        gadget(prover.mut_cs());
+        let n = prover.circuit_size().next_power_of_two();
+
+        // The commit key just fits the circuit.
+        let (ck, vk) = PC::trim(&u_params, n - 1, 0, None).unwrap();
+        assert_eq!(PC::max_supported_degree(&ck), n - 1);
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+        let mut verifier = Verifier::<F, P, PC>::new(b"srs");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // The commit key is too small for the circuit.
+        let (ck, _) = PC::trim(&u_params, n / 2 - 1, 0, None).unwrap();
+        let available = PC::max_supported_degree(&ck);
+        let mut prover = Prover::<F, P, PC>::new(b"srs");
+        gadget(prover.mut_cs());
+        assert!(matches!(
+            prover.prove(&ck),
+            Err(Error::InsufficientSRS { required, available: found })
+                if required == n - 1 && found == available
+        ));
+    }
+
+    fn test_self_check<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+        let config = ProverConfig::<F, PC>::new().self_check(vk);
+
+        // 2 * 3 = c, with c matching the public input 6
+        let gadget = |composer: &mut StandardComposer<F, P>, c: u64| {
+            let a = composer.add_input(F::from(2u64));
+            let b = composer.add_input(F::from(3u64));
+            let c = composer.add_input(F::from(c));
+            composer.arithmetic_gate(|gate| {
+                gate.witness(a, b, Some(c)).mul(F::one())
+            });
+            composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+        };
+
+        let mut prover = Prover::<F, P, PC>::with_config(b"self", config);
+        gadget(prover.mut_cs(), 6);
+        assert!(prover.prove(&ck).is_ok());
+        gadget(prover.mut_cs(), 6);
+        assert!(prover.prove_with_context(&ck, b"context").is_ok());
+
+        // An unsatisfied gate still yields a proof, which fails the check.
+        gadget(prover.mut_cs(), 7);
+        assert!(matches!(prover.prove(&ck), Err(Error::SelfCheckFailed)));
+
+        let mut prover = Prover::<F, P, PC>::new(b"self");
+        gadget(prover.mut_cs(), 7);
+        assert!(prover.prove(&ck).is_ok());
+    }
+
+    /// [`CosetFft`] forwarding to [`ArkworksFft`], counting its calls.
+    #[derive(Clone, Default)]
+    struct PassThroughFft(Arc<AtomicUsize>);
+
+    impl<F> CosetFft<F> for PassThroughFft
+    where
+        F: FftField,
+    {
+        fn coset_fft(
+            &self,
+            domain: &GeneralEvaluationDomain<F>,
+            coeffs: &[F],
+        ) -> Vec<F> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            ArkworksFft.coset_fft(domain, coeffs)
+        }
+
+        fn coset_ifft(
+            &self,
+            domain: &GeneralEvaluationDomain<F>,
+            evals: &[F],
+        ) -> Vec<F> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            ArkworksFft.coset_ifft(domain, evals)
+        }
+    }
+
+    fn test_custom_fft<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let gadget = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(2u64));
+            let b = composer.add_input(F::from(3u64));
+            let c = composer
+                .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+            composer.constrain_to_constant(c, F::zero(), Some(-F::from(6u64)));
+        };
+
+        // Both proofs are made under the same blinding rows.
+        let factors = [F::from(7u64); NUM_BLINDING_FACTORS];
+        let fft = PassThroughFft::default();
+        let config = ProverConfig::new().fft(fft.clone());
+        let mut prover = Prover::<F, P, PC>::with_config(b"fft", config);
+        gadget(prover.mut_cs());
+        prover.mut_cs().set_blinding_factors(factors);
+        let proof = prover.prove(&ck).unwrap();
+        assert!(fft.0.load(Ordering::Relaxed) > 0);
+
+        // The pass-through backend yields the same proof as the default one.
+        let mut prover = Prover::<F, P, PC>::new(b"fft");
+        gadget(prover.mut_cs());
+        prover.mut_cs().set_blinding_factors(factors);
+        assert_eq!(to_bytes(&proof), to_bytes(&prover.prove(&ck).unwrap()));
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"fft");
+        gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        let public_inputs = verifier.mut_cs().construct_dense_pi_vec();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
+
+    fn test_quotient_poly<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"quotient");
+        let composer = prover.mut_cs();
+        let a = composer.add_input(F::from(5u64));
+        let b = composer.add_input(F::from(9u64));
+        let c = composer
+            .arithmetic_gate(|gate| gate.witness(a, b, None).mul(F::one()));
+        composer.constrain_to_constant(c, F::zero(), Some(-F::from(45u64)));
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let QuotientPoly {
+            quotient,
+            z_poly,
+            alpha,
+            beta,
+            gamma,
+            ..
+        } = prover.quotient_poly(&ck, prover_key).unwrap();
+        let domain = radix2_domain::<F>(prover.circuit_size()).unwrap();
+        assert!(quotient.degree() < 4 * domain.size());
+
+        // The challenges are the ones of the proof, whose quotient
+        // commitments are the ones of the pieces of the quotient polynomial.
+        let proof = prover
+            .prove_with_preprocessed(&ck, prover_key, PhantomData)
+            .unwrap();
+        let (t_1, t_2, t_3, t_4) =
+            prover.split_tx_poly(domain.size(), &quotient);
+        let t_comms = PC::batch_commit(&ck, &[t_1, t_2, t_3, t_4]).unwrap();
+        assert_eq!(to_bytes(&t_comms), to_bytes(&proof.t_comms));
+
+        // Recompute the numerator of the quotient polynomial from the
+        // polynomials of the circuit, with the same challenges.
+        let w_polys = prover
+            .padded_witness_scalars(&domain)
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+        let permutation = &prover_key.permutation;
+        let pi_poly = prover.compute_pi_poly(&domain);
+        let arithmetic = &prover_key.arithmetic;
+
+        for _ in 0..4 {
+            let x = F::rand(&mut OsRng);
+            let [a, b, c, d] = [0, 1, 2, 3].map(|i| w_polys[i].evaluate(&x));
+            let gate_constraints = (a * b * arithmetic.q_m.0.evaluate(&x)
+                + a * arithmetic.q_l.0.evaluate(&x)
+                + b * arithmetic.q_r.0.evaluate(&x)
+                + c * arithmetic.q_o.0.evaluate(&x)
+                + d * arithmetic.q_4.0.evaluate(&x)
+                + arithmetic.q_c.0.evaluate(&x))
+                * arithmetic.q_arith.0.evaluate(&x)
+                + pi_poly.evaluate(&x);
+
+            let z = z_poly.evaluate(&x);
+            let z_next = z_poly.evaluate(&(x * domain.element(1)));
+            let identity = (a + beta * x + gamma)
+                * (b + beta * K1::<F>() * x + gamma)
+                * (c + beta * K2::<F>() * x + gamma)
+                * (d + beta * K3::<F>() * x + gamma)
+                * z;
+            let [sigma_1, sigma_2, sigma_3, sigma_4] = [
+                &permutation.left_sigma.0,
+                &permutation.right_sigma.0,
+                &permutation.out_sigma.0,
+                &permutation.fourth_sigma.0,
+            ]
+            .map(|sigma| sigma.evaluate(&x));
+            let copy = (a + beta * sigma_1 + gamma)
+                * (b + beta * sigma_2 + gamma)
+                * (c + beta * sigma_3 + gamma)
+                * (d + beta * sigma_4 + gamma)
+                * z_next;
+            let l1 = domain.evaluate_all_lagrange_coefficients(x)[0];
+            let permutation_checks = alpha * (identity - copy)
+                + alpha.square() * l1 * (z - F::one());
+
+            assert_eq!(
+                quotient.evaluate(&x) * domain.evaluate_vanishing_polynomial(x),
+                gate_constraints + permutation_checks
+            );
+        }
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_progress_stages,
+            test_hiding_proofs,
+            test_prove_with_blinding,
+            test_missing_blinding_factors,
+            test_evaluate_wires_at,
+            test_self_check,
+            test_prove_from_polys,
+            test_checkpoint,
+            test_quotient_poly,
+            test_custom_fft,
+            test_witness_commitments,
+            test_proof_metadata,
+            test_external_openings,
+            test_insufficient_srs
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_progress_stages,
+            test_hiding_proofs,
+            test_prove_with_blinding,
+            test_missing_blinding_factors,
+            test_evaluate_wires_at,
+            test_self_check,
+            test_prove_from_polys,
+            test_checkpoint,
+            test_quotient_poly,
+            test_custom_fft,
+            test_witness_commitments,
+            test_proof_metadata,
+            test_external_openings,
+            test_insufficient_srs
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
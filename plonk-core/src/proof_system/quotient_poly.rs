@@ -13,9 +13,10 @@ use crate::{
         widget::GateConstraint,
         ProverKey,
     },
+    util::radix2_domain,
 };
 use ark_ec::TEModelParameters;
-use ark_ff::{FftField, PrimeField};
+use ark_ff::{FftField, Field, PrimeField};
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
     UVPolynomial,
@@ -29,11 +30,66 @@ use super::{
     CustomValues, WitnessValues,
 };
 
+/// FFT backend evaluating and interpolating polynomials over the coset of the
+/// `4n` domain while computing the quotient polynomial.
+///
+/// The default backend is [`ArkworksFft`]. Another one, e.g. running on a GPU
+/// or using SIMD instructions, can be selected with
+/// [`ProverConfig::fft`](super::ProverConfig::fft). It must compute the same
+/// values as [`ArkworksFft`], or the proofs will not verify.
+pub trait CosetFft<F>: Send + Sync
+where
+    F: FftField,
+{
+    /// Evaluates the polynomial with coefficients `coeffs` over the coset of
+    /// `domain`.
+    fn coset_fft(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        coeffs: &[F],
+    ) -> Vec<F>;
+
+    /// Interpolates the evaluations `evals` over the coset of `domain`,
+    /// returning the coefficients of the polynomial.
+    fn coset_ifft(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        evals: &[F],
+    ) -> Vec<F>;
+}
+
+/// [`CosetFft`] using the FFTs of the arkworks [`GeneralEvaluationDomain`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ArkworksFft;
+
+impl<F> CosetFft<F> for ArkworksFft
+where
+    F: FftField,
+{
+    fn coset_fft(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        coeffs: &[F],
+    ) -> Vec<F> {
+        domain.coset_fft(coeffs)
+    }
+
+    fn coset_ifft(
+        &self,
+        domain: &GeneralEvaluationDomain<F>,
+        evals: &[F],
+    ) -> Vec<F> {
+        domain.coset_ifft(evals)
+    }
+}
+
 /// Computes the Quotient [`DensePolynomial`] given the [`EvaluationDomain`], a
-/// [`ProverKey`], and some other info.
+/// [`ProverKey`], and some other info, running the FFTs over the coset of the
+/// `4n` domain with `fft`.
 pub fn compute<F, P>(
     domain: &GeneralEvaluationDomain<F>,
     prover_key: &ProverKey<F>,
+    fft: &dyn CosetFft<F>,
     z_poly: &DensePolynomial<F>,
     w_l_poly: &DensePolynomial<F>,
     w_r_poly: &DensePolynomial<F>,
@@ -52,41 +108,17 @@ where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
-    let domain_4n = GeneralEvaluationDomain::<F>::new(4 * domain.size())
-        .ok_or(Error::InvalidEvalDomainSize {
-        log_size_of_group: (4 * domain.size()).trailing_zeros(),
-        adicity:
-            <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-    })?;
-
-    let mut z_eval_4n = domain_4n.coset_fft(z_poly);
-    z_eval_4n.push(z_eval_4n[0]);
-    z_eval_4n.push(z_eval_4n[1]);
-    z_eval_4n.push(z_eval_4n[2]);
-    z_eval_4n.push(z_eval_4n[3]);
-
-    let mut wl_eval_4n = domain_4n.coset_fft(w_l_poly);
-    wl_eval_4n.push(wl_eval_4n[0]);
-    wl_eval_4n.push(wl_eval_4n[1]);
-    wl_eval_4n.push(wl_eval_4n[2]);
-    wl_eval_4n.push(wl_eval_4n[3]);
-
-    let mut wr_eval_4n = domain_4n.coset_fft(w_r_poly);
-    wr_eval_4n.push(wr_eval_4n[0]);
-    wr_eval_4n.push(wr_eval_4n[1]);
-    wr_eval_4n.push(wr_eval_4n[2]);
-    wr_eval_4n.push(wr_eval_4n[3]);
-
-    let wo_eval_4n = domain_4n.coset_fft(w_o_poly);
-
-    let mut w4_eval_4n = domain_4n.coset_fft(w_4_poly);
-    w4_eval_4n.push(w4_eval_4n[0]);
-    w4_eval_4n.push(w4_eval_4n[1]);
-    w4_eval_4n.push(w4_eval_4n[2]);
-    w4_eval_4n.push(w4_eval_4n[3]);
+    let domain_4n = radix2_domain::<F>(4 * domain.size())?;
+
+    let z_eval_4n = coset_fft_with_next(fft, &domain_4n, z_poly);
+    let wl_eval_4n = coset_fft_with_next(fft, &domain_4n, w_l_poly);
+    let wr_eval_4n = coset_fft_with_next(fft, &domain_4n, w_r_poly);
+    let wo_eval_4n = fft.coset_fft(&domain_4n, w_o_poly);
+    let w4_eval_4n = coset_fft_with_next(fft, &domain_4n, w_4_poly);
 
     let gate_constraints = compute_gate_constraint_satisfiability::<F, P>(
         domain,
+        fft,
         *range_challenge,
         *logic_challenge,
         *fixed_base_challenge,
@@ -121,14 +153,116 @@ where
         .collect::<Vec<_>>();
 
     Ok(DensePolynomial::from_coefficients_vec(
-        domain_4n.coset_ifft(&quotient),
+        fft.coset_ifft(&domain_4n, &quotient),
     ))
 }
 
+/// Evaluates `poly` over the coset of `domain_4n`, followed by the evaluations
+/// at the first four points again so that the evaluation at the next row of
+/// the circuit, four points further, can be read at every point.
+fn coset_fft_with_next<F>(
+    fft: &dyn CosetFft<F>,
+    domain_4n: &GeneralEvaluationDomain<F>,
+    poly: &DensePolynomial<F>,
+) -> Vec<F>
+where
+    F: FftField,
+{
+    let mut evals = fft.coset_fft(domain_4n, poly);
+    evals.extend_from_within(..4);
+    evals
+}
+
+/// Contributions of each type of gate to the gate constraints part of the
+/// quotient polynomial, at a single point of the coset of the `4n` domain.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GateContributions<F>
+where
+    F: Field,
+{
+    /// Contribution of the arithmetic gates.
+    pub arithmetic: F,
+
+    /// Contribution of the range gates.
+    pub range: F,
+
+    /// Contribution of the logic gates.
+    pub logic: F,
+
+    /// Contribution of the fixed base scalar multiplication gates.
+    pub fixed_base: F,
+
+    /// Contribution of the curve addition gates.
+    pub curve_addition: F,
+
+    /// Contribution of the public inputs.
+    pub pi: F,
+}
+
+impl<F> GateContributions<F>
+where
+    F: Field,
+{
+    /// Returns the sum of the contributions, which is the value of the gate
+    /// constraints part of the quotient polynomial.
+    pub fn sum(&self) -> F {
+        self.arithmetic
+            + self.pi
+            + self.range
+            + self.logic
+            + self.fixed_base
+            + self.curve_addition
+    }
+}
+
+/// Computes, for every point of the coset of the `4n` domain, the
+/// contribution of each type of gate to the gate constraints part of the
+/// quotient polynomial.
+///
+/// This is meant to find which gate makes the quotient polynomial wrong: on
+/// an unsatisfied circuit, the culprit is the gate whose contribution breaks
+/// the divisibility by the vanishing polynomial.
+#[cfg(any(test, feature = "trace"))]
+pub(crate) fn compute_gate_contributions<F, P>(
+    domain: &GeneralEvaluationDomain<F>,
+    prover_key: &ProverKey<F>,
+    w_l_poly: &DensePolynomial<F>,
+    w_r_poly: &DensePolynomial<F>,
+    w_o_poly: &DensePolynomial<F>,
+    w_4_poly: &DensePolynomial<F>,
+    public_inputs_poly: &DensePolynomial<F>,
+    range_challenge: F,
+    logic_challenge: F,
+    fixed_base_challenge: F,
+    var_base_challenge: F,
+) -> Result<Vec<GateContributions<F>>, Error>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    let domain_4n = radix2_domain::<F>(4 * domain.size())?;
+    map_gate_contributions::<F, P, _, _>(
+        domain,
+        &ArkworksFft,
+        range_challenge,
+        logic_challenge,
+        fixed_base_challenge,
+        var_base_challenge,
+        prover_key,
+        &coset_fft_with_next(&ArkworksFft, &domain_4n, w_l_poly),
+        &coset_fft_with_next(&ArkworksFft, &domain_4n, w_r_poly),
+        &domain_4n.coset_fft(w_o_poly),
+        &coset_fft_with_next(&ArkworksFft, &domain_4n, w_4_poly),
+        public_inputs_poly,
+        |contributions| contributions,
+    )
+}
+
 /// Computes contribution to the quotient polynomial that ensures
 /// the gate constraints are satisfied.
 fn compute_gate_constraint_satisfiability<F, P>(
     domain: &GeneralEvaluationDomain<F>,
+    fft: &dyn CosetFft<F>,
     range_challenge: F,
     logic_challenge: F,
     fixed_base_challenge: F,
@@ -144,15 +278,48 @@ where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
-    let domain_4n = GeneralEvaluationDomain::<F>::new(4 * domain.size())
-        .ok_or(Error::InvalidEvalDomainSize {
-        log_size_of_group: (4 * domain.size()).trailing_zeros(),
-        adicity:
-            <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-    })?;
-    let pi_eval_4n = domain_4n.coset_fft(pi_poly);
-
-    // TODO Eliminate contribution of unused gates
+    map_gate_contributions::<F, P, _, _>(
+        domain,
+        fft,
+        range_challenge,
+        logic_challenge,
+        fixed_base_challenge,
+        var_base_challenge,
+        prover_key,
+        wl_eval_4n,
+        wr_eval_4n,
+        wo_eval_4n,
+        w4_eval_4n,
+        pi_poly,
+        |contributions| contributions.sum(),
+    )
+}
+
+/// Computes the [`GateContributions`] at every point of the coset of the `4n`
+/// domain, mapped with `map`.
+fn map_gate_contributions<F, P, T, M>(
+    domain: &GeneralEvaluationDomain<F>,
+    fft: &dyn CosetFft<F>,
+    range_challenge: F,
+    logic_challenge: F,
+    fixed_base_challenge: F,
+    var_base_challenge: F,
+    prover_key: &ProverKey<F>,
+    wl_eval_4n: &[F],
+    wr_eval_4n: &[F],
+    wo_eval_4n: &[F],
+    w4_eval_4n: &[F],
+    pi_poly: &DensePolynomial<F>,
+    map: M,
+) -> Result<Vec<T>, Error>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    M: Fn(GateContributions<F>) -> T,
+{
+    let domain_4n = radix2_domain::<F>(4 * domain.size())?;
+    let pi_eval_4n = fft.coset_fft(&domain_4n, pi_poly);
+
     Ok((0..domain_4n.size())
         .map(|i| {
             let wit_vals = WitnessValues {
@@ -176,40 +343,62 @@ where
             let arithmetic =
                 prover_key.arithmetic.compute_quotient_i(i, wit_vals);
 
-            let range = Range::quotient_term(
-                prover_key.range_selector.1[i],
-                range_challenge,
-                wit_vals,
-                RangeVals::from_evaluations(&custom_vals),
+            let range = prover_key.range_selector.as_ref().map_or(
+                F::zero(),
+                |(_, selector)| {
+                    Range::quotient_term(
+                        selector[i],
+                        range_challenge,
+                        wit_vals,
+                        RangeVals::from_evaluations(&custom_vals),
+                    )
+                },
             );
 
-            let logic = Logic::quotient_term(
-                prover_key.logic_selector.1[i],
-                logic_challenge,
-                wit_vals,
-                LogicVals::from_evaluations(&custom_vals),
+            let logic = prover_key.logic_selector.as_ref().map_or(
+                F::zero(),
+                |(_, selector)| {
+                    Logic::quotient_term(
+                        selector[i],
+                        logic_challenge,
+                        wit_vals,
+                        LogicVals::from_evaluations(&custom_vals),
+                    )
+                },
             );
 
-            let fixed_base_scalar_mul =
-                FixedBaseScalarMul::<_, P>::quotient_term(
-                    prover_key.fixed_group_add_selector.1[i],
-                    fixed_base_challenge,
-                    wit_vals,
-                    FBSMVals::from_evaluations(&custom_vals),
-                );
-
-            let curve_addition = CurveAddition::<_, P>::quotient_term(
-                prover_key.variable_group_add_selector.1[i],
-                var_base_challenge,
-                wit_vals,
-                CAVals::from_evaluations(&custom_vals),
-            );
+            let fixed_base_scalar_mul = prover_key
+                .fixed_group_add_selector
+                .as_ref()
+                .map_or(F::zero(), |(_, selector)| {
+                    FixedBaseScalarMul::<_, P>::quotient_term(
+                        selector[i],
+                        fixed_base_challenge,
+                        wit_vals,
+                        FBSMVals::from_evaluations(&custom_vals),
+                    )
+                });
 
-            (arithmetic + pi_eval_4n[i])
-                + range
-                + logic
-                + fixed_base_scalar_mul
-                + curve_addition
+            let curve_addition = prover_key
+                .variable_group_add_selector
+                .as_ref()
+                .map_or(F::zero(), |(_, selector)| {
+                    CurveAddition::<_, P>::quotient_term(
+                        selector[i],
+                        var_base_challenge,
+                        wit_vals,
+                        CAVals::from_evaluations(&custom_vals),
+                    )
+                });
+
+            map(GateContributions {
+                arithmetic,
+                range,
+                logic,
+                fixed_base: fixed_base_scalar_mul,
+                curve_addition,
+                pi: pi_eval_4n[i],
+            })
         })
         .collect())
 }
@@ -231,15 +420,8 @@ fn compute_permutation_checks<F>(
 where
     F: PrimeField,
 {
-    let domain_4n = GeneralEvaluationDomain::<F>::new(4 * domain.size())
-        .ok_or(Error::InvalidEvalDomainSize {
-        log_size_of_group: (4 * domain.size()).trailing_zeros(),
-        adicity:
-            <<F as FftField>::FftParams as ark_ff::FftParameters>::TWO_ADICITY,
-    })?;
-    let l1_poly_alpha =
-        compute_first_lagrange_poly_scaled(domain, alpha.square());
-    let l1_alpha_sq_evals = domain_4n.coset_fft(&l1_poly_alpha.coeffs);
+    let domain_4n = radix2_domain::<F>(4 * domain.size())?;
+    let alpha_sq = alpha.square();
 
     Ok((0..domain_4n.size())
         .map(|i| {
@@ -252,7 +434,7 @@ where
                 z_eval_4n[i],
                 z_eval_4n[i + 4],
                 alpha,
-                l1_alpha_sq_evals[i],
+                prover_key.l1_coset_4n()[i] * alpha_sq,
                 beta,
                 gamma,
             )
@@ -261,7 +443,7 @@ where
 }
 
 /// Computes the first lagrange polynomial with the given `scale` over `domain`.
-fn compute_first_lagrange_poly_scaled<F>(
+pub(crate) fn compute_first_lagrange_poly_scaled<F>(
     domain: &GeneralEvaluationDomain<F>,
     scale: F,
 ) -> DensePolynomial<F>
@@ -273,3 +455,143 @@ where
     domain.ifft_in_place(&mut x_evals);
     DensePolynomial::from_coefficients_vec(x_evals)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, commitment::HomomorphicCommitment, proof_system::Prover,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use rand::rngs::OsRng;
+
+    fn test_gate_contributions_sum<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"contributions");
+        let composer = prover.mut_cs();
+        let a = composer.add_input(F::from(5u64));
+        let b = composer.add_input(F::from(9u64));
+        composer.arithmetic_gate(|gate| {
+            gate.witness(a, b, None).mul(F::one()).pi(F::from(7u64))
+        });
+        composer.range_gate(a, 8);
+        composer.xor_gate(a, b, 8);
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let domain = radix2_domain::<F>(prover.circuit_size()).unwrap();
+        let [w_l_poly, w_r_poly, w_o_poly, w_4_poly] = prover
+            .padded_witness_scalars(&domain)
+            .map(|w| DensePolynomial::from_coefficients_vec(domain.ifft(&w)));
+        let pi_poly = DensePolynomial::from_coefficients_vec(
+            domain.ifft(&prover.cs.construct_dense_pi_vec()),
+        );
+        let challenges = [5u64, 6, 7, 8].map(F::from);
+
+        let contributions = compute_gate_contributions::<F, P>(
+            &domain,
+            prover_key,
+            &w_l_poly,
+            &w_r_poly,
+            &w_o_poly,
+            &w_4_poly,
+            &pi_poly,
+            challenges[0],
+            challenges[1],
+            challenges[2],
+            challenges[3],
+        )
+        .unwrap();
+
+        let domain_4n = radix2_domain::<F>(4 * domain.size()).unwrap();
+        let combined = compute_gate_constraint_satisfiability::<F, P>(
+            &domain,
+            &ArkworksFft,
+            challenges[0],
+            challenges[1],
+            challenges[2],
+            challenges[3],
+            prover_key,
+            &coset_fft_with_next(&ArkworksFft, &domain_4n, &w_l_poly),
+            &coset_fft_with_next(&ArkworksFft, &domain_4n, &w_r_poly),
+            &domain_4n.coset_fft(&w_o_poly),
+            &coset_fft_with_next(&ArkworksFft, &domain_4n, &w_4_poly),
+            &pi_poly,
+        )
+        .unwrap();
+
+        assert_eq!(contributions.len(), combined.len());
+        for (contribution, value) in contributions.iter().zip(&combined) {
+            assert_eq!(contribution.sum(), *value);
+        }
+
+        // Only the gates used by the circuit contribute.
+        let any = |term: fn(&GateContributions<F>) -> F| {
+            contributions.iter().any(|c| !term(c).is_zero())
+        };
+        assert!(any(|c| c.arithmetic));
+        assert!(any(|c| c.range));
+        assert!(any(|c| c.logic));
+        assert!(any(|c| c.pi));
+        assert!(!any(|c| c.fixed_base));
+        assert!(!any(|c| c.curve_addition));
+    }
+
+    fn test_cached_first_lagrange<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"first_lagrange");
+        prover.mut_cs().add_dummy_constraints();
+        let domain = radix2_domain::<F>(prover.circuit_size()).unwrap();
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        // The cached evaluations scaled by `alpha^2` are the evaluations of
+        // the scaled polynomial.
+        let domain_4n = radix2_domain::<F>(4 * domain.size()).unwrap();
+        for alpha in [3u64, 11].map(F::from) {
+            let l1_alpha_sq_evals = domain_4n.coset_fft(
+                &compute_first_lagrange_poly_scaled(&domain, alpha.square()),
+            );
+            let cached = prover_key
+                .l1_coset_4n()
+                .evals
+                .iter()
+                .map(|eval| *eval * alpha.square())
+                .collect::<Vec<_>>();
+            assert_eq!(cached, l1_alpha_sq_evals);
+        }
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [test_gate_contributions_sum, test_cached_first_lagrange],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [test_gate_contributions_sum, test_cached_first_lagrange],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
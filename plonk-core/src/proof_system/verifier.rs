@@ -10,13 +10,40 @@
 use crate::{
     commitment::HomomorphicCommitment,
     constraint_system::StandardComposer,
-    error::Error,
-    proof_system::{widget::VerifierKey as PlonkVerifierKey, Proof},
+    error::{to_pc_error, Error},
+    proof_system::{
+        proof::{OpeningClaim, PublicInputs},
+        widget::{GateType, VerifierKey as PlonkVerifierKey},
+        BarycentricPublicInputs, Proof, PublicInputCommitment,
+        PublicInputOpening,
+    },
+    transcript::TranscriptProtocol,
 };
 use ark_ec::TEModelParameters;
 use ark_ff::PrimeField;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
+    UVPolynomial,
+};
 use core::marker::PhantomData;
 use merlin::Transcript;
+use rand::RngCore;
+
+/// Metadata about a [`Proof`] that was successfully verified.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationReport {
+    /// Size of the evaluation domain of the circuit.
+    pub domain_size: usize,
+
+    /// Number of non-zero public inputs the proof was verified against.
+    ///
+    /// Public inputs equal to zero cannot be told apart from the absence of
+    /// public inputs, so they are not counted.
+    pub num_public_inputs: usize,
+
+    /// Types of gates used by the circuit.
+    pub gate_types_present: Vec<GateType>,
+}
 
 /// Abstraction structure designed verify [`Proof`]s.
 pub struct Verifier<F, P, PC>
@@ -38,6 +65,21 @@ where
     /// not copied, then the verification procedure will modify the transcript,
     /// making it unusable for future proofs.
     pub preprocessed_transcript: Transcript,
+
+    /// Maximum number of public inputs accepted when verifying a [`Proof`].
+    ///
+    /// The number of public inputs is always bounded by the size of the
+    /// evaluation domain, this allows setting a tighter limit so that
+    /// verification can bail out early on oversized inputs.
+    pub max_public_inputs: Option<usize>,
+
+    /// Types of gates a [`Proof`] may use to be accepted, if restricted.
+    ///
+    /// Verification fails with [`Error::DisallowedGateType`] when the
+    /// verifier key commits to the selector of any other gate type, e.g. to
+    /// reject proofs using a gate deprecated by a protocol upgrade.
+    /// [`GateType::Arithmetic`] is used by every circuit.
+    pub allowed_gate_types: Option<Vec<GateType>>,
 }
 
 impl<F, P, PC> Verifier<F, P, PC>
@@ -52,15 +94,35 @@ where
             verifier_key: None,
             cs: StandardComposer::new(),
             preprocessed_transcript: Transcript::new(label),
+            max_public_inputs: None,
+            allowed_gate_types: None,
         }
     }
 
+    /// Creates a new `Verifier` instance whose transcript is bound to the
+    /// circuit identified by `circuit_id`.
+    ///
+    /// Proofs only verify when the prover and the verifier use the same
+    /// `label` and `circuit_id`, so that proofs for circuits sharing the same
+    /// structure, and therefore the same keys, cannot be used for one
+    /// another. The identifier can be, for instance, a hash of the verifier
+    /// key together with a protocol version.
+    pub fn with_circuit_id(label: &'static [u8], circuit_id: &[u8]) -> Self {
+        let mut verifier = Self::new(label);
+        verifier
+            .preprocessed_transcript
+            .append_circuit_id(circuit_id);
+        verifier
+    }
+
     /// Creates a new `Verifier` instance with some expected size.
     pub fn with_expected_size(label: &'static [u8], size: usize) -> Self {
         Self {
             verifier_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            max_public_inputs: None,
+            allowed_gate_types: None,
         }
     }
 
@@ -100,18 +162,483 @@ where
         self.preprocessed_transcript.append_message(label, message);
     }
 
+    /// Returns the key of the preprocessed circuit, or
+    /// [`Error::CircuitNotPreprocessed`] if [`Verifier::preprocess`] has not
+    /// been called yet.
+    ///
+    /// Fails with [`Error::DisallowedGateType`] if the key uses a gate type
+    /// which is not in `allowed_gate_types`.
+    fn plonk_verifier_key(&self) -> Result<&PlonkVerifierKey<F, PC>, Error> {
+        let verifier_key = self
+            .verifier_key
+            .as_ref()
+            .ok_or(Error::CircuitNotPreprocessed)?;
+        if let Some(allowed_gate_types) = &self.allowed_gate_types {
+            if let Some(gate_type) = verifier_key
+                .gate_types()
+                .into_iter()
+                .find(|gate_type| !allowed_gate_types.contains(gate_type))
+            {
+                return Err(Error::DisallowedGateType { gate_type });
+            }
+        }
+        Ok(verifier_key)
+    }
+
+    /// Checks that `public_inputs` does not exceed the configured
+    /// `max_public_inputs`.
+    fn check_public_inputs_limit(
+        &self,
+        public_inputs: &[F],
+    ) -> Result<(), Error> {
+        match self.max_public_inputs {
+            Some(limit) if public_inputs.len() > limit => {
+                Err(Error::TooManyPublicInputs {
+                    count: public_inputs.len(),
+                    limit,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Verifies a [`Proof`] using `pc_verifier_key` and `public_inputs`.
+    ///
+    /// With the `tracing` feature, the verification enters a span for each
+    /// of its phases: `batch_openings` replays the transcript and computes
+    /// the batch openings, including the `linearisation` multi-scalar
+    /// multiplication, and `opening_checks` checks them, with a `PC::check`
+    /// span for each check. A subscriber timing these spans shows where the
+    /// verification time goes.
     pub fn verify(
         &self,
         proof: &Proof<F, PC>,
         pc_verifier_key: &PC::VerifierKey,
         public_inputs: &[F],
     ) -> Result<(), Error> {
+        self.verify_with_context(proof, pc_verifier_key, public_inputs, &[])
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], checking that it was
+    /// bound to the application `context` by
+    /// [`Prover::prove_with_context`](super::Prover::prove_with_context).
+    ///
+    /// Verification fails if the proof was created with a different
+    /// `context`.
+    pub fn verify_with_context(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        context: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_bound(proof, pc_verifier_key, public_inputs, context, &[])
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], checking that it was
+    /// bound to `aux_data` by
+    /// [`Prover::prove_with_aux_data`](super::Prover::prove_with_aux_data).
+    ///
+    /// Verification fails if the proof was created with different
+    /// `aux_data`. The data itself is not checked here: it is up to the
+    /// caller to check it out-of-band, e.g. that a timestamp is recent.
+    pub fn verify_with_aux_data(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        aux_data: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_bound(proof, pc_verifier_key, public_inputs, &[], aux_data)
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], checking that it was
+    /// bound to both the application `context` and `aux_data` by
+    /// [`Prover::prove_bound`](super::Prover::prove_bound).
+    ///
+    /// Either of them may be empty, in which case this is the same as
+    /// [`Verifier::verify_with_aux_data`] or
+    /// [`Verifier::verify_with_context`].
+    pub fn verify_bound(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        context: &[u8],
+        aux_data: &[u8],
+    ) -> Result<(), Error> {
+        self.check_public_inputs_limit(public_inputs)?;
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append_context(context);
         proof.verify::<P>(
-            self.verifier_key.as_ref().unwrap(),
+            self.plonk_verifier_key()?,
+            &mut transcript,
+            pc_verifier_key,
+            PublicInputs::Values(public_inputs),
+            aux_data,
+        )
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], as the next proof of
+    /// a chain of proofs whose state so far is `incoming`, returning the
+    /// state to verify the next proof of the chain from.
+    ///
+    /// The proof must have been made by
+    /// [`Prover::prove_chained`](super::Prover::prove_chained) from the same
+    /// state, so that a chain only verifies when its proofs are given in the
+    /// order they were made in.
+    pub fn verify_chained(
+        &self,
+        mut incoming: Transcript,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+    ) -> Result<Transcript, Error> {
+        let link = incoming.chain_link();
+        self.verify_with_context(proof, pc_verifier_key, public_inputs, &link)?;
+        incoming.append(b"chained_proof", proof);
+        Ok(incoming)
+    }
+
+    /// Returns the [`OpeningClaim`]s the opening proofs `aw_opening` and
+    /// `saw_opening` of `proof` must satisfy, in this order, for `proof` to
+    /// verify with [`Verifier::verify_with_aux_data`] against
+    /// `public_inputs` and `aux_data`.
+    ///
+    /// This lets the opening proofs be computed apart from the
+    /// [`Prover`](super::Prover), e.g. by a specialized or hardware prover
+    /// of the commitment scheme. The claims only depend on the commitments
+    /// and evaluations of `proof`, whose opening proofs are ignored and may
+    /// be placeholders until they are replaced by the ones computed for the
+    /// claims. All of the other checks of [`Verifier::verify`] are run.
+    pub fn opening_claims(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        aux_data: &[u8],
+    ) -> Result<[OpeningClaim<F, PC>; 2], Error> {
+        self.check_public_inputs_limit(public_inputs)?;
+        proof.opening_claims::<P>(
+            self.plonk_verifier_key()?,
             &mut self.preprocessed_transcript.clone(),
             pc_verifier_key,
-            public_inputs,
+            PublicInputs::Values(public_inputs),
+            aux_data,
+        )
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], reading the public
+    /// outputs of the circuit from `public_outputs` instead of
+    /// `public_inputs`.
+    ///
+    /// `public_outputs` are the values returned by
+    /// [`StandardComposer::public_outputs`] on the prover side, sent along
+    /// with the proof. They are written into `public_inputs` at the positions
+    /// of the public outputs of the circuit of this `Verifier`, so the
+    /// entries of `public_inputs` at those positions are ignored. Once the
+    /// proof verifies, the verifier can trust `public_outputs` to be the
+    /// values computed by the circuit.
+    pub fn verify_with_public_outputs(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        public_outputs: &[F],
+    ) -> Result<(), Error> {
+        let public_inputs =
+            self.cs.with_public_outputs(public_inputs, public_outputs)?;
+        self.verify(proof, pc_verifier_key, &public_inputs)
+    }
+
+    /// Performs a cheaper probabilistic check of a [`Proof`] bound to
+    /// `aux_data`, meant to reject invalid proofs before running the full
+    /// [`Verifier::verify_with_aux_data`].
+    ///
+    /// All of the checks of the verification are run except for the
+    /// opening proofs: only one of the two batch openings, picked at random
+    /// with `rng`, is checked. An invalid proof is therefore caught most of
+    /// the time, and a valid proof always passes.
+    ///
+    /// # Soundness
+    ///
+    /// This is an optimization for rejecting invalid proofs, not a
+    /// replacement for the verification: a proof passing the quick check
+    /// must still be checked with [`Verifier::verify_with_aux_data`].
+    pub fn quick_check<R>(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+        aux_data: &[u8],
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: RngCore,
+    {
+        self.check_public_inputs_limit(public_inputs)?;
+        proof.quick_check::<P, R>(
+            self.plonk_verifier_key()?,
+            &mut self.preprocessed_transcript.clone(),
+            pc_verifier_key,
+            PublicInputs::Values(public_inputs),
+            aux_data,
+            rng,
+        )
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], given a commitment to
+    /// the public inputs opened at the evaluation challenge of the proof
+    /// instead of the public inputs themselves.
+    ///
+    /// The proof and the opening are produced together by
+    /// [`Prover::prove_with_committed_public_inputs`], bound to the same
+    /// `aux_data`, see [`Verifier::verify_with_aux_data`].
+    ///
+    /// # Soundness
+    ///
+    /// The proof is only checked against the public inputs committed to by
+    /// `pi_opening.commitment`, so the verifier must obtain that commitment
+    /// from a source it trusts to commit to the intended public inputs. The
+    /// commitment is absorbed into the transcript before any challenge is
+    /// drawn, as by the prover, and the claimed evaluation is only used once
+    /// its opening proof has been checked against the commitment, at the
+    /// evaluation challenge derived from the proof.
+    ///
+    /// [`Prover::prove_with_committed_public_inputs`]:
+    /// super::Prover::prove_with_committed_public_inputs
+    pub fn verify_with_committed_public_inputs(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        pi_opening: &PublicInputOpening<F, PC>,
+        aux_data: &[u8],
+    ) -> Result<(), Error> {
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append(b"pi_comm", &pi_opening.commitment);
+        proof.verify::<P>(
+            self.plonk_verifier_key()?,
+            &mut transcript,
+            pc_verifier_key,
+            PublicInputs::Committed(pi_opening),
+            aux_data,
+        )
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], against the public
+    /// inputs committed to by `commitment` under the [`PublicInputCommitment`]
+    /// `scheme`.
+    ///
+    /// With [`BarycentricPublicInputs`] as
+    /// the `scheme`, this accepts the same proofs as
+    /// [`Verifier::verify_with_aux_data`] given the same `aux_data`.
+    ///
+    /// # Soundness
+    ///
+    /// The proof is only checked against the contribution of the public
+    /// inputs returned by `scheme`, see [`PublicInputCommitment`].
+    pub fn verify_with_public_input_commitment<S>(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        scheme: &S,
+        commitment: &S::Commitment,
+        aux_data: &[u8],
+    ) -> Result<(), Error>
+    where
+        S: PublicInputCommitment<F>,
+    {
+        let contribution =
+            |z_challenge: F, domain: &GeneralEvaluationDomain<F>| {
+                scheme.verify_contribution(commitment, z_challenge, domain)
+            };
+        proof.verify::<P>(
+            self.plonk_verifier_key()?,
+            &mut self.preprocessed_transcript.clone(),
+            pc_verifier_key,
+            PublicInputs::Contribution(&contribution),
+            aux_data,
+        )
+    }
+
+    /// Returns the evaluation challenge `z` of a [`Proof`] bound to
+    /// `aux_data`, together with the evaluation at `z` of the public input
+    /// polynomial of `public_inputs`.
+    ///
+    /// The pair can be computed once, by a party trusted with the public
+    /// inputs, and then given to
+    /// [`Verifier::verify_with_public_input_evaluations`].
+    ///
+    /// # Cost
+    ///
+    /// Nothing is amortized across proofs: the challenge `z` is drawn from
+    /// the commitments of `proof`, so each proof needs its own evaluation,
+    /// which costs a full barycentric evaluation over `public_inputs`. The
+    /// work is only moved to the party computing the pair.
+    pub fn public_input_evaluation(
+        &self,
+        proof: &Proof<F, PC>,
+        public_inputs: &[F],
+        aux_data: &[u8],
+    ) -> Result<(F, F), Error> {
+        self.check_public_inputs_limit(public_inputs)?;
+        let domain = self.plonk_verifier_key()?.domain()?;
+        let z_challenge = proof.evaluation_challenge(
+            &mut self.preprocessed_transcript.clone(),
+            aux_data,
+        );
+        let scheme = BarycentricPublicInputs;
+        let eval = PublicInputCommitment::verify_contribution(
+            &scheme,
+            &scheme.commit(public_inputs),
+            z_challenge,
+            &domain,
+        )?;
+        Ok((z_challenge, eval))
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], given precomputed
+    /// evaluations of the public input polynomial instead of the public
+    /// inputs themselves.
+    ///
+    /// `evaluations` are `(point, evaluation)` pairs, as returned by
+    /// [`Verifier::public_input_evaluation`], among which the one at the
+    /// evaluation challenge of the proof is used. This skips the barycentric
+    /// evaluation over the public inputs, whose cost grows with their number,
+    /// but only by delegating it: the evaluation challenge is specific to
+    /// each proof, so the evaluations cannot be reused for another proof.
+    /// Returns [`Error::MissingPublicInputEvaluation`] if none of the points
+    /// is the evaluation challenge.
+    ///
+    /// # Soundness
+    ///
+    /// The evaluations are trusted as given: a proof verifies against any
+    /// public inputs whose evaluation is supplied for it, so they must come
+    /// from a source trusted to compute them from the intended public inputs.
+    /// When no such source is available, the public inputs can instead be
+    /// committed to and opened by the prover, and checked with
+    /// [`Verifier::verify_with_committed_public_inputs`].
+    pub fn verify_with_public_input_evaluations(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        evaluations: &[(F, F)],
+        aux_data: &[u8],
+    ) -> Result<(), Error> {
+        let contribution = |z_challenge: F, _: &GeneralEvaluationDomain<F>| {
+            evaluations
+                .iter()
+                .find(|(point, _)| *point == z_challenge)
+                .map(|(_, eval)| *eval)
+                .ok_or(Error::MissingPublicInputEvaluation)
+        };
+        proof.verify::<P>(
+            self.plonk_verifier_key()?,
+            &mut self.preprocessed_transcript.clone(),
+            pc_verifier_key,
+            PublicInputs::Contribution(&contribution),
+            aux_data,
+        )
+    }
+
+    /// Commits to the public input polynomial interpolating `public_inputs`
+    /// over the evaluation domain of the circuit, without hiding.
+    ///
+    /// This is needed when the verifier is given a commitment to the public
+    /// inputs instead of their values, as verified by
+    /// [`Verifier::verify_with_committed_public_inputs`]: a verifier which
+    /// knows the public inputs, or some of them, can recompute their
+    /// commitment and compare it, or combine it homomorphically, with the
+    /// supplied one. For the same public inputs, the commitment is the one
+    /// returned by [`Prover::prove_with_committed_public_inputs`].
+    ///
+    /// # Security
+    ///
+    /// The commitment is deterministic, so it does not keep the public
+    /// inputs secret: anyone can check a guess of them by recomputing it.
+    ///
+    /// [`Prover::prove_with_committed_public_inputs`]:
+    /// super::Prover::prove_with_committed_public_inputs
+    pub fn commit_public_inputs(
+        &self,
+        commit_key: &PC::CommitterKey,
+        public_inputs: &[F],
+    ) -> Result<PC::Commitment, Error> {
+        self.check_public_inputs_limit(public_inputs)?;
+        let domain = self.plonk_verifier_key()?.domain()?;
+        if public_inputs.len() > domain.size() {
+            return Err(Error::TooManyPublicInputs {
+                count: public_inputs.len(),
+                limit: domain.size(),
+            });
+        }
+        let pi_poly =
+            DensePolynomial::from_coefficients_vec(domain.ifft(public_inputs));
+        let mut commitments = PC::batch_commit(commit_key, &[pi_poly])
+            .map_err(to_pc_error::<F, PC>)?;
+        Ok(commitments.remove(0))
+    }
+
+    /// Verifies a [`Proof`] like [`Verifier::verify`], returning a
+    /// [`VerificationReport`] describing what was verified.
+    pub fn verify_with_report(
+        &self,
+        proof: &Proof<F, PC>,
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[F],
+    ) -> Result<VerificationReport, Error> {
+        self.verify(proof, pc_verifier_key, public_inputs)?;
+        let plonk_verifier_key = self.plonk_verifier_key()?;
+        Ok(VerificationReport {
+            domain_size: plonk_verifier_key.domain()?.size(),
+            num_public_inputs: public_inputs
+                .iter()
+                .filter(|input| !input.is_zero())
+                .count(),
+            gate_types_present: plonk_verifier_key.gate_types(),
+        })
+    }
+
+    /// Verifies a batch of [`Proof`]s for the circuit preprocessed by this
+    /// `Verifier`, each one against its own set of `public_inputs` and its
+    /// own `aux_data`, see [`Verifier::verify_with_aux_data`].
+    ///
+    /// The evaluation domain is only built once and shared by all of the
+    /// proofs, and the opening proofs of all of them are checked at once,
+    /// combined with a random linear combination drawn from their
+    /// transcripts, see [`HomomorphicCommitment::check_multi_point`].
+    ///
+    /// Returns [`Error::BatchLengthMismatch`] if the number of public input
+    /// sets or of auxiliary data does not match the number of proofs.
+    pub fn batch_verify(
+        &self,
+        proofs: &[Proof<F, PC>],
+        pc_verifier_key: &PC::VerifierKey,
+        public_inputs: &[Vec<F>],
+        aux_data: &[&[u8]],
+    ) -> Result<(), Error> {
+        if proofs.len() != public_inputs.len() || proofs.len() != aux_data.len()
+        {
+            return Err(Error::BatchLengthMismatch {
+                proofs: proofs.len(),
+                public_inputs: public_inputs.len(),
+                aux_data: aux_data.len(),
+            });
+        }
+        for pi in public_inputs {
+            self.check_public_inputs_limit(pi)?;
+        }
+        let plonk_verifier_key = self.plonk_verifier_key()?;
+        Proof::verify_batch::<P, _>(
+            &plonk_verifier_key.domain()?,
+            plonk_verifier_key,
+            &self.preprocessed_transcript,
+            pc_verifier_key,
+            proofs.iter().zip(public_inputs).zip(aux_data).map(
+                |((proof, pi), aux_data)| {
+                    (proof, PublicInputs::Values(pi), *aux_data)
+                },
+            ),
         )
     }
 }
@@ -127,3 +654,1040 @@ where
         Verifier::new(b"plonk")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        batch_test, constraint_system::helper::*, label_polynomial,
+        proof_system::Prover, transcript::mock,
+    };
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_poly::Polynomial;
+    use ark_serialize::CanonicalSerialize;
+    use rand::rngs::OsRng;
+
+    /// Adds a logic gate.
+    fn logic_gadget<F, P>(composer: &mut StandardComposer<F, P>)
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+    {
+        let a = composer.add_input(F::from(0b1010u64));
+        let b = composer.add_input(F::from(0b0110u64));
+        let c = composer.xor_gate(a, b, 4);
+        composer.constrain_to_constant(c, F::from(0b1100u64), None);
+    }
+
+    fn test_batch_verify<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let values = [F::from(3u64), F::from(5u64), F::from(7u64)];
+        let aux_data: [&[u8]; 3] = [b"first", b"", b"third"];
+
+        // Prover
+        let mut prover = Prover::<F, P, PC>::new(b"batch");
+        let mut proofs = Vec::new();
+        let mut public_inputs = Vec::new();
+        for (i, (value, aux_data)) in values.iter().zip(aux_data).enumerate() {
+            pi_gadget(prover.mut_cs(), *value);
+            if i == 0 {
+                prover.preprocess(&ck).unwrap();
+            }
+            public_inputs.push(prover.mut_cs().construct_dense_pi_vec());
+            proofs.push(prover.prove_with_aux_data(&ck, aux_data).unwrap());
+        }
+
+        // Verifier
+        let mut verifier = Verifier::<F, P, PC>::new(b"batch");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier
+            .batch_verify(&proofs, &vk, &public_inputs, &aux_data)
+            .is_ok());
+
+        // Tamper with the public inputs of the second proof only.
+        let mut bad_public_inputs = public_inputs.clone();
+        bad_public_inputs[1] = public_inputs[2].clone();
+        assert!(verifier
+            .batch_verify(&proofs, &vk, &bad_public_inputs, &aux_data)
+            .is_err());
+        for (i, verifies) in [true, false, true].into_iter().enumerate() {
+            assert_eq!(
+                verifier
+                    .verify_with_aux_data(
+                        &proofs[i],
+                        &vk,
+                        &bad_public_inputs[i],
+                        aux_data[i],
+                    )
+                    .is_ok(),
+                verifies
+            );
+        }
+
+        // And so is the auxiliary data of a proof.
+        let bad_aux_data: [&[u8]; 3] = [b"first", b"second", b"third"];
+        assert!(verifier
+            .batch_verify(&proofs, &vk, &public_inputs, &bad_aux_data)
+            .is_err());
+
+        // Openings swapped between two proofs are rejected as well.
+        let mut swapped = proofs.clone();
+        swapped[0].aw_opening = proofs[1].aw_opening.clone();
+        swapped[1].aw_opening = proofs[0].aw_opening.clone();
+        assert!(verifier
+            .batch_verify(&swapped, &vk, &public_inputs, &aux_data)
+            .is_err());
+
+        // Every proof needs its own public inputs and auxiliary data.
+        assert!(matches!(
+            verifier.batch_verify(&proofs, &vk, &public_inputs[..2], &aux_data),
+            Err(Error::BatchLengthMismatch {
+                proofs: 3,
+                public_inputs: 2,
+                aux_data: 3,
+            })
+        ));
+        assert!(matches!(
+            verifier.batch_verify(&proofs, &vk, &public_inputs, &aux_data[..1]),
+            Err(Error::BatchLengthMismatch {
+                proofs: 3,
+                public_inputs: 3,
+                aux_data: 1,
+            })
+        ));
+    }
+
+    fn test_public_inputs_limit<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            mut public_inputs,
+            mut verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"limit");
+
+        // As many public inputs as elements in the domain, padded with zeros.
+        let domain_size = verifier
+            .verifier_key
+            .as_ref()
+            .unwrap()
+            .domain()
+            .unwrap()
+            .size();
+        assert!(public_inputs.len() < domain_size);
+        public_inputs.resize(domain_size, F::zero());
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // One more than the domain can hold.
+        let mut oversized = public_inputs.clone();
+        oversized.push(F::zero());
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &oversized),
+            Err(Error::TooManyPublicInputs { count, limit })
+                if count == domain_size + 1 && limit == domain_size
+        ));
+
+        // Limit exactly at the number of public inputs.
+        verifier.max_public_inputs = Some(domain_size);
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // Limit below the number of public inputs.
+        verifier.max_public_inputs = Some(domain_size - 1);
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &public_inputs),
+            Err(Error::TooManyPublicInputs { count, limit })
+                if count == domain_size && limit == domain_size - 1
+        ));
+    }
+
+    fn test_allowed_gate_types<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"allowed_gates");
+        logic_gadget(prover.mut_cs());
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"allowed_gates");
+        logic_gadget(verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &[]).is_ok());
+
+        // Logic is allowed.
+        let mut allowed_gate_types =
+            verifier.verifier_key.as_ref().unwrap().gate_types();
+        assert!(allowed_gate_types.contains(&GateType::Logic));
+        verifier.allowed_gate_types = Some(allowed_gate_types.clone());
+        assert!(verifier.verify(&proof, &vk, &[]).is_ok());
+
+        // Logic is disallowed.
+        allowed_gate_types.retain(|gate_type| *gate_type != GateType::Logic);
+        verifier.allowed_gate_types = Some(allowed_gate_types);
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &[]),
+            Err(Error::DisallowedGateType {
+                gate_type: GateType::Logic
+            })
+        ));
+    }
+
+    fn test_blinding_agnostic<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        // Number of extra rounds of blinding rows added to the circuit.
+        for extra_blinding in 0..2 {
+            for zero_blinding in [false, true] {
+                let mut prover = Prover::<F, P, PC>::new(b"blinding");
+                if zero_blinding {
+                    // Zero out the blinding witnesses added by the composer.
+                    for var in prover.cs.blinding_vars {
+                        *prover.cs.variables.get_mut(&var).unwrap() = F::zero();
+                    }
+                }
+                for _ in 0..extra_blinding {
+                    prover.mut_cs().add_blinding_factors(&mut OsRng);
+                }
+                pi_gadget(prover.mut_cs(), F::from(3u64));
+                prover.preprocess(&ck).unwrap();
+                let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+                let proof = prover.prove(&ck).unwrap();
+
+                // The verifier blinds its own circuit with unrelated values.
+                let mut verifier = Verifier::<F, P, PC>::new(b"blinding");
+                for _ in 0..extra_blinding {
+                    verifier.mut_cs().add_blinding_factors(&mut OsRng);
+                }
+                pi_gadget(verifier.mut_cs(), F::zero());
+                verifier.preprocess(&ck).unwrap();
+
+                assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+            }
+        }
+    }
+
+    fn test_sparse_selectors<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        // Arithmetic-only circuit
+        let mut prover = Prover::<F, P, PC>::new(b"sparse");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"sparse");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        let arithmetic_key = verifier.verifier_key.unwrap();
+        assert!(arithmetic_key.range_selector_commitment.is_none());
+        assert!(arithmetic_key.logic_selector_commitment.is_none());
+        assert!(arithmetic_key.fixed_group_add_selector_commitment.is_none());
+        assert!(arithmetic_key
+            .variable_group_add_selector_commitment
+            .is_none());
+
+        // Same circuit with an additional range gate
+        let mut verifier = Verifier::<F, P, PC>::new(b"sparse");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        let witness = verifier.mut_cs().add_input(F::from(5u64));
+        verifier.mut_cs().range_gate(witness, 8);
+        verifier.preprocess(&ck).unwrap();
+
+        let range_key = verifier.verifier_key.unwrap();
+        assert!(range_key.range_selector_commitment.is_some());
+        assert!(range_key.logic_selector_commitment.is_none());
+
+        // The unused selector commitments are left out of the serialized key.
+        let commitment_size = PC::Commitment::default().serialized_size();
+        assert!(
+            range_key.serialized_size()
+                >= arithmetic_key.serialized_size() + commitment_size
+        );
+    }
+
+    fn test_verification_report<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let gadget = |composer: &mut StandardComposer<F, P>, value: F| {
+            pi_gadget(composer, value);
+            pi_gadget(composer, value);
+            let witness = composer.add_input(F::from(5u64));
+            composer.range_gate(witness, 8);
+        };
+
+        let mut prover = Prover::<F, P, PC>::new(b"report");
+        gadget(prover.mut_cs(), F::from(3u64));
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"report");
+        gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        let report = verifier
+            .verify_with_report(&proof, &vk, &public_inputs)
+            .unwrap();
+        assert_eq!(
+            report,
+            VerificationReport {
+                domain_size: 16,
+                num_public_inputs: 2,
+                gate_types_present: vec![GateType::Arithmetic, GateType::Range],
+            }
+        );
+
+        // No report for proofs failing verification.
+        let wrong_inputs = vec![F::one(); public_inputs.len()];
+        assert!(verifier
+            .verify_with_report(&proof, &vk, &wrong_inputs)
+            .is_err());
+    }
+
+    fn test_proof_context<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"context");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove_with_context(&ck, b"block=5").unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"context");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier
+            .verify_with_context(&proof, &vk, &public_inputs, b"block=5")
+            .is_ok());
+        assert!(verifier
+            .verify_with_context(&proof, &vk, &public_inputs, b"block=6")
+            .is_err());
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+
+        // Proofs without context verify with an empty one.
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let proof = prover.prove(&ck).unwrap();
+        assert!(verifier
+            .verify_with_context(&proof, &vk, &public_inputs, &[])
+            .is_ok());
+    }
+
+    fn test_proof_aux_data<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"aux_data");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove_with_aux_data(&ck, b"request=7").unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"aux_data");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier
+            .verify_with_aux_data(&proof, &vk, &public_inputs, b"request=7")
+            .is_ok());
+        assert!(verifier
+            .verify_with_aux_data(&proof, &vk, &public_inputs, b"request=8")
+            .is_err());
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_err());
+
+        // Auxiliary data is not interchangeable with a context.
+        assert!(verifier
+            .verify_with_context(&proof, &vk, &public_inputs, b"request=7")
+            .is_err());
+
+        // A proof bound to both a context and auxiliary data only verifies
+        // given both of them.
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let proof = prover.prove_bound(&ck, b"app", b"request=7").unwrap();
+        let verify = |context: &[u8], aux_data: &[u8]| {
+            verifier
+                .verify_bound(&proof, &vk, &public_inputs, context, aux_data)
+                .is_ok()
+        };
+        assert!(verify(b"app", b"request=7"));
+        assert!(!verify(b"app", b"request=8"));
+        assert!(!verify(b"other", b"request=7"));
+        assert!(!verify(b"app", b""));
+        assert!(!verify(b"", b"request=7"));
+    }
+
+    fn test_chained_proofs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"chain");
+        let mut state = Transcript::new(b"chain");
+        let mut chain = Vec::new();
+        for value in 1..=3u64 {
+            pi_gadget(prover.mut_cs(), F::from(value));
+            let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+            let (proof, next) = prover.prove_chained(&ck, state).unwrap();
+            chain.push((proof, public_inputs));
+            state = next;
+        }
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"chain");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        let verify_chain = |order: [usize; 3]| {
+            order
+                .iter()
+                .try_fold(Transcript::new(b"chain"), |state, &i| {
+                    let (proof, public_inputs) = &chain[i];
+                    verifier.verify_chained(state, proof, &vk, public_inputs)
+                })
+        };
+        assert!(verify_chain([0, 1, 2]).is_ok());
+        assert!(verify_chain([1, 0, 2]).is_err());
+        assert!(verify_chain([0, 2, 1]).is_err());
+
+        // The proofs are bound to their chain, so they do not verify alone.
+        let (proof, public_inputs) = &chain[1];
+        assert!(verifier.verify(proof, &vk, public_inputs).is_err());
+    }
+
+    #[cfg(feature = "tracing")]
+    fn test_verify_phase_spans<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        use crate::test::SpanRecorder;
+
+        let PiCircuit {
+            vk,
+            proof,
+            public_inputs,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"timings");
+        let n = verifier.verifier_key.as_ref().unwrap().domain().unwrap();
+        let n = n.size() as u64;
+
+        // A plain verification goes through each of the phases once.
+        let (spans, verified) = SpanRecorder::record(|| {
+            verifier.verify(&proof, &vk, &public_inputs)
+        });
+        assert!(verified.is_ok());
+        let phases = spans
+            .iter()
+            .filter(|span| !["group_ops", "PC::check"].contains(&span.name))
+            .map(|span| (span.name, span.field("domain_size")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            [
+                ("batch_openings", n),
+                ("linearisation", n),
+                ("opening_checks", n)
+            ]
+        );
+
+        // Each check of the batch openings has its own span, followed by
+        // the span of its group operations.
+        let checks = spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| span.name == "PC::check")
+            .collect::<Vec<_>>();
+        assert!(!checks.is_empty());
+        for (i, check) in checks {
+            assert!(check.field("polynomials") > 0);
+            assert_eq!(spans[i + 1].name, "group_ops");
+        }
+    }
+
+    fn test_circuit_id<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        // Two circuits with the same structure, hence the same keys.
+        let mut prover =
+            Prover::<F, P, PC>::with_circuit_id(b"plonk", b"circuit-a");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let verifier_for = |circuit_id: &[u8]| {
+            let mut verifier =
+                Verifier::<F, P, PC>::with_circuit_id(b"plonk", circuit_id);
+            pi_gadget(verifier.mut_cs(), F::zero());
+            verifier.preprocess(&ck).unwrap();
+            verifier
+        };
+        assert!(verifier_for(b"circuit-a")
+            .verify(&proof, &vk, &public_inputs)
+            .is_ok());
+        assert!(verifier_for(b"circuit-b")
+            .verify(&proof, &vk, &public_inputs)
+            .is_err());
+
+        let mut unbound = Verifier::<F, P, PC>::new(b"plonk");
+        pi_gadget(unbound.mut_cs(), F::zero());
+        unbound.preprocess(&ck).unwrap();
+        assert!(unbound.verify(&proof, &vk, &public_inputs).is_err());
+    }
+
+    fn test_committed_public_inputs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let aux_data = b"request=7";
+        let mut prover = Prover::<F, P, PC>::new(b"committed");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let (proof, pi_opening) = prover
+            .prove_with_committed_public_inputs(&ck, aux_data)
+            .unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"committed");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+        let verify = |pi_opening: &PublicInputOpening<F, PC>| {
+            verifier.verify_with_committed_public_inputs(
+                &proof, &vk, pi_opening, aux_data,
+            )
+        };
+        assert!(verify(&pi_opening).is_ok());
+
+        // Other auxiliary data.
+        assert!(verifier
+            .verify_with_committed_public_inputs(
+                &proof,
+                &vk,
+                &pi_opening,
+                b"request=8",
+            )
+            .is_err());
+
+        // A claimed evaluation which does not match the opening.
+        let mut wrong_eval = pi_opening.clone();
+        wrong_eval.eval += F::one();
+        assert!(verify(&wrong_eval).is_err());
+
+        // The opening of a proof of other public inputs.
+        pi_gadget(prover.mut_cs(), F::from(4u64));
+        let (_, other_opening) = prover
+            .prove_with_committed_public_inputs(&ck, aux_data)
+            .unwrap();
+        assert!(verify(&other_opening).is_err());
+
+        // A commitment to other public inputs swapped in after proving, and
+        // correctly opened at the evaluation challenge the proof gets with
+        // it.
+        let mut composer = StandardComposer::<F, P>::new();
+        pi_gadget(&mut composer, F::from(4u64));
+        let domain = verifier.plonk_verifier_key().unwrap().domain().unwrap();
+        let other_poly = DensePolynomial::from_coefficients_vec(
+            domain.ifft(&composer.construct_dense_pi_vec()),
+        );
+        let other_polys = [label_polynomial!(other_poly)];
+        let (other_commits, other_rands) =
+            PC::commit(&ck, &other_polys, None).unwrap();
+        let commitment = other_commits[0].commitment().clone();
+        let mut transcript = verifier.preprocessed_transcript.clone();
+        transcript.append(b"pi_comm", &commitment);
+        let z_challenge = proof.evaluation_challenge(&mut transcript, aux_data);
+        let swapped = PublicInputOpening {
+            commitment,
+            eval: other_poly.evaluate(&z_challenge),
+            opening: PC::open(
+                &ck,
+                &other_polys,
+                &other_commits,
+                &z_challenge,
+                F::one(),
+                &other_rands,
+                None,
+            )
+            .unwrap(),
+        };
+        assert!(verify(&swapped).is_err());
+    }
+
+    fn test_commit_public_inputs<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: PartialEq,
+    {
+        let (ck, _) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"pi_commitment");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let (_, pi_opening) =
+            prover.prove_with_committed_public_inputs(&ck, &[]).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"pi_commitment");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+        let commitment =
+            verifier.commit_public_inputs(&ck, &public_inputs).unwrap();
+        assert!(commitment == pi_opening.commitment);
+
+        // Other public inputs are committed to differently.
+        let mut composer = StandardComposer::<F, P>::new();
+        pi_gadget(&mut composer, F::from(4u64));
+        let other_inputs = composer.construct_dense_pi_vec();
+        let other = verifier.commit_public_inputs(&ck, &other_inputs).unwrap();
+        assert!(other != pi_opening.commitment);
+    }
+
+    /// [`PublicInputCommitment`] evaluating the public input polynomial in
+    /// coefficient form.
+    struct InterpolatedPublicInputs;
+
+    impl<F> PublicInputCommitment<F> for InterpolatedPublicInputs
+    where
+        F: PrimeField,
+    {
+        type Commitment = Vec<F>;
+
+        fn commit(&self, inputs: &[F]) -> Vec<F> {
+            inputs.to_vec()
+        }
+
+        fn verify_contribution(
+            &self,
+            commitment: &Vec<F>,
+            z_challenge: F,
+            domain: &GeneralEvaluationDomain<F>,
+        ) -> Result<F, Error> {
+            let pi_poly =
+                DensePolynomial::from_coefficients_vec(domain.ifft(commitment));
+            Ok(pi_poly.evaluate(&z_challenge))
+        }
+    }
+
+    fn test_public_input_commitment<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            public_inputs,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"pi_scheme");
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[0] += F::one();
+
+        // The default scheme agrees with `verify`.
+        let scheme = BarycentricPublicInputs;
+        for inputs in [&public_inputs, &wrong_inputs] {
+            let commitment = scheme.commit(inputs);
+            assert_eq!(
+                verifier
+                    .verify_with_public_input_commitment(
+                        &proof,
+                        &vk,
+                        &scheme,
+                        &commitment,
+                        &[],
+                    )
+                    .is_ok(),
+                verifier.verify(&proof, &vk, inputs).is_ok()
+            );
+        }
+
+        // And so does another scheme computing the same contribution.
+        let scheme = InterpolatedPublicInputs;
+        let commitment = scheme.commit(&public_inputs);
+        assert!(verifier
+            .verify_with_public_input_commitment(
+                &proof,
+                &vk,
+                &scheme,
+                &commitment,
+                &[],
+            )
+            .is_ok());
+        let commitment = scheme.commit(&wrong_inputs);
+        assert!(verifier
+            .verify_with_public_input_commitment(
+                &proof,
+                &vk,
+                &scheme,
+                &commitment,
+                &[],
+            )
+            .is_err());
+    }
+
+    fn test_public_input_evaluations<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"pi_evaluations");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        pi_gadget(prover.mut_cs(), F::from(7u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let aux_data = b"request=7";
+        let proof = prover.prove_with_aux_data(&ck, aux_data).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"pi_evaluations");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        pi_gadget(verifier.mut_cs(), F::zero());
+        verifier.preprocess(&ck).unwrap();
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[0] += F::one();
+
+        // The precomputed path agrees with the full barycentric one.
+        for inputs in [&public_inputs, &wrong_inputs] {
+            let evaluation = verifier
+                .public_input_evaluation(&proof, inputs, aux_data)
+                .unwrap();
+            let candidates = [(F::from(2u64), F::one()), evaluation];
+            assert_eq!(
+                verifier
+                    .verify_with_public_input_evaluations(
+                        &proof,
+                        &vk,
+                        &candidates,
+                        aux_data,
+                    )
+                    .is_ok(),
+                verifier
+                    .verify_with_aux_data(&proof, &vk, inputs, aux_data)
+                    .is_ok()
+            );
+        }
+
+        // The evaluation challenge must be among the candidates.
+        let (z_challenge, eval) = verifier
+            .public_input_evaluation(&proof, &public_inputs, aux_data)
+            .unwrap();
+        assert!(matches!(
+            verifier.verify_with_public_input_evaluations(
+                &proof,
+                &vk,
+                &[(z_challenge + F::one(), eval)],
+                aux_data,
+            ),
+            Err(Error::MissingPublicInputEvaluation)
+        ));
+
+        // The evaluation challenge depends on the auxiliary data.
+        let (other_challenge, _) = verifier
+            .public_input_evaluation(&proof, &public_inputs, b"request=8")
+            .unwrap();
+        assert_ne!(other_challenge, z_challenge);
+    }
+
+    fn test_verify_unpreprocessed<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let (ck, vk) = test_keys::<F, PC>();
+
+        let mut prover = Prover::<F, P, PC>::new(b"unpreprocessed");
+        pi_gadget(prover.mut_cs(), F::from(3u64));
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"unpreprocessed");
+        pi_gadget(verifier.mut_cs(), F::zero());
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &public_inputs),
+            Err(Error::CircuitNotPreprocessed)
+        ));
+        assert!(matches!(
+            verifier.verify_with_report(&proof, &vk, &public_inputs),
+            Err(Error::CircuitNotPreprocessed)
+        ));
+        assert!(matches!(
+            verifier.batch_verify(&[proof], &vk, &[public_inputs], &[&[]]),
+            Err(Error::CircuitNotPreprocessed)
+        ));
+    }
+
+    fn test_missing_evaluation<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            mut proof,
+            public_inputs,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"missing");
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        proof
+            .evaluations
+            .custom_evals
+            .vals
+            .retain(|(label, _)| label != "a_next_eval");
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &public_inputs),
+            Err(Error::MissingEvaluation {
+                label: "a_next_eval"
+            })
+        ));
+    }
+
+    fn test_quick_check<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            public_inputs,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"quick");
+
+        let mut wrong_eval = proof.clone();
+        wrong_eval.evaluations.wire_evals.a_eval += F::one();
+        let mut wrong_comm = proof.clone();
+        wrong_comm.a_comm = proof.b_comm.clone();
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[0] += F::one();
+
+        for _ in 0..8 {
+            assert!(verifier
+                .quick_check(&proof, &vk, &public_inputs, &[], &mut OsRng)
+                .is_ok());
+            assert!(verifier
+                .quick_check(&wrong_eval, &vk, &public_inputs, &[], &mut OsRng)
+                .is_err());
+            assert!(verifier
+                .quick_check(&wrong_comm, &vk, &public_inputs, &[], &mut OsRng)
+                .is_err());
+        }
+
+        // The public inputs only enter the opening at `z`, the first one,
+        // which is picked deterministically here.
+        assert!(verifier
+            .quick_check(&proof, &vk, &wrong_inputs, &[], &mut OpeningRng(0))
+            .is_err());
+        assert!(verifier
+            .quick_check(&proof, &vk, &wrong_inputs, &[], &mut OpeningRng(1))
+            .is_ok());
+    }
+
+    /// RNG always returning the same value, so that [`Verifier::quick_check`]
+    /// picks the batch opening of this index.
+    struct OpeningRng(u32);
+
+    impl RngCore for OpeningRng {
+        fn next_u32(&mut self) -> u32 {
+            self.0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 as u64
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(self.0 as u8)
+        }
+
+        fn try_fill_bytes(
+            &mut self,
+            dest: &mut [u8],
+        ) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    fn test_non_power_of_two_domain<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            public_inputs,
+            mut verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"domain");
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // A key whose size is not a power of two is rejected, rather than
+        // being read over the domain of the next power of two.
+        verifier.verifier_key.as_mut().unwrap().n = 200;
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &public_inputs),
+            Err(Error::NonPowerOfTwoDomain { n: 200 })
+        ));
+    }
+
+    fn test_challenge_in_domain<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let PiCircuit {
+            vk,
+            proof,
+            public_inputs,
+            verifier,
+            ..
+        } = pi_circuit::<F, P, PC>(b"challenge_in_domain");
+        let domain = verifier.verifier_key.as_ref().unwrap().domain().unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        // A transcript landing the evaluation challenge on a root of unity
+        // makes the verifier bail out instead of dividing by zero.
+        let verified = mock::with_challenge(b"z", domain.element(3), || {
+            verifier.verify(&proof, &vk, &public_inputs)
+        });
+        assert!(matches!(verified, Err(Error::ChallengeInDomain)));
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_batch_verify,
+            test_public_inputs_limit,
+            test_allowed_gate_types,
+            test_blinding_agnostic,
+            test_sparse_selectors,
+            test_verification_report,
+            test_proof_context,
+            test_proof_aux_data,
+            test_chained_proofs,
+            test_circuit_id,
+            test_committed_public_inputs,
+            test_commit_public_inputs,
+            test_public_input_commitment,
+            test_public_input_evaluations,
+            test_verify_unpreprocessed,
+            test_missing_evaluation,
+            test_quick_check,
+            test_non_power_of_two_domain,
+            test_challenge_in_domain
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+    #[cfg(feature = "tracing")]
+    batch_test!(
+        [test_verify_phase_spans],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_batch_verify,
+            test_public_inputs_limit,
+            test_allowed_gate_types,
+            test_blinding_agnostic,
+            test_sparse_selectors,
+            test_verification_report,
+            test_proof_context,
+            test_proof_aux_data,
+            test_chained_proofs,
+            test_circuit_id,
+            test_committed_public_inputs,
+            test_commit_public_inputs,
+            test_public_input_commitment,
+            test_public_input_evaluations,
+            test_verify_unpreprocessed,
+            test_missing_evaluation,
+            test_quick_check,
+            test_non_power_of_two_domain,
+            test_challenge_in_domain
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+    #[cfg(feature = "tracing")]
+    batch_test!(
+        [test_verify_phase_spans],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
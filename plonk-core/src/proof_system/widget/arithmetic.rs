@@ -85,7 +85,7 @@ where
 /// Arithmetic Gates Verifier Key
 #[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
 #[derivative(
-    Clone,
+    Clone(bound = ""),
     Copy(bound = "PC::Commitment: Copy"),
     Debug(bound = "PC::Commitment: std::fmt::Debug"),
     Eq(bound = "PC::Commitment: Eq"),
@@ -13,15 +13,22 @@ pub mod range;
 
 use crate::{
     commitment::HomomorphicCommitment,
+    error::{to_pc_error, Error},
     proof_system::{
         linearisation_poly::CustomEvaluations,
-        linearisation_poly::ProofEvaluations, permutation,
+        linearisation_poly::{EvaluationsSchema, ProofEvaluations},
+        permutation,
     },
     transcript::TranscriptProtocol,
+    util::key_domain,
 };
 use ark_ff::PrimeField;
-use ark_poly::{univariate::DensePolynomial, Evaluations};
+use ark_poly::{
+    univariate::DensePolynomial, Evaluations, GeneralEvaluationDomain,
+};
+use ark_poly_commit::LabeledPolynomial;
 use ark_serialize::*;
+use blake2::digest::{self, Digest};
 
 /// Set of values needed for a custom gate
 pub trait CustomValues<F>
@@ -128,6 +135,73 @@ where
     }
 }
 
+/// Types of gates a circuit can be made of.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GateType {
+    /// Arithmetic Gate
+    Arithmetic,
+
+    /// Range Gate
+    Range,
+
+    /// Logic Gate
+    Logic,
+
+    /// Fixed Base Scalar Multiplication Gate
+    FixedBaseScalarMul,
+
+    /// Variable Base Curve Addition Gate
+    CurveAddition,
+}
+
+/// Differences between two [`VerifierKey`]s, as reported by
+/// [`VerifierKey::diff`].
+///
+/// Commitments are identified by the labels they are added to the transcript
+/// with, e.g. `q_range` or `left_sigma`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifierKeyDiff {
+    /// Circuit sizes of both keys, if they differ.
+    pub n: Option<(usize, usize)>,
+
+    /// Labels of the selector commitments that differ.
+    pub selectors: Vec<&'static str>,
+
+    /// Labels of the permutation commitments that differ.
+    pub permutation: Vec<&'static str>,
+}
+
+impl VerifierKeyDiff {
+    /// Returns `true` if both keys are identical.
+    pub fn is_empty(&self) -> bool {
+        self.n.is_none()
+            && self.selectors.is_empty()
+            && self.permutation.is_empty()
+    }
+}
+
+/// Byte order of the digest returned by [`VerifierKey::hash`].
+///
+/// The default is [`HashEndianness::Big`], the order in which the hash
+/// function outputs the digest. This is how e.g. Ethereum reads a Keccak-256
+/// digest as a `uint256`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashEndianness {
+    /// Digest bytes in the order output by the hash function.
+    Big,
+
+    /// Digest bytes in the reverse order of the one output by the hash
+    /// function.
+    Little,
+}
+
+impl Default for HashEndianness {
+    #[inline]
+    fn default() -> Self {
+        Self::Big
+    }
+}
+
 /// PLONK circuit Verification Key.
 ///
 /// This structure is used by the Verifier in order to verify a
@@ -135,13 +209,9 @@ where
 #[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
 #[derivative(
     Clone(bound = ""),
-    Debug(
-        bound = "arithmetic::VerifierKey<F,PC>: std::fmt::Debug, PC::Commitment: std::fmt::Debug"
-    ),
-    Eq(bound = "arithmetic::VerifierKey<F,PC>: Eq, PC::Commitment: Eq"),
-    PartialEq(
-        bound = "arithmetic::VerifierKey<F,PC>: PartialEq, PC::Commitment: PartialEq"
-    )
+    Debug(bound = "PC::Commitment: std::fmt::Debug"),
+    Eq(bound = "PC::Commitment: Eq"),
+    PartialEq(bound = "PC::Commitment: PartialEq")
 )]
 pub struct VerifierKey<F, PC>
 where
@@ -155,16 +225,24 @@ where
     pub(crate) arithmetic: arithmetic::VerifierKey<F, PC>,
 
     /// Range Gate Selector Commitment
-    pub(crate) range_selector_commitment: PC::Commitment,
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) range_selector_commitment: Option<PC::Commitment>,
 
     /// Logic Gate Selector Commitment
-    pub(crate) logic_selector_commitment: PC::Commitment,
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) logic_selector_commitment: Option<PC::Commitment>,
 
     /// Fixed Group Addition Selector Commitment
-    pub(crate) fixed_group_add_selector_commitment: PC::Commitment,
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) fixed_group_add_selector_commitment: Option<PC::Commitment>,
 
     /// Variable Group Addition Selector Commitment
-    pub(crate) variable_group_add_selector_commitment: PC::Commitment,
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) variable_group_add_selector_commitment: Option<PC::Commitment>,
 
     /// VerifierKey for permutation checks
     pub(crate) permutation: permutation::VerifierKey<PC::Commitment>,
@@ -178,6 +256,9 @@ where
     /// Constructs a [`VerifierKey`] from the widget VerifierKey's that are
     /// constructed based on the selector polynomial commitments and the
     /// sigma polynomial commitments.
+    ///
+    /// Returns [`Error::NonPowerOfTwoDomain`] if the domain size `n` is not a
+    /// power of two.
     pub(crate) fn from_polynomial_commitments(
         n: usize,
         q_m: PC::Commitment,
@@ -187,16 +268,19 @@ where
         q_4: PC::Commitment,
         q_c: PC::Commitment,
         q_arith: PC::Commitment,
-        q_range: PC::Commitment,
-        q_logic: PC::Commitment,
-        q_fixed_group_add: PC::Commitment,
-        q_variable_group_add: PC::Commitment,
+        q_range: Option<PC::Commitment>,
+        q_logic: Option<PC::Commitment>,
+        q_fixed_group_add: Option<PC::Commitment>,
+        q_variable_group_add: Option<PC::Commitment>,
         left_sigma: PC::Commitment,
         right_sigma: PC::Commitment,
         out_sigma: PC::Commitment,
         fourth_sigma: PC::Commitment,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, Error> {
+        if !n.is_power_of_two() {
+            return Err(Error::NonPowerOfTwoDomain { n });
+        }
+        Ok(Self {
             n,
             arithmetic: arithmetic::VerifierKey {
                 q_m,
@@ -217,13 +301,216 @@ where
                 out_sigma,
                 fourth_sigma,
             },
-        }
+        })
     }
 
     /// Returns the Circuit size padded to the next power of two.
     pub fn padded_circuit_size(&self) -> usize {
         self.n.next_power_of_two()
     }
+
+    /// Reports which of the circuit size, selector commitments and
+    /// permutation commitments differ between `self` and `other`.
+    ///
+    /// Selectors of unused gates are compared as well, so a gate type being
+    /// used by only one of the circuits shows up as a differing selector.
+    pub fn diff(&self, other: &Self) -> VerifierKeyDiff {
+        fn differ<T>(a: &T, b: &T) -> bool
+        where
+            T: CanonicalSerialize,
+        {
+            let mut a_bytes = Vec::new();
+            let mut b_bytes = Vec::new();
+            a.serialize(&mut a_bytes)
+                .expect("Serializing into a vector never fails");
+            b.serialize(&mut b_bytes)
+                .expect("Serializing into a vector never fails");
+            a_bytes != b_bytes
+        }
+
+        let selectors = [
+            ("q_m", differ(&self.arithmetic.q_m, &other.arithmetic.q_m)),
+            ("q_l", differ(&self.arithmetic.q_l, &other.arithmetic.q_l)),
+            ("q_r", differ(&self.arithmetic.q_r, &other.arithmetic.q_r)),
+            ("q_o", differ(&self.arithmetic.q_o, &other.arithmetic.q_o)),
+            ("q_4", differ(&self.arithmetic.q_4, &other.arithmetic.q_4)),
+            ("q_c", differ(&self.arithmetic.q_c, &other.arithmetic.q_c)),
+            (
+                "q_arith",
+                differ(&self.arithmetic.q_arith, &other.arithmetic.q_arith),
+            ),
+            (
+                "q_range",
+                differ(
+                    &self.range_selector_commitment,
+                    &other.range_selector_commitment,
+                ),
+            ),
+            (
+                "q_logic",
+                differ(
+                    &self.logic_selector_commitment,
+                    &other.logic_selector_commitment,
+                ),
+            ),
+            (
+                "q_fixed_group_add",
+                differ(
+                    &self.fixed_group_add_selector_commitment,
+                    &other.fixed_group_add_selector_commitment,
+                ),
+            ),
+            (
+                "q_variable_group_add",
+                differ(
+                    &self.variable_group_add_selector_commitment,
+                    &other.variable_group_add_selector_commitment,
+                ),
+            ),
+        ];
+        let permutation = [
+            (
+                "left_sigma",
+                differ(
+                    &self.permutation.left_sigma,
+                    &other.permutation.left_sigma,
+                ),
+            ),
+            (
+                "right_sigma",
+                differ(
+                    &self.permutation.right_sigma,
+                    &other.permutation.right_sigma,
+                ),
+            ),
+            (
+                "out_sigma",
+                differ(
+                    &self.permutation.out_sigma,
+                    &other.permutation.out_sigma,
+                ),
+            ),
+            (
+                "fourth_sigma",
+                differ(
+                    &self.permutation.fourth_sigma,
+                    &other.permutation.fourth_sigma,
+                ),
+            ),
+        ];
+
+        let labels = |commitments: &[(&'static str, bool)]| -> Vec<_> {
+            commitments
+                .iter()
+                .filter(|(_, differs)| *differs)
+                .map(|(label, _)| *label)
+                .collect()
+        };
+        VerifierKeyDiff {
+            n: (self.n != other.n).then(|| (self.n, other.n)),
+            selectors: labels(&selectors),
+            permutation: labels(&permutation),
+        }
+    }
+
+    /// Returns the types of gates used by the circuit described by this key.
+    ///
+    /// Arithmetic gates are always reported, custom gates only when their
+    /// selector is committed to.
+    pub fn gate_types(&self) -> Vec<GateType> {
+        let mut gate_types = vec![GateType::Arithmetic];
+        if self.range_selector_commitment.is_some() {
+            gate_types.push(GateType::Range);
+        }
+        if self.logic_selector_commitment.is_some() {
+            gate_types.push(GateType::Logic);
+        }
+        if self.fixed_group_add_selector_commitment.is_some() {
+            gate_types.push(GateType::FixedBaseScalarMul);
+        }
+        if self.variable_group_add_selector_commitment.is_some() {
+            gate_types.push(GateType::CurveAddition);
+        }
+        gate_types
+    }
+
+    /// Checks that this key commits to the selector of every gate type in
+    /// `used_gates`, returning [`Error::MissingSelectorCommitment`] with the
+    /// label of the first missing one otherwise.
+    ///
+    /// A missing selector commitment leaves the constraints of its gate out
+    /// of the linearisation commitment, so that a proof is verified as if
+    /// the circuit did not use that gate at all.
+    pub fn validate_gate_coverage(
+        &self,
+        used_gates: &[GateType],
+    ) -> Result<(), Error> {
+        for gate in used_gates {
+            let (selector, commitment) = match gate {
+                GateType::Arithmetic => continue,
+                GateType::Range => ("q_range", &self.range_selector_commitment),
+                GateType::Logic => ("q_logic", &self.logic_selector_commitment),
+                GateType::FixedBaseScalarMul => (
+                    "q_fixed_group_add",
+                    &self.fixed_group_add_selector_commitment,
+                ),
+                GateType::CurveAddition => (
+                    "q_variable_group_add",
+                    &self.variable_group_add_selector_commitment,
+                ),
+            };
+            if commitment.is_none() {
+                return Err(Error::MissingSelectorCommitment { selector });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of pieces the quotient polynomial of the circuit
+    /// is split into, each of them of degree lower than the domain size.
+    ///
+    /// This is the maximum degree of the gate constraints, in the wire and
+    /// selector polynomials, the quotient is built for. It is `4` for every
+    /// gate of this crate, all of them being checked over a coset of four
+    /// times the size of the domain.
+    pub fn quotient_piece_count(&self) -> usize {
+        4
+    }
+
+    /// Returns the [`EvaluationsSchema`] of the proofs of the circuit
+    /// described by this key, whose custom gate evaluations are the ones
+    /// read by its [`gate_types`](Self::gate_types).
+    pub fn evaluations_schema(&self) -> EvaluationsSchema {
+        EvaluationsSchema::for_gate_types(&self.gate_types())
+    }
+
+    /// Hashes the canonical serialization of this key with the hash function
+    /// `D`, returning the digest in the byte order `endianness`.
+    ///
+    /// Two keys have the same hash exactly when they describe the same
+    /// circuit, so that the hash can identify the circuit, e.g. on a chain
+    /// whose conventions are matched by the choice of `D` and `endianness`.
+    pub fn hash<D>(
+        &self,
+        endianness: HashEndianness,
+    ) -> Result<digest::Output<D>, Error>
+    where
+        D: Digest,
+    {
+        let mut bytes = Vec::with_capacity(self.serialized_size());
+        self.serialize(&mut bytes)?;
+        let mut digest = D::digest(&bytes);
+        if endianness == HashEndianness::Little {
+            digest.reverse();
+        }
+        Ok(digest)
+    }
+
+    /// Builds the evaluation domain over which the circuit described by this
+    /// key is defined.
+    pub(crate) fn domain(&self) -> Result<GeneralEvaluationDomain<F>, Error> {
+        key_domain(self.n)
+    }
 }
 
 impl<F, PC> VerifierKey<F, PC>
@@ -283,17 +570,26 @@ where
     pub(crate) arithmetic: arithmetic::ProverKey<F>,
 
     /// Range Gate Selector
-    pub(crate) range_selector: (DensePolynomial<F>, Evaluations<F>),
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) range_selector: Option<(DensePolynomial<F>, Evaluations<F>)>,
 
     /// Logic Gate Selector
-    pub(crate) logic_selector: (DensePolynomial<F>, Evaluations<F>),
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) logic_selector: Option<(DensePolynomial<F>, Evaluations<F>)>,
 
     /// Fixed Group Addition Selector
-    pub(crate) fixed_group_add_selector: (DensePolynomial<F>, Evaluations<F>),
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
+    pub(crate) fixed_group_add_selector:
+        Option<(DensePolynomial<F>, Evaluations<F>)>,
 
     /// Variable Group Addition Selector
+    ///
+    /// This is `None` whenever the circuit contains no such gate.
     pub(crate) variable_group_add_selector:
-        (DensePolynomial<F>, Evaluations<F>),
+        Option<(DensePolynomial<F>, Evaluations<F>)>,
 
     /// ProverKey for permutation checks
     pub(crate) permutation: permutation::ProverKey<F>,
@@ -305,6 +601,11 @@ where
     /// in their evaluation phase and divide by the quotient
     /// polynomial without having to perform IFFT
     pub(crate) v_h_coset_4n: Evaluations<F>,
+
+    /// Pre-processes the 4n Evaluations for the first Lagrange polynomial,
+    /// which only depend on the domain. The quotient polynomial reads them
+    /// scaled by the `alpha^2` challenge of each proof.
+    pub(crate) l1_coset_4n: Evaluations<F>,
 }
 
 impl<F> ProverKey<F>
@@ -315,9 +616,35 @@ where
         &self.v_h_coset_4n
     }
 
+    pub(crate) fn l1_coset_4n(&self) -> &Evaluations<F> {
+        &self.l1_coset_4n
+    }
+
+    /// Returns the types of gates whose selector is held by this key, like
+    /// [`VerifierKey::gate_types`].
+    pub(crate) fn gate_types(&self) -> Vec<GateType> {
+        let mut gate_types = vec![GateType::Arithmetic];
+        if self.range_selector.is_some() {
+            gate_types.push(GateType::Range);
+        }
+        if self.logic_selector.is_some() {
+            gate_types.push(GateType::Logic);
+        }
+        if self.fixed_group_add_selector.is_some() {
+            gate_types.push(GateType::FixedBaseScalarMul);
+        }
+        if self.variable_group_add_selector.is_some() {
+            gate_types.push(GateType::CurveAddition);
+        }
+        gate_types
+    }
+
     /// Constructs a [`ProverKey`] from the widget ProverKey's that are
     /// constructed based on the selector polynomials and the
     /// sigma polynomials and it's evaluations.
+    ///
+    /// Returns [`Error::NonPowerOfTwoDomain`] if the domain size `n` is not a
+    /// power of two.
     pub(crate) fn from_polynomials_and_evals(
         n: usize,
         q_m: (DensePolynomial<F>, Evaluations<F>),
@@ -327,18 +654,22 @@ where
         q_4: (DensePolynomial<F>, Evaluations<F>),
         q_c: (DensePolynomial<F>, Evaluations<F>),
         q_arith: (DensePolynomial<F>, Evaluations<F>),
-        q_range: (DensePolynomial<F>, Evaluations<F>),
-        q_logic: (DensePolynomial<F>, Evaluations<F>),
-        q_fixed_group_add: (DensePolynomial<F>, Evaluations<F>),
-        q_variable_group_add: (DensePolynomial<F>, Evaluations<F>),
+        q_range: Option<(DensePolynomial<F>, Evaluations<F>)>,
+        q_logic: Option<(DensePolynomial<F>, Evaluations<F>)>,
+        q_fixed_group_add: Option<(DensePolynomial<F>, Evaluations<F>)>,
+        q_variable_group_add: Option<(DensePolynomial<F>, Evaluations<F>)>,
         left_sigma: (DensePolynomial<F>, Evaluations<F>),
         right_sigma: (DensePolynomial<F>, Evaluations<F>),
         out_sigma: (DensePolynomial<F>, Evaluations<F>),
         fourth_sigma: (DensePolynomial<F>, Evaluations<F>),
         linear_evaluations: Evaluations<F>,
         v_h_coset_4n: Evaluations<F>,
-    ) -> Self {
-        Self {
+        l1_coset_4n: Evaluations<F>,
+    ) -> Result<Self, Error> {
+        if !n.is_power_of_two() {
+            return Err(Error::NonPowerOfTwoDomain { n });
+        }
+        Ok(Self {
             n,
             arithmetic: arithmetic::ProverKey {
                 q_m,
@@ -361,20 +692,118 @@ where
                 linear_evaluations,
             },
             v_h_coset_4n,
-        }
+            l1_coset_4n,
+        })
+    }
+
+    /// Commits to the selector and permutation polynomials of this key with
+    /// `commit_key`, returning the [`VerifierKey`] of the same circuit.
+    pub fn verifier_key<PC>(
+        &self,
+        commit_key: &PC::CommitterKey,
+    ) -> Result<VerifierKey<F, PC>, Error>
+    where
+        PC: HomomorphicCommitment<F>,
+    {
+        let optional_selectors = [
+            ("q_range", &self.range_selector),
+            ("q_logic", &self.logic_selector),
+            ("q_fixed_group_add", &self.fixed_group_add_selector),
+            ("q_variable_group_add", &self.variable_group_add_selector),
+        ];
+        let polynomials = [
+            ("q_m", &self.arithmetic.q_m.0),
+            ("q_l", &self.arithmetic.q_l.0),
+            ("q_r", &self.arithmetic.q_r.0),
+            ("q_o", &self.arithmetic.q_o.0),
+            ("q_4", &self.arithmetic.q_4.0),
+            ("q_c", &self.arithmetic.q_c.0),
+            ("q_arith", &self.arithmetic.q_arith.0),
+            ("left_sigma", &self.permutation.left_sigma.0),
+            ("right_sigma", &self.permutation.right_sigma.0),
+            ("out_sigma", &self.permutation.out_sigma.0),
+            ("fourth_sigma", &self.permutation.fourth_sigma.0),
+        ]
+        .into_iter()
+        .chain(optional_selectors.iter().filter_map(|&(label, selector)| {
+            selector.as_ref().map(|(poly, _)| (label, poly))
+        }))
+        .map(|(label, poly)| {
+            LabeledPolynomial::new(label.to_owned(), poly.clone(), None, None)
+        })
+        .collect::<Vec<_>>();
+
+        let (commitments, _) = PC::commit(commit_key, polynomials.iter(), None)
+            .map_err(to_pc_error::<F, PC>)?;
+        let mut commitments = commitments
+            .into_iter()
+            .map(|commitment| commitment.commitment().clone());
+        let mut next = || commitments.next().unwrap();
+
+        let (q_m, q_l, q_r, q_o) = (next(), next(), next(), next());
+        let (q_4, q_c, q_arith) = (next(), next(), next());
+        let (left_sigma, right_sigma) = (next(), next());
+        let (out_sigma, fourth_sigma) = (next(), next());
+        let [q_range, q_logic, q_fixed_group_add, q_variable_group_add] =
+            optional_selectors
+                .map(|(_, selector)| selector.as_ref().map(|_| next()));
+
+        VerifierKey::from_polynomial_commitments(
+            self.n,
+            q_m,
+            q_l,
+            q_r,
+            q_o,
+            q_4,
+            q_c,
+            q_arith,
+            q_range,
+            q_logic,
+            q_fixed_group_add,
+            q_variable_group_add,
+            left_sigma,
+            right_sigma,
+            out_sigma,
+            fourth_sigma,
+        )
+    }
+
+    /// Checks that this key and `verifier_key` were generated by
+    /// preprocessing the same circuit, by recommitting to the selector and
+    /// permutation polynomials of this key with `commit_key` and comparing
+    /// the result to `verifier_key`, domain size included.
+    ///
+    /// This catches a verifier key regenerated from a slightly different
+    /// circuit before any proof is made against it, at about the cost of
+    /// preprocessing the circuit again.
+    pub fn matches_verifier_key<PC>(
+        &self,
+        commit_key: &PC::CommitterKey,
+        verifier_key: &VerifierKey<F, PC>,
+    ) -> Result<bool, Error>
+    where
+        PC: HomomorphicCommitment<F>,
+    {
+        Ok(self.verifier_key(commit_key)?.diff(verifier_key).is_empty())
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::batch_test;
+    use crate::{
+        batch_test,
+        constraint_system::StandardComposer,
+        proof_system::{Prover, Verifier},
+    };
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
     use ark_ec::models::TEModelParameters;
     use ark_poly::polynomial::univariate::DensePolynomial;
     use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, UVPolynomial};
+    use ark_std::{rand::rngs::StdRng, test_rng};
     use rand::rngs::OsRng;
+    use sha3::Keccak256;
 
     fn rand_poly_eval<F>(n: usize) -> (DensePolynomial<F>, Evaluations<F>)
     where
@@ -405,10 +834,10 @@ mod test {
         let q_4 = rand_poly_eval(n);
         let q_c = rand_poly_eval(n);
         let q_arith = rand_poly_eval(n);
-        let q_range = rand_poly_eval(n);
-        let q_logic = rand_poly_eval(n);
-        let q_fixed_group_add = rand_poly_eval(n);
-        let q_variable_group_add = rand_poly_eval(n);
+        let q_range = Some(rand_poly_eval(n));
+        let q_logic = None;
+        let q_fixed_group_add = Some(rand_poly_eval(n));
+        let q_variable_group_add = Some(rand_poly_eval(n));
 
         let left_sigma = rand_poly_eval(n);
         let right_sigma = rand_poly_eval(n);
@@ -417,6 +846,7 @@ mod test {
 
         let linear_evaluations = rand_evaluations(n);
         let v_h_coset_8n = rand_evaluations(n);
+        let l1_coset_8n = rand_evaluations(n);
 
         let prover_key = ProverKey::from_polynomials_and_evals(
             n,
@@ -437,7 +867,9 @@ mod test {
             fourth_sigma,
             linear_evaluations,
             v_h_coset_8n,
-        );
+            l1_coset_8n,
+        )
+        .unwrap();
 
         let mut prover_key_bytes = vec![];
         prover_key
@@ -467,10 +899,10 @@ mod test {
         let q_4 = PC::Commitment::default();
         let q_c = PC::Commitment::default();
         let q_arith = PC::Commitment::default();
-        let q_range = PC::Commitment::default();
-        let q_logic = PC::Commitment::default();
-        let q_fixed_group_add = PC::Commitment::default();
-        let q_variable_group_add = PC::Commitment::default();
+        let q_range = Some(PC::Commitment::default());
+        let q_logic = None;
+        let q_fixed_group_add = Some(PC::Commitment::default());
+        let q_variable_group_add = Some(PC::Commitment::default());
 
         let left_sigma = PC::Commitment::default();
         let right_sigma = PC::Commitment::default();
@@ -494,7 +926,8 @@ mod test {
             right_sigma,
             out_sigma,
             fourth_sigma,
-        );
+        )
+        .unwrap();
 
         let mut verifier_key_bytes = vec![];
         verifier_key
@@ -508,16 +941,351 @@ mod test {
         assert!(verifier_key == obtained_vk);
     }
 
+    fn test_verifier_key_diff<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let commitment = PC::Commitment::default;
+        let verifier_key = VerifierKey::<F, PC>::from_polynomial_commitments(
+            32,
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            Some(commitment()),
+            Some(commitment()),
+            None,
+            None,
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+        )
+        .unwrap();
+
+        // A key does not differ from itself
+        let diff = verifier_key.diff(&verifier_key.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff, VerifierKeyDiff::default());
+
+        // Altering a single selector
+        let mut altered = verifier_key.clone();
+        altered.logic_selector_commitment = None;
+        assert_eq!(
+            verifier_key.diff(&altered),
+            VerifierKeyDiff {
+                n: None,
+                selectors: vec!["q_logic"],
+                permutation: vec![],
+            }
+        );
+
+        // Altering the circuit size
+        altered.n = 64;
+        let diff = verifier_key.diff(&altered);
+        assert_eq!(diff.n, Some((32, 64)));
+        assert_eq!(diff.selectors, ["q_logic"]);
+        assert!(!diff.is_empty());
+    }
+
+    fn test_validate_gate_coverage<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut verifier = Verifier::<F, P, PC>::new(b"coverage");
+        let composer = verifier.mut_cs();
+        let a = composer.add_input(F::from(3u64));
+        let b = composer.add_input(F::from(5u64));
+        composer.xor_gate(a, b, 8);
+        verifier.preprocess(&ck).unwrap();
+        let verifier_key = verifier.verifier_key.unwrap();
+
+        let used_gates = verifier_key.gate_types();
+        assert!(used_gates.contains(&GateType::Logic));
+        assert!(verifier_key.validate_gate_coverage(&used_gates).is_ok());
+
+        // A key without the logic selector does not cover the circuit.
+        let mut altered = verifier_key.clone();
+        altered.logic_selector_commitment = None;
+        assert!(matches!(
+            altered.validate_gate_coverage(&used_gates),
+            Err(Error::MissingSelectorCommitment {
+                selector: "q_logic"
+            })
+        ));
+        assert!(altered
+            .validate_gate_coverage(&[GateType::Arithmetic])
+            .is_ok());
+    }
+
+    fn test_evaluations_schema<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let arithmetic = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(3u64));
+            composer.arithmetic_gate(|gate| {
+                gate.witness(a, a, None).add(F::one(), -F::one())
+            });
+        };
+        let logic = |composer: &mut StandardComposer<F, P>| {
+            let a = composer.add_input(F::from(3u64));
+            let b = composer.add_input(F::from(5u64));
+            composer.xor_gate(a, b, 8);
+        };
+        let keys_and_proofs =
+            [&arithmetic as &dyn Fn(&mut StandardComposer<F, P>), &logic].map(
+                |gadget| {
+                    let mut prover = Prover::<F, P, PC>::new(b"schema");
+                    gadget(prover.mut_cs());
+                    let public_inputs = prover.cs.construct_dense_pi_vec();
+                    let proof = prover.prove(&ck).unwrap();
+
+                    let mut verifier = Verifier::<F, P, PC>::new(b"schema");
+                    gadget(verifier.mut_cs());
+                    verifier.preprocess(&ck).unwrap();
+                    assert!(verifier
+                        .verify(&proof, &vk, &public_inputs)
+                        .is_ok());
+                    (verifier.verifier_key.unwrap(), proof)
+                },
+            );
+        let [(arithmetic_key, arithmetic_proof), (logic_key, logic_proof)] =
+            keys_and_proofs;
+
+        // The schema depends on the gates of the circuit, and matches the
+        // evaluations of its proofs.
+        let arithmetic_schema = arithmetic_key.evaluations_schema();
+        let logic_schema = logic_key.evaluations_schema();
+        assert!(!arithmetic_schema.custom_labels().contains(&"q_c_eval"));
+        assert!(logic_schema.custom_labels().contains(&"q_c_eval"));
+        for (schema, proof) in [
+            (&arithmetic_schema, &arithmetic_proof),
+            (&logic_schema, &logic_proof),
+        ] {
+            let evaluations = proof.evaluations();
+            let bytes = schema.to_compact_bytes(evaluations).unwrap();
+            let read = schema.from_compact_bytes::<F>(&bytes).unwrap();
+            assert_eq!(read.to_flat_vec(), evaluations.to_flat_vec());
+            assert_eq!(read.custom_labels(), evaluations.custom_labels());
+        }
+
+        // Proofs do not match the schema of another circuit.
+        assert!(matches!(
+            logic_schema.to_compact_bytes(arithmetic_proof.evaluations()),
+            Err(Error::EvaluationsSchemaMismatch)
+        ));
+        assert!(matches!(
+            arithmetic_schema.to_compact_bytes(logic_proof.evaluations()),
+            Err(Error::EvaluationsSchemaMismatch)
+        ));
+    }
+
+    fn test_verifier_key_hash<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        // Builds the key of the same circuit from a setup seeded with `rng`.
+        let key_from_setup = |rng: &mut StdRng| {
+            let u_params = PC::setup(2 * 30, None, rng).unwrap();
+            let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+            let mut verifier = Verifier::<F, P, PC>::new(b"hash");
+            let composer = verifier.mut_cs();
+            let a = composer.add_input(F::from(3u64));
+            let b = composer.add_input(F::from(5u64));
+            composer.arithmetic_gate(|gate| {
+                gate.witness(a, b, None)
+                    .mul(F::one())
+                    .add(F::one(), F::one())
+            });
+            composer.range_gate(a, 8);
+            verifier.preprocess(&ck).unwrap();
+            (ck, verifier.verifier_key.unwrap())
+        };
+        let (ck, verifier_key) = key_from_setup(&mut test_rng());
+
+        // The same seeded setup gives the same key, and so the same digest.
+        let (_, same_key) = key_from_setup(&mut test_rng());
+        assert_eq!(
+            same_key.hash::<Keccak256>(HashEndianness::Big).unwrap(),
+            verifier_key.hash::<Keccak256>(HashEndianness::Big).unwrap()
+        );
+
+        let mut bytes = Vec::new();
+        verifier_key.serialize(&mut bytes).unwrap();
+        let hash = verifier_key
+            .hash::<Keccak256>(HashEndianness::default())
+            .unwrap();
+        assert_eq!(hash, Keccak256::digest(&bytes));
+        let mut reversed = hash;
+        reversed.reverse();
+        assert_eq!(
+            verifier_key
+                .hash::<Keccak256>(HashEndianness::Little)
+                .unwrap(),
+            reversed
+        );
+
+        // Changing any selector commitment changes the hash.
+        let poly = DensePolynomial::rand(8, &mut OsRng);
+        let other = PC::batch_commit(&ck, &[poly]).unwrap().remove(0);
+        for i in 0..8 {
+            let mut altered = verifier_key.clone();
+            let selector = match i {
+                0 => &mut altered.arithmetic.q_m,
+                1 => &mut altered.arithmetic.q_l,
+                2 => &mut altered.arithmetic.q_r,
+                3 => &mut altered.arithmetic.q_o,
+                4 => &mut altered.arithmetic.q_4,
+                5 => &mut altered.arithmetic.q_c,
+                6 => &mut altered.arithmetic.q_arith,
+                _ => altered.range_selector_commitment.as_mut().unwrap(),
+            };
+            *selector = other.clone();
+            assert_ne!(
+                altered.hash::<Keccak256>(HashEndianness::Big).unwrap(),
+                hash
+            );
+        }
+        let mut altered = verifier_key.clone();
+        altered.logic_selector_commitment = Some(other);
+        assert_ne!(
+            altered.hash::<Keccak256>(HashEndianness::Big).unwrap(),
+            hash
+        );
+    }
+
+    /// Clones a key with no other bound on `PC` than the commitment scheme
+    /// itself, so that neither `PC` nor its opening proofs need to be
+    /// `Clone`.
+    fn clone_key<F, PC>(key: &VerifierKey<F, PC>) -> VerifierKey<F, PC>
+    where
+        F: PrimeField,
+        PC: HomomorphicCommitment<F>,
+    {
+        key.clone()
+    }
+
+    fn test_clone_verifier_key<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+        PC::Commitment: PartialEq,
+    {
+        let commitment = PC::Commitment::default;
+        let verifier_key = VerifierKey::<F, PC>::from_polynomial_commitments(
+            32,
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+            Some(commitment()),
+            None,
+            None,
+            Some(commitment()),
+            commitment(),
+            commitment(),
+            commitment(),
+            commitment(),
+        )
+        .unwrap();
+
+        assert!(clone_key(&verifier_key) == verifier_key);
+    }
+
+    fn test_matches_verifier_key<F, P, PC>()
+    where
+        F: PrimeField,
+        P: TEModelParameters<BaseField = F>,
+        PC: HomomorphicCommitment<F>,
+    {
+        fn gadget<F, P>(composer: &mut StandardComposer<F, P>, q_l: F)
+        where
+            F: PrimeField,
+            P: TEModelParameters<BaseField = F>,
+        {
+            let a = composer.add_input(F::from(3u64));
+            let b = composer.add_input(F::from(5u64));
+            composer.arithmetic_gate(|gate| {
+                gate.witness(a, b, None).add(q_l, F::one())
+            });
+            composer.range_gate(a, 8);
+        }
+
+        let u_params = PC::setup(2 * 30, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&u_params, 2 * 16, 0, None).unwrap();
+
+        let mut prover = Prover::<F, P, PC>::new(b"matches");
+        gadget(prover.mut_cs(), F::one());
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        let verifier_key = |q_l: F| {
+            let mut verifier = Verifier::<F, P, PC>::new(b"matches");
+            gadget(verifier.mut_cs(), q_l);
+            verifier.preprocess(&ck).unwrap();
+            verifier.verifier_key.unwrap()
+        };
+
+        // Keys of the same circuit match
+        assert!(prover_key
+            .matches_verifier_key(&ck, &verifier_key(F::one()))
+            .unwrap());
+
+        // A single differing selector is caught
+        assert!(!prover_key
+            .matches_verifier_key(&ck, &verifier_key(F::from(2u64)))
+            .unwrap());
+    }
+
     // Test for Bls12_381
     batch_test!(
-        [test_serialise_deserialise_verifier_key],
+        [
+            test_serialise_deserialise_verifier_key,
+            test_verifier_key_diff,
+            test_matches_verifier_key,
+            test_validate_gate_coverage,
+            test_evaluations_schema,
+            test_verifier_key_hash,
+            test_clone_verifier_key
+        ],
         [] => (
             Bls12_381, ark_ed_on_bls12_381::EdwardsParameters      )
     );
 
     // Test for Bls12_377
     batch_test!(
-        [test_serialise_deserialise_verifier_key],
+        [
+            test_serialise_deserialise_verifier_key,
+            test_verifier_key_diff,
+            test_matches_verifier_key,
+            test_validate_gate_coverage,
+            test_evaluations_schema,
+            test_verifier_key_hash,
+            test_clone_verifier_key
+        ],
         [] => (
             Bls12_377, ark_ed_on_bls12_377::EdwardsParameters       )
     );
@@ -113,3 +113,94 @@ macro_rules! batch_test {
         }
     }
 }
+
+/// Span created while a [`SpanRecorder`] is the default subscriber, with the
+/// values of its unsigned fields.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Debug)]
+pub(crate) struct RecordedSpan {
+    /// Name of the span.
+    pub name: &'static str,
+
+    /// Names and values of the unsigned fields of the span.
+    pub fields: Vec<(&'static str, u64)>,
+}
+
+#[cfg(feature = "tracing")]
+impl RecordedSpan {
+    /// Returns the value of the field `name` of the span, or `0` if it has
+    /// none.
+    pub fn field(&self, name: &str) -> u64 {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map_or(0, |(_, value)| *value)
+    }
+}
+
+/// Records the spans created while it is the default subscriber.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Default)]
+pub(crate) struct SpanRecorder(
+    std::sync::Arc<std::sync::Mutex<Vec<RecordedSpan>>>,
+);
+
+#[cfg(feature = "tracing")]
+impl SpanRecorder {
+    /// Runs `f` with the recorder as the default subscriber, returning the
+    /// spans created by `f` in order, together with its result.
+    pub fn record<T>(f: impl FnOnce() -> T) -> (Vec<RecordedSpan>, T) {
+        let recorder = Self::default();
+        let result = tracing::subscriber::with_default(recorder.clone(), f);
+        let spans = recorder.0.lock().unwrap().clone();
+        (spans, result)
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(
+        &self,
+        span: &tracing::span::Attributes<'_>,
+    ) -> tracing::span::Id {
+        struct Fields(Vec<(&'static str, u64)>);
+        impl tracing::field::Visit for Fields {
+            fn record_u64(&mut self, field: &tracing::field::Field, v: u64) {
+                self.0.push((field.name(), v));
+            }
+            fn record_debug(
+                &mut self,
+                _: &tracing::field::Field,
+                _: &dyn core::fmt::Debug,
+            ) {
+            }
+        }
+        let mut fields = Fields(Vec::new());
+        span.record(&mut fields);
+        let mut spans = self.0.lock().unwrap();
+        spans.push(RecordedSpan {
+            name: span.metadata().name(),
+            fields: fields.0,
+        });
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(
+        &self,
+        _: &tracing::span::Id,
+        _: &tracing::span::Id,
+    ) {
+    }
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
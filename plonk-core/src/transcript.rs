@@ -20,8 +20,48 @@ pub(crate) trait TranscriptProtocol {
     /// Compute a `label`ed challenge variable.
     fn challenge_scalar<F: PrimeField>(&mut self, label: &'static [u8]) -> F;
 
+    /// Compute several `labels`ed challenge variables at once, storing the
+    /// challenge of `labels[i]` in `out[i]`.
+    ///
+    /// Transcripts able to draw several field elements at a time, like a
+    /// sponge squeezing them out of a single permutation, override this. By
+    /// default the challenges are drawn one by one, in order, with
+    /// [`TranscriptProtocol::challenge_scalar`].
+    ///
+    /// # Panics
+    /// Panics if `labels` and `out` have different lengths.
+    fn challenge_scalars<F: PrimeField>(
+        &mut self,
+        labels: &[&'static [u8]],
+        out: &mut [F],
+    ) {
+        assert_eq!(labels.len(), out.len(), "one challenge is drawn per label");
+        for (label, challenge) in labels.iter().zip(out) {
+            *challenge = self.challenge_scalar(label);
+        }
+    }
+
     /// Append domain separator for the circuit size.
     fn circuit_domain_sep(&mut self, n: u64);
+
+    /// Append the application `context` a proof is bound to. Nothing is
+    /// appended for an empty `context`.
+    fn append_context(&mut self, context: &[u8]);
+
+    /// Append the auxiliary data a proof is bound to, after the witness
+    /// commitments. Nothing is appended for empty `aux_data`.
+    fn append_aux_data(&mut self, aux_data: &[u8]);
+
+    /// Append the `version` of the proof being created or verified, before
+    /// the witness commitments.
+    fn append_proof_version(&mut self, version: u8);
+
+    /// Append the identifier of the circuit the transcript is used for.
+    fn append_circuit_id(&mut self, circuit_id: &[u8]);
+
+    /// Draw the context binding the next proof of a chain of proofs to this
+    /// transcript.
+    fn chain_link(&mut self) -> [u8; 32];
 }
 
 impl TranscriptProtocol for Transcript {
@@ -35,16 +75,144 @@ impl TranscriptProtocol for Transcript {
     where
         F: PrimeField,
     {
-        // XXX: review this: assure from_random_bytes returnes a valid Field
-        // element
+        // Fewer bytes than the modulus are drawn, so they always represent a
+        // valid field element.
         let size = F::size_in_bits() / 8;
         let mut buf = vec![0u8; size];
         self.challenge_bytes(label, &mut buf);
-        F::from_random_bytes(&buf).unwrap()
+        #[cfg(test)]
+        if let Some(challenge) = mock::forced_challenge(label) {
+            return challenge;
+        }
+        F::from_random_bytes(&buf)
+            .expect("challenge bytes are always smaller than the modulus")
     }
 
     fn circuit_domain_sep(&mut self, n: u64) {
         self.append_message(b"dom-sep", b"circuit_size");
         self.append_u64(b"n", n);
     }
+
+    fn append_context(&mut self, context: &[u8]) {
+        if !context.is_empty() {
+            self.append_message(b"context", context);
+        }
+    }
+
+    fn append_aux_data(&mut self, aux_data: &[u8]) {
+        if !aux_data.is_empty() {
+            self.append_message(b"aux_data", aux_data);
+        }
+    }
+
+    fn append_proof_version(&mut self, version: u8) {
+        self.append_message(b"proof_version", &[version]);
+    }
+
+    fn append_circuit_id(&mut self, circuit_id: &[u8]) {
+        self.append_message(b"dom-sep", b"circuit_id");
+        self.append_message(b"circuit_id", circuit_id);
+    }
+
+    fn chain_link(&mut self) -> [u8; 32] {
+        let mut link = [0u8; 32];
+        self.challenge_bytes(b"chain_link", &mut link);
+        link
+    }
+}
+
+/// Challenges forced on the transcripts, standing in for a transcript whose
+/// challenges the tests control.
+#[cfg(test)]
+pub(crate) mod mock {
+    use ark_ff::PrimeField;
+    use core::cell::RefCell;
+
+    thread_local! {
+        static FORCED: RefCell<Vec<(&'static [u8], Vec<u8>)>> =
+            RefCell::new(Vec::new());
+    }
+
+    /// Runs `f` with every challenge labeled `label` drawn by a transcript
+    /// of the current thread replaced by `challenge`.
+    ///
+    /// The transcript state still absorbs the bytes of the replaced
+    /// challenge, so only the returned value changes.
+    pub(crate) fn with_challenge<F, T>(
+        label: &'static [u8],
+        challenge: F,
+        f: impl FnOnce() -> T,
+    ) -> T
+    where
+        F: PrimeField,
+    {
+        let mut bytes = Vec::new();
+        challenge.serialize(&mut bytes).unwrap();
+        FORCED.with(|forced| forced.borrow_mut().push((label, bytes)));
+        let result = f();
+        FORCED.with(|forced| forced.borrow_mut().pop());
+        result
+    }
+
+    /// Returns the challenge forced for `label`, if any.
+    pub(super) fn forced_challenge<F>(label: &[u8]) -> Option<F>
+    where
+        F: PrimeField,
+    {
+        FORCED.with(|forced| {
+            forced
+                .borrow()
+                .iter()
+                .rev()
+                .find(|(forced_label, _)| *forced_label == label)
+                .map(|(_, bytes)| F::deserialize(bytes.as_slice()).unwrap())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_field;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    fn test_batched_challenges<F>()
+    where
+        F: PrimeField,
+    {
+        let labels: [&[u8]; 3] = [b"alpha", b"beta", b"alpha"];
+        let mut sequential = Transcript::new(b"challenges");
+        let mut batched = sequential.clone();
+        sequential.append(b"item", &F::from(7u64));
+        batched.append(b"item", &F::from(7u64));
+
+        let expected =
+            labels.map(|label| sequential.challenge_scalar::<F>(label));
+        let mut challenges = [F::zero(); 3];
+        batched.challenge_scalars(&labels, &mut challenges);
+        assert_eq!(challenges, expected);
+
+        // Both transcripts are left in the same state.
+        assert_eq!(
+            batched.challenge_scalar::<F>(b"next"),
+            sequential.challenge_scalar::<F>(b"next")
+        );
+    }
+
+    // Tests for Bls12_381
+    batch_test_field!(
+        [test_batched_challenges],
+        [] => (
+            Bls12_381
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test_field!(
+        [test_batched_challenges],
+        [] => (
+            Bls12_377
+        )
+    );
 }
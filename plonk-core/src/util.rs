@@ -4,8 +4,11 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use crate::error::Error;
 use ark_ec::{ModelParameters, TEModelParameters};
-use ark_ff::{BigInteger, FftField, Field, FpParameters, PrimeField};
+use ark_ff::{
+    BigInteger, FftField, FftParameters, Field, FpParameters, PrimeField,
+};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 
 /// Returns an iterator over increasing powers of the given `scalar` starting
@@ -86,6 +89,81 @@ where
     }
 }
 
+/// Builds the evaluation domain of `size` elements, rounded up to the next
+/// power of two.
+///
+/// The prover and verifier rely on the structure of radix-2 domains, so the
+/// mixed-radix domains `ark-poly` falls back to for fields with a small
+/// subgroup are rejected with [`Error::UnsupportedDomainType`].
+pub(crate) fn radix2_domain<F>(
+    size: usize,
+) -> Result<GeneralEvaluationDomain<F>, Error>
+where
+    F: FftField,
+{
+    let domain = GeneralEvaluationDomain::new(size).ok_or(
+        Error::InvalidEvalDomainSize {
+            log_size_of_group: size.trailing_zeros(),
+            adicity: <F::FftParams as FftParameters>::TWO_ADICITY,
+        },
+    )?;
+    ensure_radix2(domain)
+}
+
+/// Builds the evaluation domain of a circuit key, whose size `n` must be a
+/// power of two.
+///
+/// Unlike [`radix2_domain`], the size is not rounded up, so that a key with a
+/// corrupted size is rejected with [`Error::NonPowerOfTwoDomain`] instead of
+/// being read over a domain of another size.
+pub(crate) fn key_domain<F>(
+    n: usize,
+) -> Result<GeneralEvaluationDomain<F>, Error>
+where
+    F: FftField,
+{
+    if !n.is_power_of_two() {
+        return Err(Error::NonPowerOfTwoDomain { n });
+    }
+    radix2_domain(n)
+}
+
+/// Returns `point * omega`, the point at which the openings of the next
+/// row are checked, where `omega` is the generator of `domain`.
+///
+/// The permutation argument steps from one row to the next by
+/// [`EvaluationDomainExt::group_gen`], so that the shifted openings must use
+/// the same generator. `ark-poly` computes `domain.element(1)` as
+/// `group_gen` for both radix-2 and mixed-radix domains, which is asserted in
+/// debug builds.
+pub(crate) fn shifted_point<F>(
+    domain: &GeneralEvaluationDomain<F>,
+    point: F,
+) -> F
+where
+    F: FftField,
+{
+    let omega = domain.group_gen();
+    debug_assert_eq!(domain.element(1), omega);
+    point * omega
+}
+
+/// Returns `domain` if it is a radix-2 domain, and
+/// [`Error::UnsupportedDomainType`] otherwise.
+fn ensure_radix2<F>(
+    domain: GeneralEvaluationDomain<F>,
+) -> Result<GeneralEvaluationDomain<F>, Error>
+where
+    F: FftField,
+{
+    match domain {
+        GeneralEvaluationDomain::Radix2(_) => Ok(domain),
+        GeneralEvaluationDomain::MixedRadix(_) => {
+            Err(Error::UnsupportedDomainType)
+        }
+    }
+}
+
 /// Get a pairing friendly curve scalar `E::Fr` from a scalar of the embedded
 /// curve. Panics if the embedded scalar is greater than the modulus of the
 /// pairing firendly curve scalar field
@@ -183,3 +261,133 @@ macro_rules! get_label {
         stringify!($comm).to_owned()
     };
 }
+
+/// Enters a `tracing` span named after the proving or verification `$phase`,
+/// recording the `$domain_size` of the circuit, until the end of the
+/// enclosing scope. Expands to nothing without the `tracing` feature.
+macro_rules! phase_span {
+    ($phase:literal, $domain_size:expr) => {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!($phase, domain_size = $domain_size).entered();
+    };
+}
+
+pub(crate) use phase_span;
+
+/// Enters a `tracing` span around a single check of a batch opening proof
+/// with `PC::check`, recording the number of `$polynomials` it opens, until
+/// the end of the enclosing scope. Expands to nothing without the `tracing`
+/// feature.
+macro_rules! check_span {
+    ($polynomials:expr) => {
+        #[cfg(feature = "tracing")]
+        let _check =
+            tracing::info_span!("PC::check", polynomials = $polynomials as u64)
+                .entered();
+    };
+}
+
+pub(crate) use check_span;
+
+/// Enters a `tracing` span recording the number of terms `$msm_terms` of the
+/// multi-scalar multiplications over the group of the commitments and the
+/// number of `$pairings` computed until the end of the enclosing scope.
+/// Expands to nothing without the `tracing` feature.
+macro_rules! group_ops_span {
+    ($msm_terms:expr, $pairings:expr) => {
+        #[cfg(feature = "tracing")]
+        let _group_ops = tracing::trace_span!(
+            "group_ops",
+            msm_terms = $msm_terms as u64,
+            pairings = $pairings as u64
+        )
+        .entered();
+    };
+}
+
+pub(crate) use group_ops_span;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test_field;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_poly::domain::MixedRadixEvaluationDomain;
+
+    fn test_radix2_domain<F>()
+    where
+        F: PrimeField,
+    {
+        let domain = radix2_domain::<F>(5).unwrap();
+        assert!(matches!(domain, GeneralEvaluationDomain::Radix2(_)));
+        assert_eq!(domain.size(), 8);
+
+        // None of the fields supported by this crate has a small subgroup, so
+        // sizes beyond their two-adicity cannot be built at all.
+        let adicity = <F::FftParams as FftParameters>::TWO_ADICITY;
+        assert!(matches!(
+            radix2_domain::<F>(1 << (adicity + 1)),
+            Err(Error::InvalidEvalDomainSize { .. })
+        ));
+    }
+
+    fn test_mixed_radix_domain_rejected<F>()
+    where
+        F: PrimeField,
+    {
+        // `ark-poly` only builds mixed-radix domains for fields with a small
+        // subgroup, so a size `2^2 * 3` domain is put together by hand.
+        let size = 12u64;
+        let size_as_field_element = F::from(size);
+        let group_gen = F::get_root_of_unity(4).unwrap();
+        let domain =
+            GeneralEvaluationDomain::MixedRadix(MixedRadixEvaluationDomain {
+                size,
+                log_size_of_group: 2,
+                size_as_field_element,
+                size_inv: size_as_field_element.inverse().unwrap(),
+                group_gen,
+                group_gen_inv: group_gen.inverse().unwrap(),
+                generator_inv: F::multiplicative_generator().inverse().unwrap(),
+            });
+        assert!(matches!(
+            ensure_radix2(domain),
+            Err(Error::UnsupportedDomainType)
+        ));
+    }
+
+    fn test_shifted_point<F>()
+    where
+        F: PrimeField,
+    {
+        let point = F::from(7u64);
+        for size in [1, 2, 8, 64, 1 << 10] {
+            let domain = radix2_domain::<F>(size).unwrap();
+            let shifted = shifted_point(&domain, point);
+            assert_eq!(shifted, point * domain.group_gen());
+            assert_eq!(shifted, point * domain.element(1));
+            // Stepping `size` times from a point comes back to it.
+            let back = (0..size).fold(point, |p, _| shifted_point(&domain, p));
+            assert_eq!(back, point);
+        }
+    }
+
+    batch_test_field!(
+        [
+            test_radix2_domain,
+            test_mixed_radix_domain_rejected,
+            test_shifted_point
+        ],
+        [] => (Bls12_381)
+    );
+    batch_test_field!(
+        [
+            test_radix2_domain,
+            test_mixed_radix_domain_rejected,
+            test_shifted_point
+        ],
+        [] => (Bls12_377)
+    );
+}
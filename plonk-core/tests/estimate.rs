@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) ZK-GARAGE. All rights reserved.
+
+//! Comparison of the memory estimate of [`estimate_resources`] with the peak
+//! memory of an actual proof.
+//!
+//! The memory is measured with a counting global allocator, which is why
+//! this test lives in its own binary holding no other test: all of the
+//! allocations of the process, including the ones of the `rayon` workers,
+//! are counted.
+
+use ark_bls12_381::{Bls12_381, Fr as BlsScalar};
+use ark_ed_on_bls12_381::EdwardsParameters as JubJubParameters;
+use ark_ff::One;
+use ark_poly_commit::PolynomialCommitment;
+use core::sync::atomic::{AtomicIsize, Ordering};
+use plonk_core::commitment::KZG10;
+use plonk_core::proof_system::{estimate_resources, Prover};
+use rand::rngs::OsRng;
+use std::alloc::{GlobalAlloc, Layout, System};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Bytes currently allocated by the process.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+
+/// Most bytes allocated at once by the process since the last call to
+/// [`measure_peak_memory`].
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// [`System`] allocator counting the bytes allocated by the process.
+struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Adds `bytes` to the bytes allocated by the process.
+    fn track(bytes: isize) {
+        let current = CURRENT.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        PEAK.fetch_max(current, Ordering::SeqCst);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::track(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::track(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f`, returning the most bytes it kept allocated at once, together
+/// with its result.
+fn measure_peak_memory<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let start = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(start, Ordering::SeqCst);
+    let result = f();
+    let peak = PEAK.load(Ordering::SeqCst);
+    ((peak - start) as usize, result)
+}
+
+#[test]
+fn test_estimate_memory() {
+    let mut prover =
+        Prover::<BlsScalar, JubJubParameters, KZG10<Bls12_381>>::new(
+            b"estimate",
+        );
+    let composer = prover.mut_cs();
+    let one = composer.add_input(BlsScalar::one());
+    for _ in 0..100 {
+        composer.arithmetic_gate(|gate| {
+            gate.witness(one, one, None)
+                .add(BlsScalar::one(), BlsScalar::one())
+        });
+    }
+    let estimate = estimate_resources::<BlsScalar>(prover.circuit_size());
+
+    let u_params =
+        KZG10::<Bls12_381>::setup(2 * estimate.domain_size, None, &mut OsRng)
+            .unwrap();
+    let (ck, _) =
+        KZG10::<Bls12_381>::trim(&u_params, estimate.domain_size, 0, None)
+            .unwrap();
+
+    // Measure an actual run, from the preprocessing to the proof.
+    let (peak_memory_bytes, proof) = measure_peak_memory(|| prover.prove(&ck));
+    assert!(proof.is_ok());
+    assert!(estimate.peak_memory_bytes <= 4 * peak_memory_bytes);
+    assert!(peak_memory_bytes <= 4 * estimate.peak_memory_bytes);
+}